@@ -0,0 +1,224 @@
+use crate::{tool::ToolResult, StateData};
+
+use sha2::{Digest, Sha256};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Deserialize, Serialize)]
+pub enum SuppressionKind {
+    /// The result was reviewed and dismissed as a false positive.
+    FalsePositive,
+
+    /// The result was merged into an existing finding.
+    Merged { finding_id: usize }
+}
+
+/// A fingerprinted tool result which later imports should not turn into a new draft finding.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct Suppression {
+    pub fingerprint: String,
+    pub tool: String,
+    pub rule: String,
+    pub file: String,
+    pub kind: SuppressionKind
+}
+
+impl Suppression {
+    pub fn description(&self) -> String {
+        match self.kind {
+            SuppressionKind::FalsePositive => "False positive".to_string(),
+            SuppressionKind::Merged { finding_id } => format!("Merged into finding {}", finding_id)
+        }
+    }
+}
+
+/// What a later import should do with a tool result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Disposition {
+    New,
+    Suppressed,
+    Linked(usize)
+}
+
+/// Returns the file a result is fingerprinted against, which is the first file it reports.
+pub fn primary_file(result: &ToolResult) -> &str {
+    result.locations.first().map(|location| location.file.as_str()).unwrap_or("")
+}
+
+/// Fingerprints a tool result by its rule, primary file and normalized snippet, so that it
+/// remains stable when the surrounding code moves between analyzer runs.
+pub fn fingerprint(result: &ToolResult) -> String {
+    let snippet = match result.snippet {
+        Some(ref snippet) => normalize_whitespace(snippet.as_str()),
+        None => normalize_whitespace(strip_line_references(result.description.as_str()).as_str())
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(result.tool.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(result.rule.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(primary_file(result).as_bytes());
+    hasher.update(&[0]);
+    hasher.update(snippet.as_bytes());
+
+    format!("{:x}", hasher.finalize())
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Removes line references such as `#L10-L20`, `#10-20` and `L42` which change whenever code moves.
+fn strip_line_references(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let starts_reference = (c == '#' || c == 'L')
+            && (index == 0 || !chars[index - 1].is_alphanumeric() || c == '#');
+
+        if starts_reference {
+            let mut end = index + 1;
+
+            if c == '#' && chars.get(end) == Some(&'L') {
+                end += 1;
+            }
+
+            if chars.get(end).map_or(false, |c| c.is_ascii_digit()) {
+                while end < chars.len() && (chars[end].is_ascii_digit() || chars[end] == '-' || chars[end] == 'L') {
+                    end += 1;
+                }
+
+                // A reference ends the word, unlike the start of a name such as `L1Bridge`
+                if !chars.get(end).is_some_and(|c| c.is_alphanumeric() || *c == '_') {
+                    index = end;
+                    continue;
+                }
+            }
+        }
+
+        result.push(c);
+        index += 1;
+    }
+
+    result
+}
+
+/// Decides whether a fingerprinted result is new, suppressed by the baseline or already tracked by a finding.
+pub fn disposition(state: &StateData, fingerprint: &str) -> Disposition {
+    let suppression = state.baseline.iter().find(|suppression| suppression.fingerprint == fingerprint);
+
+    match suppression.map(|suppression| suppression.kind) {
+        Some(SuppressionKind::FalsePositive) => Disposition::Suppressed,
+
+        // A finding which has since been removed takes its merged results with it
+        Some(SuppressionKind::Merged { finding_id }) if state.findings.contains_key(&finding_id) => Disposition::Linked(finding_id),
+        Some(SuppressionKind::Merged { .. }) => Disposition::Suppressed,
+
+        None => match state.findings.values().find(|finding| finding.fingerprints.iter().any(|existing| existing == fingerprint)) {
+            Some(finding) => Disposition::Linked(finding.id),
+            None => Disposition::New
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::report::{Finding, Location};
+
+    fn result(description: &str, file: &str, lines: Vec<usize>) -> ToolResult {
+        ToolResult {
+            tool: "Slither".to_string(),
+            rule: "reentrancy-eth".to_string(),
+            title: "Reentrancy Eth".to_string(),
+            class: "Volatile Code".to_string(),
+            severity: None,
+            impact: None,
+            description: description.to_string(),
+            locations: vec![Location::new(file.to_string(), lines)],
+            snippet: None
+        }
+    }
+
+    #[test]
+    fn strip_line_references_removes_anchors_and_line_numbers() {
+        assert_eq!(strip_line_references("Reentrancy in [withdraw](contracts/Vault.sol#L10-L20)"), "Reentrancy in [withdraw](contracts/Vault.sol)");
+        assert_eq!(strip_line_references("Vault.fee (contracts/Vault.sol#8-9) should be constant"), "Vault.fee (contracts/Vault.sol) should be constant");
+        assert_eq!(strip_line_references("See L42 and L3-L7."), "See  and .");
+    }
+
+    #[test]
+    fn strip_line_references_keeps_other_text() {
+        // A standalone `L2` reads the same as a line reference and is removed, which keeps the fingerprint stable all the same
+        assert_eq!(strip_line_references("Bridged to L2 by the relayer"), "Bridged to  by the relayer");
+
+        // Letters inside words and anchors without line numbers are kept
+        assert_eq!(strip_line_references("HTML5 and SHA256 in the L1Bridge"), "HTML5 and SHA256 in the L1Bridge");
+        assert_eq!(strip_line_references("Written in C# with #Lambda and issue#fix"), "Written in C# with #Lambda and issue#fix");
+    }
+
+    #[test]
+    fn moved_code_keeps_its_fingerprint() {
+        let before = result("Reentrancy in withdraw (contracts/Vault.sol#L40-L42)", "contracts/Vault.sol", vec![40, 41, 42]);
+        let after = result("Reentrancy in  withdraw\n(contracts/Vault.sol#L52-L54)", "contracts/Vault.sol", vec![52, 53, 54]);
+
+        assert_eq!(fingerprint(&before), fingerprint(&after));
+
+        // Another file or rule is another result
+        assert_ne!(fingerprint(&before), fingerprint(&result("Reentrancy in withdraw (contracts/Pool.sol#L40-L42)", "contracts/Pool.sol", vec![40])));
+
+        let mut other_rule = before.clone();
+        other_rule.rule = "reentrancy-no-eth".to_string();
+        assert_ne!(fingerprint(&before), fingerprint(&other_rule));
+    }
+
+    #[test]
+    fn snippets_are_fingerprinted_instead_of_descriptions() {
+        let mut before = result("Found on line 40", "contracts/Vault.sol", vec![40]);
+        let mut after = result("Found on line 52", "contracts/Vault.sol", vec![52]);
+        before.snippet = Some("msg.sender.call{value: amount}(\"\");".to_string());
+        after.snippet = Some("  msg.sender.call{value: amount}(\"\");\n".to_string());
+
+        assert_eq!(fingerprint(&before), fingerprint(&after));
+    }
+
+    fn suppression(fingerprint: &str, kind: SuppressionKind) -> Suppression {
+        Suppression {
+            fingerprint: fingerprint.to_string(),
+            tool: "Slither".to_string(),
+            rule: "reentrancy-eth".to_string(),
+            file: "contracts/Vault.sol".to_string(),
+            kind
+        }
+    }
+
+    #[test]
+    fn disposition_follows_the_baseline_and_findings() {
+        let mut state = StateData::new();
+
+        let mut finding = Finding::new(3);
+        finding.fingerprints = vec!["tracked".to_string()];
+        state.findings.insert(finding.id, finding);
+        state.findings.insert(4, Finding::new(4));
+
+        state.baseline = vec![
+            suppression("dismissed", SuppressionKind::FalsePositive),
+            suppression("merged", SuppressionKind::Merged { finding_id: 4 }),
+            suppression("merged_into_removed", SuppressionKind::Merged { finding_id: 9 })
+        ];
+
+        assert_eq!(disposition(&state, "dismissed"), Disposition::Suppressed);
+        assert_eq!(disposition(&state, "merged"), Disposition::Linked(4));
+        assert_eq!(disposition(&state, "tracked"), Disposition::Linked(3));
+        assert_eq!(disposition(&state, "unseen"), Disposition::New);
+
+        // Results merged into a finding which was removed since are not brought back as drafts
+        assert_eq!(disposition(&state, "merged_into_removed"), Disposition::Suppressed);
+
+        state.findings.remove(&4);
+        assert_eq!(disposition(&state, "merged"), Disposition::Suppressed);
+    }
+}
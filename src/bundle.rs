@@ -0,0 +1,158 @@
+use crate::report::Report;
+
+use std::io::{Cursor, Read, Write};
+
+/// The workbook inside a bundle, with the attachment contents stored as separate files.
+pub const MANIFEST_FILE: &str = "workbook.json";
+
+pub const ATTACHMENTS_DIRECTORY: &str = "attachments";
+
+fn attachment_path(finding_id: usize, name: &str) -> String {
+    format!("{}/{}/{}", ATTACHMENTS_DIRECTORY, finding_id, name)
+}
+
+/// Returns true if the bytes start with the signature of a zip archive.
+pub fn is_bundle(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"PK\x03\x04")
+}
+
+/// Writes the workbook as a zip bundle holding a JSON manifest and a file for each attachment.
+pub fn write(report: &Report) -> Result<Vec<u8>, String> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut manifest = report.clone();
+
+    for finding in manifest.findings.iter_mut() {
+        for attachment in finding.attachments.iter_mut() {
+            writer.start_file(attachment_path(finding.id, attachment.name.as_str()), options).map_err(|error| error.to_string())?;
+            writer.write_all(&attachment.data).map_err(|error| error.to_string())?;
+
+            // The manifest only lists the attachments, which are omitted from the JSON once empty
+            attachment.data.clear();
+        }
+    }
+
+    let json = serde_json::to_string_pretty(&manifest).map_err(|error| error.to_string())?;
+
+    writer.start_file(MANIFEST_FILE, options).map_err(|error| error.to_string())?;
+    writer.write_all(json.as_bytes()).map_err(|error| error.to_string())?;
+
+    let cursor = writer.finish().map_err(|error| error.to_string())?;
+    Ok(cursor.into_inner())
+}
+
+/// Reads a workbook bundle, loading the attachments listed in its manifest.
+pub fn read(bytes: &[u8]) -> Result<Report, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|error| error.to_string())?;
+
+    let mut json = String::new();
+
+    archive.by_name(MANIFEST_FILE)
+        .map_err(|_| format!("The bundle has no {}", MANIFEST_FILE))?
+        .read_to_string(&mut json)
+        .map_err(|error| error.to_string())?;
+
+    let mut report: Report = serde_json::from_str(json.as_str()).map_err(|error| error.to_string())?;
+
+    for finding in report.findings.iter_mut() {
+        for attachment in finding.attachments.iter_mut() {
+            let path = attachment_path(finding.id, attachment.name.as_str());
+
+            let mut file = archive.by_name(path.as_str())
+                .map_err(|_| format!("The bundle is missing the attachment {}", path))?;
+
+            attachment.data.clear();
+            file.read_to_end(&mut attachment.data).map_err(|error| error.to_string())?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Parses a workbook from either a plain JSON file or a bundle.
+pub fn parse_workbook(bytes: &[u8]) -> Result<Report, String> {
+    if is_bundle(bytes) {
+        read(bytes)
+    } else {
+        serde_json::from_slice(bytes).map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::report::{Attachment, Finding};
+
+    fn report() -> Report {
+        let mut report: Report = serde_json::from_value(serde_json::json!({
+            "title": "Audit",
+            "auditors": [],
+            "start_time": "",
+            "delivery_time": "",
+            "repository": "",
+            "commit_hashes": [],
+            "checklist": [],
+            "overview": "",
+            "findings": []
+        })).unwrap();
+
+        let mut finding = Finding::new(2);
+        finding.title = "Reentrancy".to_string();
+        finding.attachments = vec![
+            Attachment::new("trace.png", vec![0x89, b'P', b'N', b'G', 0, 255]),
+            Attachment::new("exploit.sol", b"contract Exploit {}\n".to_vec())
+        ];
+        report.findings.push(finding);
+
+        report
+    }
+
+    #[test]
+    fn bundles_round_trip_with_attachments() {
+        let report = report();
+        let bytes = write(&report).unwrap();
+
+        assert!(is_bundle(&bytes));
+        assert_eq!(read(&bytes).unwrap(), report);
+        assert_eq!(parse_workbook(&bytes).unwrap(), report);
+    }
+
+    #[test]
+    fn bundles_store_attachments_outside_the_manifest() {
+        let bytes = write(&report()).unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes.as_slice())).unwrap();
+
+        let mut data = vec![];
+        archive.by_name("attachments/2/trace.png").unwrap().read_to_end(&mut data).unwrap();
+        assert_eq!(data, vec![0x89, b'P', b'N', b'G', 0, 255]);
+
+        let mut json = String::new();
+        archive.by_name(MANIFEST_FILE).unwrap().read_to_string(&mut json).unwrap();
+        assert!(json.contains("trace.png") && !json.contains("\"data\""));
+    }
+
+    #[test]
+    fn reading_reports_missing_attachments() {
+        // A manifest listing an attachment whose file is not in the bundle
+        let mut manifest = report();
+        manifest.findings[0].attachments.truncate(1);
+        manifest.findings[0].attachments[0].data.clear();
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+        writer.start_file(MANIFEST_FILE, zip::write::FileOptions::default()).unwrap();
+        writer.write_all(serde_json::to_string(&manifest).unwrap().as_bytes()).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        assert_eq!(read(&bytes).unwrap_err(), "The bundle is missing the attachment attachments/2/trace.png");
+    }
+
+    #[test]
+    fn plain_workbooks_are_not_bundles() {
+        let json = serde_json::to_vec(&report()).unwrap();
+
+        assert!(!is_bundle(&json));
+        assert_eq!(parse_workbook(&json).unwrap(), report());
+    }
+}
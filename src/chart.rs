@@ -0,0 +1,123 @@
+use crate::{
+    report::{Severity, Status},
+    summary::Summary
+};
+
+use std::f64::consts::PI;
+
+const FONT: &str = "font-family=\"sans-serif\" font-size=\"12\"";
+
+pub fn severity_color(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::Critical) => "#e53935",
+        Some(Severity::Major) => "#fb8c00",
+        Some(Severity::Minor) => "#fdd835",
+        Some(Severity::Informational) => "#43a047",
+        None => "#9e9e9e"
+    }
+}
+
+pub fn status_color(status: Status) -> &'static str {
+    match status {
+        Status::Pending => "#9e9e9e",
+        Status::Acknowledged => "#1e88e5",
+        Status::PartiallyResolved => "#fb8c00",
+        Status::Resolved => "#43a047",
+        Status::Declined => "#e53935"
+    }
+}
+
+/// Builds a donut chart of the number of findings of each severity.
+pub fn severity_donut(summary: &Summary) -> String {
+    let mut segments: Vec<(String, usize, &str)> = Severity::ALL.iter()
+        .zip(summary.severities.iter())
+        .map(|(severity, count)| (count.name.clone(), count.count, severity_color(Some(*severity))))
+        .collect();
+
+    if summary.unrated != 0 {
+        segments.push(("Unrated".to_string(), summary.unrated, severity_color(None)));
+    }
+
+    let (radius, stroke_width, center) = (60.0, 24.0, 80.0);
+    let circumference = 2.0 * PI * radius;
+
+    let mut svg = String::new();
+    svg.push_str(format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"320\" height=\"160\" viewBox=\"0 0 320 160\" {}>\n", FONT).as_str());
+    svg.push_str("<title>Findings by Severity</title>\n");
+    svg.push_str(
+        format!(
+            "<circle cx=\"{c}\" cy=\"{c}\" r=\"{r}\" fill=\"none\" stroke=\"#e0e0e0\" stroke-width=\"{w}\"/>\n",
+            c = center, r = radius, w = stroke_width
+        ).as_str()
+    );
+
+    let mut offset = 0.0;
+
+    for (_, count, color) in segments.iter().filter(|segment| segment.1 != 0) {
+        let length = circumference * (*count as f64) / (summary.total as f64);
+
+        // Each segment is a dashed stroke starting at 12 o'clock and offset by the previous segments
+        svg.push_str(
+            format!(
+                "<circle cx=\"{c}\" cy=\"{c}\" r=\"{r}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"{w}\" stroke-dasharray=\"{len:.3} {rest:.3}\" stroke-dashoffset=\"{offset:.3}\" transform=\"rotate(-90 {c} {c})\"/>\n",
+                c = center, r = radius, w = stroke_width, color = color, len = length, rest = circumference - length, offset = -offset
+            ).as_str()
+        );
+
+        offset += length;
+    }
+
+    svg.push_str(format!("<text x=\"{c}\" y=\"{c}\" text-anchor=\"middle\" font-size=\"24\" dy=\"8\">{}</text>\n", summary.total, c = center).as_str());
+
+    for (index, (name, count, color)) in segments.iter().enumerate() {
+        let y = 30 + index * 22;
+        svg.push_str(format!("<rect x=\"180\" y=\"{}\" width=\"12\" height=\"12\" fill=\"{}\"/>\n", y, color).as_str());
+        svg.push_str(format!("<text x=\"198\" y=\"{}\">{}: {}</text>\n", y + 11, name, count).as_str());
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Builds a stacked bar chart of the number of findings with each status.
+pub fn status_bar(summary: &Summary) -> String {
+    let (x, y, width, height) = (10.0, 20.0, 300.0, 24.0);
+
+    let mut svg = String::new();
+    svg.push_str(format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"320\" height=\"160\" viewBox=\"0 0 320 160\" {}>\n", FONT).as_str());
+    svg.push_str("<title>Findings by Status</title>\n");
+    svg.push_str(format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#e0e0e0\"/>\n", x, y, width, height).as_str());
+
+    let mut offset = 0.0;
+
+    for (status, count) in Status::ALL.iter().zip(summary.statuses.iter()).filter(|(_, count)| count.count != 0) {
+        let length = width * (count.count as f64) / (summary.total as f64);
+
+        svg.push_str(
+            format!(
+                "<rect x=\"{:.3}\" y=\"{}\" width=\"{:.3}\" height=\"{}\" fill=\"{}\"/>\n",
+                x + offset, y, length, height, status_color(*status)
+            ).as_str()
+        );
+
+        offset += length;
+    }
+
+    for (index, (status, count)) in Status::ALL.iter().zip(summary.statuses.iter()).enumerate() {
+        let legend_x = 10 + (index % 2) * 150;
+        let legend_y = 60 + (index / 2) * 22;
+        svg.push_str(format!("<rect x=\"{}\" y=\"{}\" width=\"12\" height=\"12\" fill=\"{}\"/>\n", legend_x, legend_y, status_color(*status)).as_str());
+        svg.push_str(format!("<text x=\"{}\" y=\"{}\">{}: {}</text>\n", legend_x + 18, legend_y + 11, count.name, count.count).as_str());
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Builds a small circular badge used in place of the finding heading image.
+pub fn severity_icon(severity: Option<Severity>) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\" height=\"24\" viewBox=\"0 0 24 24\" style=\"vertical-align: middle; margin-right: 6.5pt\"><circle cx=\"12\" cy=\"12\" r=\"10\" fill=\"{}\"/></svg>",
+        severity_color(severity)
+    )
+}
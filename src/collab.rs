@@ -0,0 +1,626 @@
+use crate::report::Report;
+
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc
+    },
+    thread,
+    time::Duration
+};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+/// The command which delivers session messages to the invoke handler, followed by the message as JSON.
+pub const MESSAGE_COMMAND: &str = "collab_message ";
+
+/// The client id of the hosting instance.
+pub const HOST_ID: usize = 0;
+
+/// Commands which only depend on the workbook, so every instance applying them in the same
+/// order ends up with the same workbook.
+pub const SHARED_COMMANDS: [&str; 36] = [
+    "set_report_field", "set_audited_commit", "set_fix_commit", "set_reaudit_verdict", "remove_suppression",
+    "create_checklist_entry", "remove_checklist_entry", "clear_checklist_entries", "set_checklist_entry_checked",
+    "set_checklist_entry_text", "create_finding", "accept_finding", "suppress_finding", "merge_finding",
+    "remove_finding", "clear_findings", "set_finding_title", "set_finding_type", "set_finding_severity",
+    "set_finding_likelihood", "set_finding_impact", "set_finding_justification", "set_finding_status",
+    "set_finding_location", "set_finding_description", "set_finding_recommendation", "set_finding_alleviation",
+    "set_severity_matrix_cell", "set_finding_order", "remove_attachment", "set_lint_rule",
+    "set_finding_tags", "set_finding_field", "create_custom_field", "remove_custom_field", "set_custom_field_exported"
+];
+
+/// Commands which depend on local files, dialogs or the clipboard. They are run locally to build
+/// the resulting workbook, which is shared as a snapshot and applied once the host has put it in order.
+pub const SNAPSHOT_COMMANDS: [&str; 14] = [
+    "load_workbook", "import_markdown", "import_slither", "import_sarif", "import_baseline", "import_csv_statuses",
+    "paste_finding", "merge_workbooks", "resolve_conflict", "select_repository", "remap_locations",
+    "create_workspace_workbook", "set_workspace_auditors", "add_attachments"
+];
+
+pub fn is_shared_command(command: &str) -> bool {
+    SHARED_COMMANDS.contains(&command)
+}
+
+pub fn is_snapshot_command(command: &str) -> bool {
+    SNAPSHOT_COMMANDS.contains(&command)
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Peer {
+    pub id: usize,
+    pub name: String,
+    pub finding_id: Option<usize>
+}
+
+/// A message of the session protocol, sent as a line of JSON.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum Message {
+    /// Sent by an instance joining the session.
+    Hello { name: String },
+
+    /// Sent by the host to a joining instance with its id and the current workbook.
+    Welcome { client_id: usize, snapshot: Box<Report> },
+
+    /// Asks the host to apply a shared command.
+    Submit { command: String },
+
+    /// Asks the host to replace the workbook, which was built from the operations up to `base_seq`.
+    SubmitSnapshot {
+        #[serde(default)]
+        base_seq: usize,
+        snapshot: Box<Report>
+    },
+
+    /// A shared command, applied by every instance in the order of `seq`.
+    Operation { seq: usize, client_id: usize, command: String },
+
+    /// A replacement workbook, applied by every instance in the order of `seq`.
+    Snapshot { seq: usize, client_id: usize, snapshot: Box<Report> },
+
+    /// Tells the host which finding the instance is editing.
+    Presence { finding_id: Option<usize> },
+
+    /// Sent by the host whenever an instance joins, leaves or moves to another finding.
+    Peers { peers: Vec<Peer> },
+
+    /// Ends the session.
+    Closed { reason: String }
+}
+
+fn write_message(stream: &mut TcpStream, message: &Message) -> Result<(), String> {
+    let mut line = serde_json::to_string(message).map_err(|error| error.to_string())?;
+    line.push('\n');
+
+    stream.write_all(line.as_bytes()).map_err(|error| format!("Failed to send to the session: {}", error))
+}
+
+/// Reads messages from the stream until it is closed. Lines which are not valid messages are skipped.
+fn read_messages(stream: TcpStream, mut on_message: impl FnMut(Message)) {
+    for line in BufReader::new(stream).lines() {
+        match line {
+            Err(_) => break,
+            Ok(line) => {
+                if let Ok(message) = serde_json::from_str(line.as_str()) {
+                    on_message(message);
+                }
+            }
+        }
+    }
+}
+
+/// Writes messages to an instance on a thread of its own, so an instance which stops reading
+/// does not hold up the others. The connection is closed once the sender is dropped.
+fn spawn_writer(mut stream: TcpStream) -> Sender<Message> {
+    let (sender, messages) = mpsc::channel::<Message>();
+
+    thread::spawn(move || {
+        for message in messages {
+            let closed = matches!(message, Message::Closed { .. });
+
+            if write_message(&mut stream, &message).is_err() || closed {
+                break;
+            }
+        }
+
+        let _ = stream.shutdown(Shutdown::Both);
+    });
+
+    sender
+}
+
+/// Passes a message to the invoke handler, which owns the state data, on the web view's thread.
+fn deliver(handle: &web_view::Handle<()>, message: &Message) {
+    let json = match serde_json::to_string(message) {
+        Err(_) => return,
+        Ok(json) => json
+    };
+
+    // A JSON string is also a valid JavaScript string literal
+    let script = match serde_json::to_string(&format!("{}{}", MESSAGE_COMMAND, json)) {
+        Err(_) => return,
+        Ok(argument) => format!("external.invoke({})", argument)
+    };
+
+    let _ = handle.dispatch(move |view| view.eval(script.as_str()));
+}
+
+#[derive(Debug)]
+enum Event {
+    Connected(usize, TcpStream),
+    Received(usize, Message),
+    Disconnected(usize),
+    Shutdown
+}
+
+/// Delivers messages to the instance hosting the session.
+type Deliver = Box<dyn Fn(&Message) + Send>;
+
+/// Orders the operations of every instance and relays them, along with presence, to all instances.
+struct Hub {
+    deliver: Deliver,
+    pending: Vec<(usize, TcpStream)>,
+    clients: Vec<(usize, Sender<Message>)>,
+    peers: Vec<Peer>,
+    snapshot: Box<Report>,
+    log: Vec<Message>,
+    seq: usize
+}
+
+impl Hub {
+    /// Sends a message to every joined instance, including the host.
+    fn broadcast(&mut self, message: &Message) {
+        for (_, sender) in self.clients.iter() {
+            // Instances which cannot be written to are removed once their reader disconnects
+            let _ = sender.send(message.clone());
+        }
+
+        (self.deliver)(message);
+    }
+
+    fn broadcast_peers(&mut self) {
+        let message = Message::Peers { peers: self.peers.clone() };
+        self.broadcast(&message);
+    }
+
+    fn join(&mut self, id: usize, name: String) {
+        let sender = match self.pending.iter().position(|(pending_id, _)| *pending_id == id) {
+            None => return,
+            Some(index) => spawn_writer(self.pending.remove(index).1)
+        };
+
+        // Joining instances start from the last snapshot and replay the operations since it
+        let _ = sender.send(Message::Welcome { client_id: id, snapshot: self.snapshot.clone() });
+
+        for operation in &self.log {
+            let _ = sender.send(operation.clone());
+        }
+
+        self.clients.push((id, sender));
+        self.peers.push(Peer { id, name, finding_id: None });
+        self.broadcast_peers();
+    }
+
+    /// Returns whether an instance has said hello, which the host has done from the start.
+    fn joined(&self, id: usize) -> bool {
+        self.peers.iter().any(|peer| peer.id == id)
+    }
+
+    fn run(mut self, events: Receiver<Event>) {
+        for event in events {
+            match event {
+                Event::Connected(id, stream) => self.pending.push((id, stream)),
+
+                Event::Received(id, Message::Hello { name }) => self.join(id, name),
+
+                Event::Received(id, Message::Submit { command }) => {
+                    // Malformed commands would fail, or panic, in every instance they reach
+                    if !self.joined(id) || crate::check_shared_command(&command).is_err() {
+                        continue;
+                    }
+
+                    self.seq += 1;
+
+                    let operation = Message::Operation { seq: self.seq, client_id: id, command };
+                    self.log.push(operation.clone());
+                    self.broadcast(&operation);
+                }
+
+                Event::Received(id, Message::SubmitSnapshot { base_seq, snapshot }) => {
+                    if !self.joined(id) {
+                        continue;
+                    }
+
+                    self.seq += 1;
+
+                    // Operations put in order after the snapshot was built are applied again on top of it
+                    let concurrent: Vec<Message> = self.log.drain(..)
+                        .filter(|operation| matches!(operation, Message::Operation { seq, .. } if *seq > base_seq))
+                        .collect();
+
+                    self.snapshot = snapshot.clone();
+                    self.broadcast(&Message::Snapshot { seq: self.seq, client_id: id, snapshot });
+
+                    for operation in concurrent {
+                        if let Message::Operation { client_id, command, .. } = operation {
+                            self.seq += 1;
+
+                            let operation = Message::Operation { seq: self.seq, client_id, command };
+                            self.log.push(operation.clone());
+                            self.broadcast(&operation);
+                        }
+                    }
+                }
+
+                Event::Received(id, Message::Presence { finding_id }) => {
+                    if let Some(peer) = self.peers.iter_mut().find(|peer| peer.id == id) {
+                        if peer.finding_id != finding_id {
+                            peer.finding_id = finding_id;
+                            self.broadcast_peers();
+                        }
+                    }
+                }
+
+                // The remaining messages are only sent by the host
+                Event::Received(_, _) => {}
+
+                Event::Disconnected(id) => {
+                    self.pending.retain(|(pending_id, _)| *pending_id != id);
+                    self.clients.retain(|(client_id, _)| *client_id != id);
+
+                    if self.peers.iter().any(|peer| peer.id == id) {
+                        self.peers.retain(|peer| peer.id != id);
+                        self.broadcast_peers();
+                    }
+                }
+
+                Event::Shutdown => {
+                    let closed = Message::Closed { reason: "The host ended the session".to_string() };
+
+                    for (_, sender) in self.clients.iter() {
+                        let _ = sender.send(closed.clone());
+                    }
+
+                    for (_, stream) in self.pending.iter() {
+                        let _ = stream.shutdown(Shutdown::Both);
+                    }
+
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Link {
+    Hub(Sender<Event>),
+    Host(TcpStream)
+}
+
+/// A live collaboration session, either hosted by this instance or joined from another one.
+#[derive(Debug)]
+pub struct Session {
+    pub name: String,
+    pub address: String,
+    pub client_id: Option<usize>,
+    pub peers: Vec<Peer>,
+    pub finding_id: Option<usize>,
+    pub seq: usize,
+    link: Link,
+    stopped: Arc<AtomicBool>
+}
+
+impl Session {
+    /// Hosts a session on the address, starting from the workbook.
+    pub fn host(handle: web_view::Handle<()>, address: &str, name: &str, snapshot: Report) -> Result<Session, String> {
+        Session::host_with(address, name, snapshot, Box::new(move |message| deliver(&handle, message)))
+    }
+
+    fn host_with(address: &str, name: &str, snapshot: Report, deliver: Deliver) -> Result<Session, String> {
+        let listener = TcpListener::bind(address).map_err(|error| format!("Failed to listen on {}: {}", address, error))?;
+
+        // The port is picked by the system when the address asks for port 0
+        let address = listener.local_addr().map(|address| address.to_string()).unwrap_or_else(|_| address.to_string());
+
+        // The listener is polled so it can be closed when the session ends
+        listener.set_nonblocking(true).map_err(|error| error.to_string())?;
+
+        let (sender, events) = mpsc::channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        {
+            let sender = sender.clone();
+            let stopped = stopped.clone();
+
+            thread::spawn(move || {
+                let mut next_id = HOST_ID + 1;
+
+                while !stopped.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            let id = next_id;
+                            next_id += 1;
+
+                            let reader = match stream.set_nonblocking(false).and_then(|_| stream.try_clone()) {
+                                Err(_) => continue,
+                                Ok(reader) => reader
+                            };
+
+                            if sender.send(Event::Connected(id, stream)).is_err() {
+                                break;
+                            }
+
+                            let sender = sender.clone();
+
+                            thread::spawn(move || {
+                                read_messages(reader, |message| {
+                                    let _ = sender.send(Event::Received(id, message));
+                                });
+
+                                let _ = sender.send(Event::Disconnected(id));
+                            });
+                        }
+
+                        Err(error) if error.kind() == ErrorKind::WouldBlock => thread::sleep(Duration::from_millis(100)),
+                        Err(_) => break
+                    }
+                }
+            });
+        }
+
+        let peers = vec![Peer { id: HOST_ID, name: name.to_string(), finding_id: None }];
+
+        let hub = Hub {
+            deliver,
+            pending: vec![],
+            clients: vec![],
+            peers: peers.clone(),
+            snapshot: Box::new(snapshot),
+            log: vec![],
+            seq: 0
+        };
+
+        thread::spawn(move || hub.run(events));
+
+        Ok(Session {
+            name: name.to_string(),
+            address,
+            client_id: Some(HOST_ID),
+            peers,
+            finding_id: None,
+            seq: 0,
+            link: Link::Hub(sender),
+            stopped
+        })
+    }
+
+    /// Joins the session hosted on the address. The workbook is replaced once the host welcomes
+    /// this instance.
+    pub fn join(handle: web_view::Handle<()>, address: &str, name: &str) -> Result<Session, String> {
+        Session::join_with(address, name, Box::new(move |message| deliver(&handle, message)))
+    }
+
+    fn join_with(address: &str, name: &str, deliver: Deliver) -> Result<Session, String> {
+        let mut stream = TcpStream::connect(address).map_err(|error| format!("Failed to connect to {}: {}", address, error))?;
+        write_message(&mut stream, &Message::Hello { name: name.to_string() })?;
+
+        let reader = stream.try_clone().map_err(|error| error.to_string())?;
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        {
+            let stopped = stopped.clone();
+
+            thread::spawn(move || {
+                read_messages(reader, |message| deliver(&message));
+
+                if !stopped.load(Ordering::SeqCst) {
+                    deliver(&Message::Closed { reason: "Lost the connection to the host".to_string() });
+                }
+            });
+        }
+
+        Ok(Session {
+            name: name.to_string(),
+            address: address.to_string(),
+            client_id: None,
+            peers: vec![],
+            finding_id: None,
+            seq: 0,
+            link: Link::Host(stream),
+            stopped
+        })
+    }
+
+    pub fn is_host(&self) -> bool {
+        matches!(self.link, Link::Hub(_))
+    }
+
+    pub fn send(&mut self, message: Message) -> Result<(), String> {
+        match self.link {
+            Link::Hub(ref sender) => sender.send(Event::Received(HOST_ID, message)).map_err(|_| "The session has ended".to_string()),
+            Link::Host(ref mut stream) => write_message(stream, &message)
+        }
+    }
+
+    /// Tells the other instances which finding is being edited, if it changed.
+    pub fn set_presence(&mut self, finding_id: Option<usize>) -> Result<(), String> {
+        if self.finding_id == finding_id {
+            return Ok(());
+        }
+
+        self.finding_id = finding_id;
+        self.send(Message::Presence { finding_id })
+    }
+
+    /// Returns the names of the other instances editing the finding.
+    pub fn editors(&self, finding_id: usize) -> Vec<&str> {
+        self.peers.iter()
+            .filter(|peer| Some(peer.id) != self.client_id && peer.finding_id == Some(finding_id))
+            .map(|peer| peer.name.as_str())
+            .collect()
+    }
+
+    pub fn leave(self) {
+        self.stopped.store(true, Ordering::SeqCst);
+
+        match self.link {
+            Link::Hub(sender) => {
+                let _ = sender.send(Event::Shutdown);
+            }
+
+            Link::Host(stream) => {
+                let _ = stream.shutdown(Shutdown::Both);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Instant;
+
+    fn report() -> Report {
+        serde_json::from_value(serde_json::json!({
+            "title": "Audit",
+            "auditors": [],
+            "start_time": "",
+            "delivery_time": "",
+            "repository": "",
+            "commit_hashes": [],
+            "checklist": [],
+            "overview": "",
+            "findings": []
+        })).unwrap()
+    }
+
+    fn channel() -> (Deliver, Receiver<Message>) {
+        let (sender, receiver) = mpsc::channel();
+
+        (Box::new(move |message: &Message| { let _ = sender.send(message.clone()); }), receiver)
+    }
+
+    /// Waits for the next message which is not a list of peers.
+    fn next(receiver: &Receiver<Message>) -> Message {
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        loop {
+            match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())).expect("timed out waiting for a message") {
+                Message::Peers { .. } => continue,
+                message => return message
+            }
+        }
+    }
+
+    /// Returns the sequence number and command of an operation, or the title of a snapshot.
+    fn summary(message: Message) -> (usize, String) {
+        match message {
+            Message::Operation { seq, command, .. } => (seq, command),
+            Message::Snapshot { seq, snapshot, .. } => (seq, format!("snapshot {}", snapshot.title)),
+            message => panic!("unexpected message {:?}", message)
+        }
+    }
+
+    #[test]
+    fn hub_orders_operations_for_every_instance() {
+        let (host_deliver, host_messages) = channel();
+        let mut host = Session::host_with("127.0.0.1:0", "Host", report(), host_deliver).unwrap();
+
+        let (alice_deliver, alice_messages) = channel();
+        let mut alice = Session::join_with(host.address.as_str(), "Alice", alice_deliver).unwrap();
+        assert!(matches!(next(&alice_messages), Message::Welcome { client_id: 1, .. }));
+
+        let (bob_deliver, bob_messages) = channel();
+        let mut bob = Session::join_with(host.address.as_str(), "Bob", bob_deliver).unwrap();
+        assert!(matches!(next(&bob_messages), Message::Welcome { client_id: 2, .. }));
+
+        alice.send(Message::Submit { command: "create_finding".to_string() }).unwrap();
+        assert_eq!(summary(next(&host_messages)), (1, "create_finding".to_string()));
+
+        bob.send(Message::Submit { command: "set_finding_title 1 \"Reentrancy\"".to_string() }).unwrap();
+        assert_eq!(summary(next(&host_messages)), (2, "set_finding_title 1 \"Reentrancy\"".to_string()));
+
+        host.send(Message::Submit { command: "set_finding_severity 1 critical".to_string() }).unwrap();
+        assert_eq!(summary(next(&host_messages)), (3, "set_finding_severity 1 critical".to_string()));
+
+        // Alice's snapshot was built before the last two operations, which are applied again after it
+        let mut snapshot = report();
+        snapshot.title = "Imported".to_string();
+        alice.send(Message::SubmitSnapshot { base_seq: 1, snapshot: Box::new(snapshot) }).unwrap();
+
+        let expected = vec![
+            (1, "create_finding".to_string()),
+            (2, "set_finding_title 1 \"Reentrancy\"".to_string()),
+            (3, "set_finding_severity 1 critical".to_string()),
+            (4, "snapshot Imported".to_string()),
+            (5, "set_finding_title 1 \"Reentrancy\"".to_string()),
+            (6, "set_finding_severity 1 critical".to_string())
+        ];
+
+        let host_received: Vec<(usize, String)> = (3..expected.len()).map(|_| summary(next(&host_messages))).collect();
+        assert_eq!(host_received, expected[3..].to_vec());
+
+        for messages in [&alice_messages, &bob_messages].iter() {
+            let received: Vec<(usize, String)> = expected.iter().map(|_| summary(next(messages))).collect();
+            assert_eq!(received, expected);
+        }
+
+        // A late joiner starts from the snapshot and replays the operations since it
+        let (carol_deliver, carol_messages) = channel();
+        let carol = Session::join_with(host.address.as_str(), "Carol", carol_deliver).unwrap();
+
+        match next(&carol_messages) {
+            Message::Welcome { snapshot, .. } => assert_eq!(snapshot.title, "Imported"),
+            message => panic!("unexpected message {:?}", message)
+        }
+
+        assert_eq!(summary(next(&carol_messages)), expected[4].clone());
+        assert_eq!(summary(next(&carol_messages)), expected[5].clone());
+
+        host.leave();
+
+        for messages in [&alice_messages, &bob_messages, &carol_messages].iter() {
+            assert!(matches!(next(messages), Message::Closed { .. }));
+        }
+
+        alice.leave();
+        bob.leave();
+        carol.leave();
+    }
+
+    #[test]
+    fn hub_drops_invalid_submissions() {
+        let (host_deliver, host_messages) = channel();
+        let mut host = Session::host_with("127.0.0.1:0", "Host", report(), host_deliver).unwrap();
+
+        // Instances which have not said hello are not part of the session
+        let mut stranger = TcpStream::connect(host.address.as_str()).unwrap();
+        write_message(&mut stranger, &Message::Submit { command: "clear_findings".to_string() }).unwrap();
+
+        let (alice_deliver, alice_messages) = channel();
+        let mut alice = Session::join_with(host.address.as_str(), "Alice", alice_deliver).unwrap();
+        assert!(matches!(next(&alice_messages), Message::Welcome { client_id: 2, .. }));
+
+        for command in ["save_workbook", "set_finding_title", "set_finding_title one \"Reentrancy\"", "remove_finding 1 2", "unknown_command"].iter() {
+            alice.send(Message::Submit { command: command.to_string() }).unwrap();
+        }
+
+        alice.send(Message::Submit { command: "create_finding".to_string() }).unwrap();
+        assert_eq!(summary(next(&host_messages)), (1, "create_finding".to_string()));
+
+        host.send(Message::Submit { command: "remove_finding 1".to_string() }).unwrap();
+        assert_eq!(summary(next(&host_messages)), (2, "remove_finding 1".to_string()));
+
+        assert_eq!(summary(next(&alice_messages)), (1, "create_finding".to_string()));
+        assert_eq!(summary(next(&alice_messages)), (2, "remove_finding 1".to_string()));
+
+        host.leave();
+        alice.leave();
+    }
+}
@@ -1,12 +1,12 @@
-
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DataKind {
     Int,
     Float,
     String
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 pub struct Parameter {
     pub name: String,
     pub kind: DataKind
@@ -21,16 +21,201 @@ impl Parameter {
     }
 }
 
+#[derive(Clone, Serialize)]
 pub struct Command {
     pub name: String,
+    /// The name shown for the command in the command palette and the shortcut settings.
+    pub title: String,
     pub parameters: Vec<Parameter>,
 }
 
 impl Command {
-    pub fn new(name: &str, parameters: &[Parameter]) -> Self {
+    pub fn new(name: &str, title: &str, parameters: &[Parameter]) -> Self {
         Self {
             name: name.to_string(),
+            title: title.to_string(),
             parameters: parameters.to_vec()
         }
     }
 }
+
+/// An entry of the command palette, which either runs a command or jumps to a finding.
+#[derive(Clone, Serialize)]
+pub struct PaletteEntry {
+    pub title: String,
+    pub shortcut: String,
+    pub command: Option<Command>,
+    pub finding_id: Option<usize>
+}
+
+/// Returns the commands which can be run from the command palette or bound to a keyboard shortcut.
+///
+/// Parameters are asked for when the command is run, with a finding id defaulting to the finding being edited.
+pub fn registry() -> Vec<Command> {
+    let id = [Parameter::new("Finding id", DataKind::Int)];
+
+    vec![
+        Command::new("show_command_palette", "Show Command Palette", &[]),
+        Command::new("create_finding", "Create Finding", &[]),
+        Command::new("copy_finding", "Copy Finding", &id),
+        Command::new("paste_finding", "Paste Finding", &[]),
+        Command::new("accept_finding", "Accept Draft Finding", &id),
+        Command::new("suppress_finding", "Suppress Draft Finding as False Positive", &id),
+        Command::new("remove_finding", "Remove Finding", &id),
+        Command::new("add_attachments", "Attach Files to Finding...", &id),
+        Command::new("create_checklist_entry", "Create Checklist Entry", &[]),
+        Command::new("set_finding_filter", "Filter Findings", &[Parameter::new("Filter", DataKind::String)]),
+        Command::new("load_workbook", "Open Workbook...", &[]),
+        Command::new("save_workbook", "Save Workbook...", &[]),
+        Command::new("save_workbook_bundle", "Save Workbook Bundle...", &[]),
+        Command::new("merge_workbooks", "Merge Workbooks...", &[]),
+        Command::new("import_markdown", "Import Markdown...", &[]),
+        Command::new("import_slither", "Import Slither Results...", &[]),
+        Command::new("import_sarif", "Import SARIF Results...", &[]),
+        Command::new("import_csv_statuses", "Import Statuses from CSV...", &[]),
+        Command::new("export_markdown", "Export Markdown...", &[]),
+        Command::new("export_html", "Export HTML...", &[]),
+        Command::new("export_pdf", "Export PDF...", &[]),
+        Command::new("export_sarif", "Export SARIF...", &[]),
+        Command::new("export_csv", "Export CSV...", &[]),
+        Command::new("export_xlsx", "Export Excel Workbook...", &[]),
+        Command::new("export_issue_bundle", "Export Issue Tracker Bundle...", &[]),
+        Command::new("select_export_template", "Select Export Template...", &[]),
+        Command::new("reset_export_template", "Use Default Export Template", &[]),
+        Command::new("run_linter", "Run Report Linter", &[]),
+        Command::new("select_repository", "Select Repository...", &[]),
+        Command::new("refresh_repository", "Refresh Repository", &[]),
+        Command::new("run_reaudit", "Run Re-audit", &[]),
+        Command::new("open_workspace", "Open Workspace...", &[]),
+        Command::new("refresh_workspace", "Refresh Workspace", &[]),
+        Command::new("create_workspace_workbook", "Create Workspace Workbook", &[Parameter::new("Workbook name, such as client/module", DataKind::String)]),
+        Command::new("leave_session", "Leave Collaboration Session", &[])
+    ]
+}
+
+pub fn find(name: &str) -> Option<Command> {
+    registry().into_iter().find(|command| command.name == name)
+}
+
+/// Quotes a command parameter, so that it is read as one token whatever it contains. Matches the
+/// `quote` function of the page.
+pub fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Splits a command string into its name and parameters. Quoted parameters may contain whitespace,
+/// and a backslash in them escapes the next character, such as a quote.
+pub fn tokenize(string: &str) -> Vec<String> {
+    let mut is_string = false;
+    let mut tokens = vec![];
+    let mut token = String::new();
+
+    // Create an iterator to loop over each character
+    let mut iter = string.chars();
+
+    // Build each token using the character iterator
+    while let Some(c) = iter.next() {
+        if c.is_whitespace() {
+            if is_string {
+                // Push all whitespace characters inside a string to the token
+                token.push(c);
+            } else if !token.is_empty() {
+                // Push the current token to the vector if token is not empty
+                tokens.push(token);
+                token = String::new();
+            }
+        } else if c == '"' {
+            // Flip string mode
+            is_string = !is_string;
+        } else if c == '\\' && is_string {
+            // Push the escaped character to the token
+            token.extend(iter.next());
+        } else {
+            // Push the character to the token
+            token.push(c);
+        }
+    }
+
+    // Push the last token (if any)
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+/// The parameters of a command string, read in the order the command takes them.
+pub struct Arguments<'a> {
+    name: &'a str,
+    iter: std::slice::Iter<'a, String>
+}
+
+impl<'a> Arguments<'a> {
+    pub fn new(name: &'a str, parameters: &'a [String]) -> Self {
+        Self {
+            name,
+            iter: parameters.iter()
+        }
+    }
+
+    /// Returns the next parameter, which the command requires.
+    pub fn text(&mut self) -> Result<String, String> {
+        self.iter.next().cloned().ok_or_else(|| format!("The '{}' command is missing a parameter", self.name))
+    }
+
+    /// Returns the next parameter, or an empty string as the page leaves out empty text.
+    pub fn optional_text(&mut self) -> String {
+        self.iter.next().cloned().unwrap_or_default()
+    }
+
+    /// Parses the next parameter, such as a finding id or a checkbox state.
+    pub fn parse<T: std::str::FromStr>(&mut self) -> Result<T, String> {
+        let text = self.text()?;
+
+        text.parse().map_err(|_| format!("Invalid parameter '{}' for the '{}' command", text, self.name))
+    }
+
+    /// Checks that every parameter was used.
+    pub fn finish(mut self) -> Result<(), String> {
+        match self.iter.next() {
+            Some(_) => Err(format!("Too many parameters for the '{}' command", self.name)),
+            None => Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_report_missing_and_invalid_parameters() {
+        let parameters = vec!["3".to_string(), "x".to_string()];
+        let mut args = Arguments::new("copy_finding", &parameters);
+
+        assert_eq!(args.parse::<usize>(), Ok(3));
+        assert_eq!(args.parse::<usize>(), Err("Invalid parameter 'x' for the 'copy_finding' command".to_string()));
+        assert_eq!(args.text(), Err("The 'copy_finding' command is missing a parameter".to_string()));
+        assert_eq!(args.optional_text(), "");
+
+        let mut args = Arguments::new("copy_finding", &parameters);
+        args.parse::<usize>().unwrap();
+        assert!(args.finish().is_err());
+    }
+
+    #[test]
+    fn quoted_parameters_round_trip() {
+        for text in ["It''s", "say \"hi\"", "C:\\audit\\", "multi\nline  text"].iter() {
+            assert_eq!(tokenize(format!("set_report_field overview {}", quote(text)).as_str()), vec!["set_report_field", "overview", text]);
+        }
+    }
+
+    #[test]
+    fn registry_names_are_unique() {
+        let commands = registry();
+
+        for (index, command) in commands.iter().enumerate() {
+            assert!(commands[index + 1..].iter().all(|other| other.name != command.name), "{} is registered twice", command.name);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// The file holding the local checkouts of the audited repositories, in the user's configuration directory.
+pub const CHECKOUTS_FILE: &str = "checkouts.json";
+
+/// Returns the directory holding the user's configuration for the workbook.
+pub fn directory() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    base.map(|base| base.join("certik-workbook"))
+}
+
+/// The local checkouts of the audited repositories, keyed by repository URL.
+///
+/// Checkouts are specific to the machine, so they are kept here rather than in the workbook file.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Checkouts(BTreeMap<String, String>);
+
+impl Checkouts {
+    pub fn load() -> Result<Checkouts, String> {
+        let path = match directory() {
+            None => return Ok(Checkouts::default()),
+            Some(directory) => directory.join(CHECKOUTS_FILE)
+        };
+
+        if !path.exists() {
+            return Ok(Checkouts::default());
+        }
+
+        let json = std::fs::read_to_string(&path).map_err(|error| error.to_string())?;
+        serde_json::from_str(json.as_str()).map_err(|error| format!("Failed to read {}: {}", path.display(), error))
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let directory = directory().ok_or_else(|| "No configuration directory was found".to_string())?;
+        std::fs::create_dir_all(&directory).map_err(|error| error.to_string())?;
+
+        let json = serde_json::to_string_pretty(self).map_err(|error| error.to_string())?;
+        std::fs::write(directory.join(CHECKOUTS_FILE), json).map_err(|error| error.to_string())
+    }
+
+    pub fn get(&self, repository: &str) -> Option<String> {
+        self.0.get(repository.trim()).cloned()
+    }
+
+    pub fn set(&mut self, repository: &str, path: &str) {
+        self.0.insert(repository.trim().to_string(), path.to_string());
+    }
+}
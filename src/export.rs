@@ -0,0 +1,300 @@
+use crate::{
+    highlight,
+    markdown,
+    reference,
+    report::Finding,
+    template::{self, ReportContext},
+    StateData
+};
+
+use std::collections::HashMap;
+
+/// Builds the markdown report for the state data using the workbook's export template.
+pub fn markdown(state: &StateData) -> Result<String, String> {
+    template::render(template::load(state)?.as_str(), &ReportContext::new(state))
+}
+
+/// A section heading of the rendered report, listed in the table of contents.
+#[derive(Clone, Debug, PartialEq)]
+struct Heading {
+    level: usize,
+    text: String,
+    anchor: String
+}
+
+/// Removes the HTML tags from a heading, such as its anchor and severity icon.
+fn strip_tags(text: &str) -> String {
+    let mut result = String::new();
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => result.push(c),
+            _ => ()
+        }
+    }
+
+    result.trim().to_string()
+}
+
+/// Turns a heading into an anchor such as `appendix-severity-matrix`.
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Finds the ATX headings of a markdown document outside of code blocks.
+///
+/// Headings which already have an anchor such as `<a id="XXX-01"></a>` keep it, and the others are given
+/// one derived from their text, so the table of contents follows whatever headings the template uses.
+fn anchor_headings(md: &str) -> (String, Vec<Heading>) {
+    let mut result = String::with_capacity(md.len());
+    let mut headings: Vec<Heading> = vec![];
+    let mut fence: Option<char> = None;
+
+    for line in md.split_inclusive('\n') {
+        let trimmed = line.trim_start_matches(' ');
+        let indented = line.len() - trimmed.len() >= 4;
+
+        if !indented && (trimmed.starts_with("```") || trimmed.starts_with("~~~")) {
+            let marker = trimmed.chars().next();
+
+            match fence {
+                None => fence = marker,
+                Some(open) if Some(open) == marker => fence = None,
+                Some(_) => ()
+            }
+
+            result.push_str(line);
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        let content = &trimmed[level..];
+
+        if fence.is_some() || indented || level == 0 || level > 6 || !(content.is_empty() || content.starts_with(' ')) {
+            result.push_str(line);
+            continue;
+        }
+
+        let content = content.trim();
+        let text = strip_tags(content);
+
+        let existing = content.find("<a id=\"")
+            .map(|index| &content[index + 7..])
+            .and_then(|rest| rest.find('"').map(|end| rest[..end].to_string()));
+
+        let anchor = match existing {
+            Some(anchor) => anchor,
+
+            None => {
+                let base = slug(text.as_str());
+                let mut anchor = base.clone();
+                let mut count = 1;
+
+                while anchor.is_empty() || headings.iter().any(|heading| heading.anchor == anchor) {
+                    count += 1;
+                    anchor = format!("{}-{}", if base.is_empty() { "section" } else { base.as_str() }, count);
+                }
+
+                result.push_str(format!("{} <a id=\"{}\"></a>{}\n", "#".repeat(level), anchor, content).as_str());
+                headings.push(Heading { level, text, anchor });
+                continue;
+            }
+        };
+
+        result.push_str(line);
+        headings.push(Heading { level, text, anchor });
+    }
+
+    (result, headings)
+}
+
+/// Lists the top two levels of headings, with the second level nested under the first.
+fn table_of_contents(headings: &[Heading]) -> String {
+    let top = match headings.iter().map(|heading| heading.level).min() {
+        None => return String::new(),
+        Some(level) => level
+    };
+
+    let mut toc = String::new();
+    toc.push_str("<nav class=\"toc\">\n");
+    toc.push_str("<h2>Table of Contents</h2>\n");
+    toc.push_str("<ol>\n");
+
+    // Each top level item stays open until the next one, so the subsections are nested inside it
+    let mut item_open = false;
+    let mut nested = false;
+
+    for heading in headings.iter().filter(|heading| heading.level <= top + 1) {
+        let link = format!("<a href=\"#{}\">{}</a>", markdown::escape_html(heading.anchor.as_str()), markdown::escape_html(heading.text.as_str()));
+
+        if heading.level == top {
+            if nested {
+                toc.push_str("</ol>\n");
+                nested = false;
+            }
+
+            if item_open {
+                toc.push_str("</li>\n");
+            }
+
+            toc.push_str(format!("<li>{}\n", link).as_str());
+            item_open = true;
+        } else {
+            if !item_open {
+                toc.push_str("<li>\n");
+                item_open = true;
+            }
+
+            if !nested {
+                toc.push_str("<ol>\n");
+                nested = true;
+            }
+
+            toc.push_str(format!("<li>{}</li>\n", link).as_str());
+        }
+    }
+
+    if nested {
+        toc.push_str("</ol>\n");
+    }
+
+    if item_open {
+        toc.push_str("</li>\n");
+    }
+
+    toc.push_str("</ol>\n");
+    toc.push_str("</nav>\n");
+    toc
+}
+
+/// Builds a self-contained HTML report with embedded styles and a table of contents.
+pub fn html(state: &StateData) -> Result<String, String> {
+    let context = ReportContext::new(state);
+    let md = template::render(template::load(state)?.as_str(), &context)?;
+    let (md, headings) = anchor_headings(md.as_str());
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n");
+    html.push_str("<html>\n");
+    html.push_str("<head>\n");
+    html.push_str("<meta charset=\"utf-8\">\n");
+    html.push_str(format!("<title>{}</title>\n", markdown::escape_html(context.report.title.as_str())).as_str());
+    html.push_str("<style>\n");
+    html.push_str(include_str!("../html/report.css"));
+    html.push_str("</style>\n");
+    html.push_str("</head>\n");
+    html.push_str("<body>\n");
+    html.push_str(format!("<h1>{}</h1>\n", markdown::escape_html(context.report.title.as_str())).as_str());
+    html.push_str(table_of_contents(&headings).as_str());
+    html.push_str(markdown::render_html(md.as_str()).as_str());
+    html.push_str("</body>\n");
+    html.push_str("</html>\n");
+
+    Ok(html)
+}
+
+/// Builds an HTML page previewing one of a finding's markdown fields as it appears in the exported report.
+pub fn preview(targets: &HashMap<usize, reference::Target>, finding: &Finding, md: &str) -> String {
+    let mut md = template::embed_attachment_uris(md, &finding.attachments);
+
+    // The preview is in a sandboxed frame which cannot navigate the workbook, so references only
+    // show what they resolve to
+    md = reference::resolve(md.as_str(), |id| targets.get(&id).cloned(), |id, _| format!("#finding{}", id));
+
+    if let Some(language) = template::code_language(finding) {
+        md = highlight::tag_code_blocks(md.as_str(), language);
+    }
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n");
+    html.push_str("<html>\n");
+    html.push_str("<head>\n");
+    html.push_str("<meta charset=\"utf-8\">\n");
+    html.push_str("<style>\n");
+    html.push_str(include_str!("../html/report.css"));
+    html.push_str("body { max-width: none; margin: 0.5rem; }\n");
+    html.push_str("</style>\n");
+    html.push_str("</head>\n");
+    html.push_str("<body>\n");
+    html.push_str(format!("<div class=\"{}\">\n", template::severity_class(finding.severity)).as_str());
+    html.push_str(markdown::render_html(md.as_str()).as_str());
+    html.push_str("</div>\n");
+    html.push_str("</body>\n");
+    html.push_str("</html>\n");
+
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchor_headings_keeps_existing_anchors() {
+        let (md, headings) = anchor_headings("## <a id=\"findings-at-a-glance\"></a>Findings at a Glance\n\nText\n");
+
+        assert_eq!(md, "## <a id=\"findings-at-a-glance\"></a>Findings at a Glance\n\nText\n");
+        assert_eq!(headings, vec![Heading { level: 2, text: "Findings at a Glance".to_string(), anchor: "findings-at-a-glance".to_string() }]);
+    }
+
+    #[test]
+    fn anchor_headings_adds_unique_anchors() {
+        let (md, headings) = anchor_headings("# Scope\n## Notes\n## Notes\n##No heading\n");
+
+        assert_eq!(md, "# <a id=\"scope\"></a>Scope\n## <a id=\"notes\"></a>Notes\n## <a id=\"notes-2\"></a>Notes\n##No heading\n");
+        assert_eq!(headings.iter().map(|heading| heading.anchor.as_str()).collect::<Vec<_>>(), vec!["scope", "notes", "notes-2"]);
+    }
+
+    #[test]
+    fn anchor_headings_skips_code_blocks() {
+        let (md, headings) = anchor_headings("```\n# comment\n```\n    # indented\n## Real\n");
+
+        assert_eq!(md, "```\n# comment\n```\n    # indented\n## <a id=\"real\"></a>Real\n");
+        assert_eq!(headings.len(), 1);
+    }
+
+    #[test]
+    fn anchor_headings_strips_tags_from_text() {
+        let (_, headings) = anchor_headings("### <svg><circle/></svg><a id=\"XXX-01\"></a>XXX-01: Reentrancy <b>in</b> Vault\n");
+
+        assert_eq!(headings[0].text, "XXX-01: Reentrancy in Vault");
+        assert_eq!(headings[0].anchor, "XXX-01");
+    }
+
+    #[test]
+    fn table_of_contents_nests_second_level() {
+        let heading = |level, anchor: &str| Heading { level, text: anchor.to_uppercase(), anchor: anchor.to_string() };
+
+        let toc = table_of_contents(&[
+            heading(2, "summary"),
+            heading(2, "findings"),
+            heading(3, "xxx-01"),
+            heading(4, "description"),
+            heading(3, "xxx-02"),
+            heading(2, "appendix")
+        ]);
+
+        assert_eq!(toc, "<nav class=\"toc\">\n<h2>Table of Contents</h2>\n<ol>\n\
+            <li><a href=\"#summary\">SUMMARY</a>\n</li>\n\
+            <li><a href=\"#findings\">FINDINGS</a>\n<ol>\n\
+            <li><a href=\"#xxx-01\">XXX-01</a></li>\n\
+            <li><a href=\"#xxx-02\">XXX-02</a></li>\n</ol>\n</li>\n\
+            <li><a href=\"#appendix\">APPENDIX</a>\n</li>\n\
+            </ol>\n</nav>\n");
+
+        assert_eq!(table_of_contents(&[]), "");
+    }
+}
@@ -0,0 +1,219 @@
+use crate::report::{Finding, Severity, Status};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Comparison {
+    Equal,
+    Less,
+    Greater
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Condition {
+    Tag(String),
+    Severity(Option<Severity>),
+    Status(Status),
+    Type(String),
+    Field(String, Comparison, String),
+    Text(String)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Term {
+    negated: bool,
+    condition: Condition
+}
+
+/// A query selecting findings, such as `tag:"needs poc" component=Vault -status:resolved reentrancy`.
+///
+/// Every term must match. Terms are `tag:name` (or `#name`), `severity:name`, `status:name`, `type:text`,
+/// a custom field compared with `=`, `<` or `>`, or text found in the title, location or description.
+/// A leading `-` negates a term.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Filter {
+    terms: Vec<Term>
+}
+
+/// Splits a query on whitespace outside of double quotes, removing the quotes.
+fn split_terms(query: &str) -> Vec<String> {
+    let mut terms = vec![];
+    let mut term = String::new();
+    let mut quoted = false;
+
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+
+            c if c.is_whitespace() && !quoted => {
+                if !term.is_empty() {
+                    terms.push(std::mem::take(&mut term));
+                }
+            }
+
+            c => term.push(c)
+        }
+    }
+
+    if !term.is_empty() {
+        terms.push(term);
+    }
+
+    terms
+}
+
+fn parse_condition(term: &str) -> Result<Condition, String> {
+    if let Some(tag) = term.strip_prefix('#') {
+        return Ok(Condition::Tag(tag.to_string()));
+    }
+
+    if let Some(index) = term.find(|c| c == ':' || c == '=' || c == '<' || c == '>') {
+        let (name, value) = (&term[..index], &term[index + 1..]);
+
+        let comparison = match &term[index..index + 1] {
+            "<" => Comparison::Less,
+            ">" => Comparison::Greater,
+            _ => Comparison::Equal
+        };
+
+        match name.to_lowercase().as_str() {
+            "tag" if comparison == Comparison::Equal => return Ok(Condition::Tag(value.to_string())),
+            "type" if comparison == Comparison::Equal => return Ok(Condition::Type(value.to_lowercase())),
+
+            "severity" if comparison == Comparison::Equal => return match value.to_lowercase().as_str() {
+                "unrated" | "none" => Ok(Condition::Severity(None)),
+                _ => Severity::from_name(value).map(|severity| Condition::Severity(Some(severity))).ok_or_else(|| format!("Unknown severity: '{}'", value))
+            },
+
+            "status" if comparison == Comparison::Equal => {
+                return Status::from_name(value).map(Condition::Status).ok_or_else(|| format!("Unknown status: '{}'", value));
+            }
+
+            _ if !name.is_empty() => return Ok(Condition::Field(name.to_string(), comparison, value.to_string())),
+            _ => ()
+        }
+    }
+
+    Ok(Condition::Text(term.to_lowercase()))
+}
+
+fn compare_field(value: &str, comparison: &Comparison, expected: &str) -> bool {
+    match comparison {
+        Comparison::Equal => value.eq_ignore_ascii_case(expected),
+
+        _ => match (value.trim().parse::<f64>(), expected.trim().parse::<f64>()) {
+            (Ok(value), Ok(expected)) if *comparison == Comparison::Less => value < expected,
+            (Ok(value), Ok(expected)) => value > expected,
+            _ => false
+        }
+    }
+}
+
+impl Filter {
+    pub fn parse(query: &str) -> Result<Filter, String> {
+        let mut terms = vec![];
+
+        for term in split_terms(query) {
+            let (negated, term) = match term.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, term.as_str())
+            };
+
+            terms.push(Term {
+                negated,
+                condition: parse_condition(term)?
+            });
+        }
+
+        Ok(Filter { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, finding: &Finding) -> bool {
+        self.terms.iter().all(|term| {
+            let matched = match term.condition {
+                Condition::Tag(ref tag) => finding.tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)),
+                Condition::Severity(severity) => finding.severity == severity,
+                Condition::Status(status) => finding.status == status,
+                Condition::Type(ref class) => finding.class.to_lowercase().contains(class.as_str()),
+
+                Condition::Field(ref name, ref comparison, ref expected) => finding.custom_fields.iter()
+                    .find(|(field, _)| field.eq_ignore_ascii_case(name))
+                    .map_or(false, |(_, value)| compare_field(value, comparison, expected)),
+
+                Condition::Text(ref text) => [&finding.title, &finding.location, &finding.description].iter()
+                    .any(|field| field.to_lowercase().contains(text.as_str()))
+            };
+
+            matched != term.negated
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(negated: bool, condition: Condition) -> Term {
+        Term { negated, condition }
+    }
+
+    #[test]
+    fn parse_terms() {
+        let filter = Filter::parse(r#"tag:"needs poc" #gas component=Vault -status:resolved severity:MAJOR type:Logic cost<10 cost>2.5 Reentrancy"#).unwrap();
+
+        assert_eq!(filter.terms, vec![
+            term(false, Condition::Tag("needs poc".to_string())),
+            term(false, Condition::Tag("gas".to_string())),
+            term(false, Condition::Field("component".to_string(), Comparison::Equal, "Vault".to_string())),
+            term(true, Condition::Status(Status::Resolved)),
+            term(false, Condition::Severity(Some(Severity::Major))),
+            term(false, Condition::Type("logic".to_string())),
+            term(false, Condition::Field("cost".to_string(), Comparison::Less, "10".to_string())),
+            term(false, Condition::Field("cost".to_string(), Comparison::Greater, "2.5".to_string())),
+            term(false, Condition::Text("reentrancy".to_string()))
+        ]);
+    }
+
+    #[test]
+    fn parse_edge_cases() {
+        assert!(Filter::parse("").unwrap().is_empty());
+        assert!(Filter::parse("   ").unwrap().is_empty());
+
+        assert_eq!(Filter::parse("severity:unrated").unwrap().terms, vec![term(false, Condition::Severity(None))]);
+
+        // A lone dash and a leading separator are searched for as text
+        assert_eq!(Filter::parse("- :x").unwrap().terms, vec![
+            term(false, Condition::Text("-".to_string())),
+            term(false, Condition::Text(":x".to_string()))
+        ]);
+
+        // Quotes group words into a single term
+        assert_eq!(Filter::parse(r#""flash loan""#).unwrap().terms, vec![term(false, Condition::Text("flash loan".to_string()))]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Filter::parse("severity:huge"), Err("Unknown severity: 'huge'".to_string()));
+        assert_eq!(Filter::parse("gas status:done"), Err("Unknown status: 'done'".to_string()));
+    }
+
+    #[test]
+    fn matches_findings() {
+        let mut finding = Finding::new(1);
+        finding.title = "Reentrancy in withdraw".to_string();
+        finding.severity = Some(Severity::Major);
+        finding.tags = vec!["Needs PoC".to_string()];
+        finding.custom_fields.insert("Component".to_string(), "Vault".to_string());
+        finding.custom_fields.insert("Cost".to_string(), "5".to_string());
+
+        for query in [r#"tag:"needs poc""#, "component=vault", "cost<10 cost>2.5", "-status:resolved", "severity:major reentrancy"].iter() {
+            assert!(Filter::parse(query).unwrap().matches(&finding), "{}", query);
+        }
+
+        for query in ["#gas", "component=Token", "cost>5", "status:pending -reentrancy", "severity:unrated", "missing=1"].iter() {
+            assert!(!Filter::parse(query).unwrap().matches(&finding), "{}", query);
+        }
+    }
+}
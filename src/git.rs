@@ -0,0 +1,240 @@
+use std::process::Command;
+
+/// The number of commits listed when picking audited commits.
+pub const LOG_LIMIT: usize = 50;
+
+/// Separates the fields of each commit in the formatted log output.
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Commit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub date: String,
+    pub summary: String
+}
+
+/// Runs a git command in the repository and returns its trimmed standard output.
+pub fn run(path: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .output()
+        .map_err(|error| format!("Failed to run git: {}", error))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(format!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+/// Returns the root directory of the checkout containing the path.
+pub fn toplevel(path: &str) -> Result<String, String> {
+    run(path, &["rev-parse", "--show-toplevel"])
+}
+
+pub fn head(path: &str) -> Result<String, String> {
+    run(path, &["rev-parse", "HEAD"])
+}
+
+/// Returns the URL of the `origin` remote, or of the first remote when there is no `origin`.
+pub fn remote_url(path: &str) -> Option<String> {
+    if let Ok(url) = run(path, &["remote", "get-url", "origin"]) {
+        return Some(url);
+    }
+
+    let remotes = run(path, &["remote"]).ok()?;
+    let remote = remotes.lines().next()?;
+
+    run(path, &["remote", "get-url", remote]).ok()
+}
+
+fn parse_commits(output: &str) -> Vec<Commit> {
+    output.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, FIELD_SEPARATOR);
+
+            Some(Commit {
+                hash: fields.next()?.to_string(),
+                short_hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                summary: fields.next()?.to_string()
+            })
+        })
+        .collect()
+}
+
+const LOG_FORMAT: &str = "--format=%H%x1f%h%x1f%an%x1f%ad%x1f%s";
+
+/// Returns the most recent commits reachable from HEAD.
+pub fn log(path: &str, limit: usize) -> Result<Vec<Commit>, String> {
+    let limit = format!("-n{}", limit);
+    run(path, &["log", LOG_FORMAT, "--date=short", limit.as_str()]).map(|output| parse_commits(output.as_str()))
+}
+
+/// Looks up a single commit, which may be given by any revision such as a short hash.
+pub fn commit(path: &str, revision: &str) -> Result<Commit, String> {
+    let output = run(path, &["show", "-s", LOG_FORMAT, "--date=short", "--end-of-options", revision, "--"])?;
+
+    parse_commits(output.as_str()).into_iter().next()
+        .ok_or_else(|| format!("No commit was found for '{}'", revision))
+}
+
+/// Resolves a revision entered by the user, such as a branch or a short hash, to the full hash of a commit.
+pub fn resolve_commit(path: &str, revision: &str) -> Result<String, String> {
+    // A revision starting with a dash is still read as a revision rather than an option
+    let revision = format!("{}^{{commit}}", revision);
+    run(path, &["rev-parse", "--verify", "--end-of-options", revision.as_str()])
+}
+
+/// Returns true if the text looks like an abbreviated or full commit hash.
+pub fn is_commit_hash(text: &str) -> bool {
+    text.len() >= 7 && text.len() <= 40 && text.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// A file of the `from` commit which was changed by the `to` commit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileChange {
+    pub old_file: String,
+    /// The path of the file at the `to` commit, or `None` if it was deleted.
+    pub new_file: Option<String>,
+    /// Whether the contents of the file changed, which renamed files may not have.
+    pub modified: bool
+}
+
+/// Parses the output of `git diff --name-status -M`, leaving out added and copied files.
+fn parse_name_status(output: &str) -> Vec<FileChange> {
+    output.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+
+            match fields.as_slice() {
+                [status, old, new] if status.starts_with('R') => Some(FileChange {
+                    old_file: old.to_string(),
+                    new_file: Some(new.to_string()),
+                    // The similarity of a rename is 100 only when the contents are identical
+                    modified: *status != "R100"
+                }),
+
+                [status, file] if status.starts_with('D') => Some(FileChange { old_file: file.to_string(), new_file: None, modified: true }),
+                [status, file] if status.starts_with('M') || status.starts_with('T') => Some(FileChange { old_file: file.to_string(), new_file: Some(file.to_string()), modified: true }),
+
+                _ => None
+            }
+        })
+        .collect()
+}
+
+/// Returns the files of the `from` commit changed by the `to` commit, following renames.
+pub fn changed_files(path: &str, from: &str, to: &str) -> Result<Vec<FileChange>, String> {
+    run(path, &["diff", "--name-status", "-M", "--end-of-options", from, to, "--"])
+        .map(|output| parse_name_status(output.as_str()))
+}
+
+/// A change between two versions of a file, in the line numbers of each version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hunk {
+    pub old_start: usize,
+    pub old_count: usize,
+    pub new_start: usize,
+    pub new_count: usize
+}
+
+fn parse_range(range: &str) -> (usize, usize) {
+    // Ranges look like `start,count`, where the count defaults to 1
+    let mut parts = range.splitn(2, ',');
+    let start = parts.next().and_then(|start| start.parse().ok()).unwrap_or(0);
+    let count = parts.next().and_then(|count| count.parse().ok()).unwrap_or(1);
+    (start, count)
+}
+
+/// Parses the hunk headers of a diff made with `--unified=0`.
+pub fn parse_hunks(output: &str) -> Vec<Hunk> {
+    output.lines()
+        .filter(|line| line.starts_with("@@ "))
+        .filter_map(|line| {
+            // Hunk headers look like `@@ -start,count +start,count @@`
+            let mut ranges = line.split_whitespace().skip(1);
+            let (old_start, old_count) = parse_range(ranges.next()?.strip_prefix('-')?);
+            let (new_start, new_count) = parse_range(ranges.next()?.strip_prefix('+')?);
+
+            Some(Hunk { old_start, old_count, new_start, new_count })
+        })
+        .collect()
+}
+
+/// Returns the hunks changing a file at the `from` commit into a file at the `to` commit, which
+/// may have a different path when the file was renamed.
+pub fn diff_hunks(path: &str, from: &str, old_file: &str, to: &str, new_file: &str) -> Result<Vec<Hunk>, String> {
+    let old_blob = format!("{}:{}", from, old_file);
+    let new_blob = format!("{}:{}", to, new_file);
+
+    run(path, &["diff", "--unified=0", "--no-color", "--end-of-options", old_blob.as_str(), new_blob.as_str(), "--"])
+        .map(|output| parse_hunks(output.as_str()))
+}
+
+/// Returns the path of a file at the `to` commit, following renames, or `None` if it was deleted.
+pub fn renamed_file(path: &str, from: &str, to: &str, file: &str) -> Result<Option<String>, String> {
+    Ok(
+        changed_files(path, from, to)?
+            .into_iter()
+            .find(|change| change.old_file == file)
+            .map_or_else(|| Some(file.to_string()), |change| change.new_file)
+    )
+}
+
+/// Returns the inclusive line ranges of a file at the `from` commit which were changed by the
+/// `to` commit, where it may have been renamed. Pure insertions are reported as the line they were
+/// inserted after.
+pub fn changed_lines(path: &str, from: &str, old_file: &str, to: &str, new_file: &str) -> Result<Vec<(usize, usize)>, String> {
+    Ok(
+        diff_hunks(path, from, old_file, to, new_file)?
+            .iter()
+            .map(|hunk| match hunk.old_count {
+                0 => (hunk.old_start.max(1), hunk.old_start.max(1)),
+                count => (hunk.old_start, hunk.old_start + count - 1)
+            })
+            .collect()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_name_status_follows_renames() {
+        let changes = parse_name_status("M\tcontracts/Vault.sol\nR100\tcontracts/Old.sol\tcontracts/New.sol\nR087\tsrc/A.sol\tsrc/B.sol\nD\tcontracts/Gone.sol\nA\tcontracts/Added.sol\nC075\tsrc/A.sol\tsrc/C.sol\n");
+
+        assert_eq!(changes, vec![
+            FileChange { old_file: "contracts/Vault.sol".to_string(), new_file: Some("contracts/Vault.sol".to_string()), modified: true },
+            FileChange { old_file: "contracts/Old.sol".to_string(), new_file: Some("contracts/New.sol".to_string()), modified: false },
+            FileChange { old_file: "src/A.sol".to_string(), new_file: Some("src/B.sol".to_string()), modified: true },
+            FileChange { old_file: "contracts/Gone.sol".to_string(), new_file: None, modified: true }
+        ]);
+    }
+
+    #[test]
+    fn resolve_commit_does_not_read_options() {
+        let path = std::env::temp_dir().join(format!("workbook_resolve_commit_{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+        let path = path.to_str().unwrap();
+
+        run(path, &["init", "-q"]).unwrap();
+        std::fs::write(format!("{}/Vault.sol", path), "contract Vault {}\n").unwrap();
+        run(path, &["add", "Vault.sol"]).unwrap();
+        run(path, &["-c", "user.name=Auditor", "-c", "user.email=auditor@example.com", "commit", "-q", "-m", "Add vault"]).unwrap();
+
+        let hash = head(path).unwrap();
+        assert_eq!(resolve_commit(path, &hash[..7]), Ok(hash.clone()));
+        assert_eq!(resolve_commit(path, "HEAD"), Ok(hash));
+        assert!(resolve_commit(path, "--output=leak").is_err());
+        assert!(resolve_commit(path, "missing").is_err());
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
@@ -0,0 +1,193 @@
+use comrak::nodes::{AstNode, NodeHtmlBlock, NodeValue};
+
+use std::sync::OnceLock;
+
+use syntect::{
+    highlighting::{Theme, ThemeSet},
+    html::highlighted_html_for_string,
+    parsing::{SyntaxDefinition, SyntaxReference, SyntaxSet}
+};
+
+/// The light theme matching the exported report styles.
+const THEME: &str = "InspiredGitHub";
+
+/// A grammar for Solidity, which the grammars bundled with syntect do not include.
+const SOLIDITY_SYNTAX: &str = include_str!("../syntaxes/Solidity.sublime-syntax");
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+
+    SYNTAX_SET.get_or_init(|| {
+        let mut builder = SyntaxSet::load_defaults_newlines().into_builder();
+
+        // The grammar is loaded by the tests, so it only fails to load if it was edited without running them
+        if let Ok(solidity) = SyntaxDefinition::load_from_str(SOLIDITY_SYNTAX, true, None) {
+            builder.add(solidity);
+        }
+
+        builder.build()
+    })
+}
+
+fn theme() -> &'static Theme {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    &THEME_SET.get_or_init(ThemeSet::load_defaults).themes[THEME]
+}
+
+/// Returns the language hint for the code in a file, based on its extension.
+pub fn language_for_path(path: &str) -> Option<&'static str> {
+    let extension = path.rsplit('.').next()?.to_lowercase();
+
+    match extension.as_str() {
+        "sol" => Some("solidity"),
+        "vy" => Some("vyper"),
+        "rs" => Some("rust"),
+        "go" => Some("go"),
+        "move" => Some("move"),
+        "js" => Some("javascript"),
+        "ts" => Some("typescript"),
+        "py" => Some("python"),
+        _ => None
+    }
+}
+
+/// Finds the grammar for a fence's language hint.
+///
+/// Solidity has its own grammar. The other languages without one are highlighted with the closest
+/// grammar, which misses the keywords it does not share: Move with Rust's, Vyper with Python's and
+/// TypeScript with JavaScript's.
+fn find_syntax(language: &str) -> Option<&'static SyntaxReference> {
+    let language = match language.to_lowercase().as_str() {
+        "solidity" => "sol".to_string(),
+        "move" => "rs".to_string(),
+        "typescript" | "ts" => "js".to_string(),
+        "vyper" | "vy" => "py".to_string(),
+        language => language.to_string()
+    };
+
+    syntax_set().find_syntax_by_token(language.as_str())
+}
+
+/// Renders a code block as highlighted HTML, if its language hint names a known grammar.
+pub fn highlight_code(code: &str, info: &str) -> Option<String> {
+    let language = info.split_whitespace().next()?;
+    let syntax = find_syntax(language)?;
+
+    highlighted_html_for_string(code, syntax_set(), syntax, theme()).ok()
+}
+
+/// Replaces the code blocks in a parsed document with their highlighted HTML.
+pub fn highlight_code_blocks<'a>(root: &'a AstNode<'a>) {
+    for node in root.descendants() {
+        let html = match node.data.borrow().value {
+            NodeValue::CodeBlock(ref code_block) => highlight_code(
+                std::str::from_utf8(&code_block.literal).unwrap_or(""),
+                std::str::from_utf8(&code_block.info).unwrap_or("")
+            ),
+
+            _ => None
+        };
+
+        if let Some(html) = html {
+            // Type 6 covers block level tags such as `<pre>`, which is what the highlighter emits
+            node.data.borrow_mut().value = NodeValue::HtmlBlock(NodeHtmlBlock {
+                block_type: 6,
+                literal: html.into_bytes()
+            });
+        }
+    }
+}
+
+/// Adds a language hint to the fenced code blocks without one.
+pub fn tag_code_blocks(md: &str, language: &str) -> String {
+    let mut result = String::with_capacity(md.len());
+    let mut fence: Option<String> = None;
+
+    for line in md.split_inclusive('\n') {
+        let trimmed = line.trim_start_matches(' ');
+        let indent = line.len() - trimmed.len();
+        let content = trimmed.trim_end();
+
+        let marker_length = content.chars().take_while(|c| *c == '`').count()
+            .max(content.chars().take_while(|c| *c == '~').count());
+
+        match fence {
+            Some(ref open) => {
+                // A closing fence is at least as long as the opening one, with nothing after it
+                if indent < 4 && content.starts_with(open.as_str()) && content.chars().all(|c| c == open.chars().next().unwrap()) {
+                    fence = None;
+                }
+
+                result.push_str(line);
+            }
+
+            None if indent < 4 && marker_length >= 3 => {
+                let marker = &content[..marker_length];
+                fence = Some(marker.to_string());
+
+                if content[marker_length..].trim().is_empty() {
+                    result.push_str(&line[..indent]);
+                    result.push_str(marker);
+                    result.push_str(language);
+                    result.push_str(&line[indent + content.len()..]);
+                } else {
+                    result.push_str(line);
+                }
+            }
+
+            None => result.push_str(line)
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_for_path_uses_the_extension() {
+        assert_eq!(language_for_path("contracts/Vault.sol"), Some("solidity"));
+        assert_eq!(language_for_path("sources/Coin.MOVE"), Some("move"));
+        assert_eq!(language_for_path("vyper/token.vy"), Some("vyper"));
+        assert_eq!(language_for_path("README.md"), None);
+        assert_eq!(language_for_path("Makefile"), None);
+    }
+
+    #[test]
+    fn smart_contract_languages_have_grammars() {
+        assert_eq!(find_syntax("solidity").unwrap().name, "Solidity");
+        assert_eq!(find_syntax("sol").unwrap().name, "Solidity");
+        assert_eq!(find_syntax("move").unwrap().name, "Rust");
+        assert_eq!(find_syntax("vyper").unwrap().name, "Python");
+        assert!(find_syntax("brainfuck").is_none());
+    }
+
+    #[test]
+    fn solidity_is_highlighted() {
+        let html = highlight_code("function withdraw(uint256 amount) external { // pays out\n    require(amount > 0, \"zero\");\n}\n", "solidity").unwrap();
+
+        // Keywords, types, comments and strings are each given their own color
+        let colors = html.matches("<span style=\"color:").count();
+        assert!(colors >= 4, "{}", html);
+        assert!(html.contains("pays out") && html.contains("zero"));
+
+        assert!(highlight_code("code", "").is_none());
+    }
+
+    #[test]
+    fn tag_code_blocks_adds_missing_hints() {
+        let md = "Text\n```\nuint x;\n```\n\n~~~~ \nmore\n~~~~\n";
+
+        assert_eq!(tag_code_blocks(md, "solidity"), "Text\n```solidity\nuint x;\n```\n\n~~~~solidity \nmore\n~~~~\n");
+    }
+
+    #[test]
+    fn tag_code_blocks_keeps_existing_hints() {
+        let md = "```rust\nfn main() {}\n```\n    ```\n    indented code\n````\n```\n````\n";
+
+        // Indented fences are code, and a shorter fence does not close a longer one
+        assert_eq!(tag_code_blocks(md, "solidity"), "```rust\nfn main() {}\n```\n    ```\n    indented code\n````solidity\n```\n````\n");
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{
+    reference,
+    report::Finding,
+    StateData
+};
+
+use std::collections::BTreeMap;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum Level {
+    Off,
+    Warning,
+    Error
+}
+
+impl Level {
+    pub const ALL: [Level; 3] = [Level::Off, Level::Warning, Level::Error];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Off => "Off",
+            Level::Warning => "Warning",
+            Level::Error => "Error"
+        }
+    }
+
+    /// Returns the identifier used for the level in commands and element ids.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Level::Off => "off",
+            Level::Warning => "warning",
+            Level::Error => "error"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|level| level.key() == name || level.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    MissingSeverity,
+    PlaceholderTitle,
+    EmptyDescription,
+    EmptyRecommendation,
+    MissingLocation,
+    FlaggedLocation,
+    UnjustifiedOverride,
+    DanglingReference,
+    UncheckedChecklist
+}
+
+impl Rule {
+    pub const ALL: [Rule; 9] = [
+        Rule::MissingSeverity,
+        Rule::PlaceholderTitle,
+        Rule::EmptyDescription,
+        Rule::EmptyRecommendation,
+        Rule::MissingLocation,
+        Rule::FlaggedLocation,
+        Rule::UnjustifiedOverride,
+        Rule::DanglingReference,
+        Rule::UncheckedChecklist
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::MissingSeverity => "Missing severity",
+            Rule::PlaceholderTitle => "Placeholder title",
+            Rule::EmptyDescription => "Empty description",
+            Rule::EmptyRecommendation => "Empty recommendation",
+            Rule::MissingLocation => "Missing location",
+            Rule::FlaggedLocation => "Location needs review",
+            Rule::UnjustifiedOverride => "Unjustified severity override",
+            Rule::DanglingReference => "Dangling reference",
+            Rule::UncheckedChecklist => "Unchecked checklist item"
+        }
+    }
+
+    /// Returns the identifier used for the rule in commands, element ids and the workbook.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Rule::MissingSeverity => "missing_severity",
+            Rule::PlaceholderTitle => "placeholder_title",
+            Rule::EmptyDescription => "empty_description",
+            Rule::EmptyRecommendation => "empty_recommendation",
+            Rule::MissingLocation => "missing_location",
+            Rule::FlaggedLocation => "flagged_location",
+            Rule::UnjustifiedOverride => "unjustified_override",
+            Rule::DanglingReference => "dangling_reference",
+            Rule::UncheckedChecklist => "unchecked_checklist"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|rule| rule.key() == name || rule.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The level of the rule in workbooks that have not configured it.
+    pub fn default_level(&self) -> Level {
+        match self {
+            Rule::MissingSeverity | Rule::PlaceholderTitle | Rule::EmptyDescription | Rule::DanglingReference => Level::Error,
+            _ => Level::Warning
+        }
+    }
+}
+
+/// The levels configured for the rules of a workbook, keyed by rule key.
+///
+/// Only the rules changed from their default level are stored, so new rules apply to existing workbooks.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Rules(BTreeMap<String, Level>);
+
+impl Rules {
+    pub fn level(&self, rule: Rule) -> Level {
+        self.0.get(rule.key()).copied().unwrap_or_else(|| rule.default_level())
+    }
+
+    pub fn set_level(&mut self, rule: Rule, level: Level) {
+        if level == rule.default_level() {
+            self.0.remove(rule.key());
+        } else {
+            self.0.insert(rule.key().to_string(), level);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Problem {
+    /// The finding with the problem, or unset for problems with the report itself.
+    pub finding_id: Option<usize>,
+    pub rule: Rule,
+    pub level: Level,
+    pub message: String
+}
+
+/// Returns true for the titles given to new findings, such as `Finding3`.
+fn is_placeholder_title(title: &str) -> bool {
+    let title = title.trim();
+
+    title.is_empty() || title.strip_prefix("Finding").map_or(false, |id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn lint_finding(state: &StateData, finding: &Finding, problems: &mut Vec<(Rule, String)>) {
+    if finding.severity.is_none() {
+        problems.push((Rule::MissingSeverity, "The finding has no severity".to_string()));
+    }
+
+    if is_placeholder_title(finding.title.as_str()) {
+        problems.push((Rule::PlaceholderTitle, format!("The title '{}' is a placeholder", finding.title.trim())));
+    }
+
+    if finding.description.trim().is_empty() {
+        problems.push((Rule::EmptyDescription, "The description is empty".to_string()));
+    }
+
+    if finding.recommendation.trim().is_empty() {
+        problems.push((Rule::EmptyRecommendation, "The recommendation is empty".to_string()));
+    }
+
+    // Older findings only have the location text, so it is the text that must be set
+    if finding.location.trim().is_empty() {
+        problems.push((Rule::MissingLocation, "The finding has no location".to_string()));
+    }
+
+    for location in finding.locations.iter().filter(|location| location.is_flagged()) {
+        problems.push((Rule::FlaggedLocation, format!("The location {} was remapped and needs review", location)));
+    }
+
+    if finding.severity_overridden && finding.justification.trim().is_empty() {
+        problems.push((Rule::UnjustifiedOverride, "The severity overrides the matrix without a justification".to_string()));
+    }
+
+    let mut references = reference::finding_references(finding);
+    references.sort();
+    references.dedup();
+
+    for id in references {
+        match state.findings.get(&id) {
+            None => problems.push((Rule::DanglingReference, format!("The finding refers to finding {}, which does not exist", id))),
+            Some(target) if target.draft => problems.push((Rule::DanglingReference, format!("The finding refers to finding {}, which is a draft and is not exported", id))),
+            Some(_) => ()
+        }
+    }
+}
+
+/// Checks the findings that will be exported and the report against the workbook's rules.
+pub fn lint(state: &StateData) -> Vec<Problem> {
+    let rules = &state.lint_rules;
+    let mut problems = vec![];
+
+    for (index, (checked, text)) in state.checklist.iter().enumerate() {
+        if !checked {
+            problems.push(Problem {
+                finding_id: None,
+                rule: Rule::UncheckedChecklist,
+                level: rules.level(Rule::UncheckedChecklist),
+                message: format!("Checklist item {} is unchecked: {}", index + 1, text)
+            });
+        }
+    }
+
+    let mut findings: Vec<&Finding> = state.findings.values().filter(|finding| !finding.draft).collect();
+    findings.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
+
+    for finding in findings {
+        let mut finding_problems = vec![];
+        lint_finding(state, finding, &mut finding_problems);
+
+        problems.extend(finding_problems.into_iter().map(|(rule, message)| Problem {
+            finding_id: Some(finding.id),
+            rule,
+            level: rules.level(rule),
+            message
+        }));
+    }
+
+    problems.retain(|problem| problem.level != Level::Off);
+    problems
+}
+
+/// Returns the number of errors and warnings in the problems.
+pub fn count(problems: &[Problem]) -> (usize, usize) {
+    (
+        problems.iter().filter(|problem| problem.level == Level::Error).count(),
+        problems.iter().filter(|problem| problem.level == Level::Warning).count()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{Confidence, Location, Severity};
+
+    /// A finding that passes every rule, which each test breaks in one way.
+    fn finding(id: usize) -> Finding {
+        let mut finding = Finding::new(id);
+        finding.title = "Reentrancy in withdraw".to_string();
+        finding.severity = Some(Severity::Major);
+        finding.description = "The balance is updated after the call".to_string();
+        finding.recommendation = "Update the balance before the call".to_string();
+        finding.location = "Vault.sol L10".to_string();
+        finding.locations = Location::parse_all(finding.location.as_str());
+        finding
+    }
+
+    fn state(findings: Vec<Finding>) -> StateData {
+        let mut state = StateData::new();
+        state.checklist = vec![(true, "Reviewed access control".to_string())];
+        state.findings = findings.into_iter().map(|finding| (finding.id, finding)).collect();
+        state
+    }
+
+    fn rules(state: &StateData) -> Vec<Rule> {
+        lint(state).into_iter().map(|problem| problem.rule).collect()
+    }
+
+    #[test]
+    fn clean_report_has_no_problems() {
+        assert_eq!(rules(&state(vec![finding(1)])), vec![]);
+    }
+
+    #[test]
+    fn missing_severity() {
+        let mut finding = finding(1);
+        finding.severity = None;
+        assert_eq!(rules(&state(vec![finding])), vec![Rule::MissingSeverity]);
+    }
+
+    #[test]
+    fn placeholder_title() {
+        for title in ["", "  ", "Finding3"].iter() {
+            let mut finding = finding(1);
+            finding.title = title.to_string();
+            assert_eq!(rules(&state(vec![finding])), vec![Rule::PlaceholderTitle], "{:?}", title);
+        }
+
+        let mut finding = finding(1);
+        finding.title = "Findings about fees".to_string();
+        assert_eq!(rules(&state(vec![finding])), vec![]);
+    }
+
+    #[test]
+    fn empty_description() {
+        let mut finding = finding(1);
+        finding.description = " \n".to_string();
+        assert_eq!(rules(&state(vec![finding])), vec![Rule::EmptyDescription]);
+    }
+
+    #[test]
+    fn empty_recommendation() {
+        let mut finding = finding(1);
+        finding.recommendation = String::new();
+        assert_eq!(rules(&state(vec![finding])), vec![Rule::EmptyRecommendation]);
+    }
+
+    #[test]
+    fn missing_location() {
+        let mut missing = finding(1);
+        missing.location = " ".to_string();
+        missing.locations = vec![];
+        assert_eq!(rules(&state(vec![missing])), vec![Rule::MissingLocation]);
+
+        // Findings from older workbooks only have the location text
+        let mut text_only = finding(1);
+        text_only.locations = vec![];
+        assert_eq!(rules(&state(vec![text_only])), vec![]);
+    }
+
+    #[test]
+    fn flagged_location() {
+        let mut finding = finding(1);
+        finding.locations[0].confidence = Some(Confidence::Low);
+        assert_eq!(rules(&state(vec![finding.clone()])), vec![Rule::FlaggedLocation]);
+
+        finding.locations[0].confidence = Some(Confidence::High);
+        assert_eq!(rules(&state(vec![finding])), vec![]);
+    }
+
+    #[test]
+    fn unjustified_override() {
+        let mut finding = finding(1);
+        finding.severity_overridden = true;
+        assert_eq!(rules(&state(vec![finding.clone()])), vec![Rule::UnjustifiedOverride]);
+
+        finding.justification = "Only the owner can call it".to_string();
+        assert_eq!(rules(&state(vec![finding])), vec![]);
+    }
+
+    #[test]
+    fn dangling_reference() {
+        let mut referring = finding(1);
+        referring.description = "See finding:2 and finding:3, and finding:2 again".to_string();
+
+        let mut draft = finding(3);
+        draft.draft = true;
+
+        let problems = lint(&state(vec![referring, finding(2), draft]));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, Rule::DanglingReference);
+        assert_eq!(problems[0].finding_id, Some(1));
+
+        let mut missing = finding(1);
+        missing.recommendation = "As in finding:9".to_string();
+        assert_eq!(rules(&state(vec![missing])), vec![Rule::DanglingReference]);
+    }
+
+    #[test]
+    fn unchecked_checklist() {
+        let mut state = state(vec![finding(1)]);
+        state.checklist.push((false, "Reviewed the oracle".to_string()));
+
+        let problems = lint(&state);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].rule, Rule::UncheckedChecklist);
+        assert_eq!(problems[0].finding_id, None);
+    }
+
+    #[test]
+    fn drafts_are_not_linted() {
+        let mut draft = finding(1);
+        draft.draft = true;
+        draft.severity = None;
+        assert_eq!(rules(&state(vec![draft])), vec![]);
+    }
+
+    #[test]
+    fn configured_levels_apply() {
+        let mut finding = finding(1);
+        finding.recommendation = String::new();
+
+        let mut state = state(vec![finding]);
+        assert_eq!(lint(&state)[0].level, Level::Warning);
+
+        state.lint_rules.set_level(Rule::EmptyRecommendation, Level::Error);
+        assert_eq!(lint(&state)[0].level, Level::Error);
+
+        state.lint_rules.set_level(Rule::EmptyRecommendation, Level::Off);
+        assert_eq!(rules(&state), vec![]);
+    }
+}
@@ -1,13 +1,40 @@
 #[macro_use]
 extern crate serde_derive;
 
+pub mod baseline;
+pub mod bundle;
+pub mod chart;
+pub mod collab;
 pub mod command;
+pub mod config;
+pub mod export;
+pub mod filter;
+pub mod git;
+pub mod highlight;
 pub mod html;
+pub mod lint;
+pub mod markdown;
+pub mod merge;
+pub mod reaudit;
+pub mod reference;
+pub mod remap;
+pub mod replace;
 pub mod report;
+pub mod sarif;
+pub mod shortcuts;
+pub mod slither;
+pub mod spreadsheet;
+pub mod summary;
+pub mod template;
+pub mod tool;
+pub mod tracker;
+pub mod workspace;
 
 use crate::{
+    baseline::{Disposition, Suppression, SuppressionKind},
     html::HtmlElement,
-    report::Finding
+    report::{CustomField, FieldKind, Finding, FindingOrder, Impact, Likelihood, Location, Reaudit, Severity, SeverityMatrix, Status, Verdict},
+    summary::Summary
 };
 
 use std::collections::HashMap;
@@ -18,7 +45,37 @@ pub struct StateData {
     pub current_finding_id: usize,
     pub checklist: Vec<(bool, String)>,
     pub findings: HashMap<usize, Finding>,
-    pub copied_finding: Option<Finding>
+    pub copied_finding: Option<Finding>,
+    pub severity_matrix: SeverityMatrix,
+    pub finding_order: FindingOrder,
+    pub title: String,
+    pub auditors: Vec<report::Auditor>,
+    pub start_time: String,
+    pub delivery_time: String,
+    pub repository: String,
+    pub commit_hashes: Vec<String>,
+    pub repository_path: Option<String>,
+    pub checkouts: config::Checkouts,
+    /// Why the user's local checkouts could not be read at startup, until they are saved again.
+    pub checkouts_error: Option<String>,
+    pub fix_commit: Option<String>,
+    pub reaudit_changes: Vec<reaudit::FindingChange>,
+    pub overview: String,
+    pub export_template: Option<String>,
+    pub baseline: Vec<Suppression>,
+    pub lint_rules: lint::Rules,
+    pub custom_fields: Vec<CustomField>,
+    pub finding_filter: filter::Filter,
+    pub find_replace: replace::FindReplace,
+    pub shortcuts: shortcuts::Shortcuts,
+    /// Why the user's shortcuts could not be read at startup, until they are saved again.
+    pub shortcuts_error: Option<String>,
+    pub merge_conflicts: Vec<merge::Conflict>,
+    pub merge_notes: Vec<String>,
+    /// The text last rendered into each shown markdown preview, by finding id and field name.
+    pub finding_previews: HashMap<(usize, String), String>,
+    pub session: Option<collab::Session>,
+    pub workspace: Option<workspace::Workspace>
 }
 
 impl StateData {
@@ -28,7 +85,65 @@ impl StateData {
             current_finding_id: 0,
             checklist: vec![],
             findings: HashMap::new(),
-            copied_finding: None
+            copied_finding: None,
+            severity_matrix: SeverityMatrix::default(),
+            finding_order: FindingOrder::default(),
+            title: "Report Title".to_string(),
+            auditors: vec![
+                report::Auditor {
+                    name: "Camden Smallwood".to_string(),
+                    email: "camden.smallwood@certik.org".to_string()
+                }
+            ],
+            start_time: "Oct. 12, 2020".to_string(),
+            delivery_time: "Oct. 19, 2020".to_string(),
+            repository: "Repository URL".to_string(),
+            commit_hashes: vec!["Commit Hash 1".to_string(), "Commit Hash 2".to_string()],
+            repository_path: None,
+            checkouts: config::Checkouts::default(),
+            checkouts_error: None,
+            fix_commit: None,
+            reaudit_changes: vec![],
+            overview: "Executive Overview".to_string(),
+            export_template: None,
+            baseline: vec![],
+            lint_rules: lint::Rules::default(),
+            custom_fields: vec![],
+            finding_filter: filter::Filter::default(),
+            find_replace: replace::FindReplace::default(),
+            shortcuts: shortcuts::Shortcuts::default(),
+            shortcuts_error: None,
+            merge_conflicts: vec![],
+            merge_notes: vec![],
+            finding_previews: HashMap::new(),
+            session: None,
+            workspace: None
+        }
+    }
+
+    /// Builds a report from the state data with its findings sorted by id.
+    pub fn to_report(&self) -> report::Report {
+        let mut findings: Vec<Finding> = self.findings.values().cloned().collect();
+        findings.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
+
+        report::Report {
+            title: self.title.clone(),
+            auditors: self.auditors.clone(),
+            start_time: self.start_time.clone(),
+            delivery_time: self.delivery_time.clone(),
+            repository: self.repository.clone(),
+            commit_hashes: self.commit_hashes.clone(),
+            repository_path: None,
+            fix_commit: self.fix_commit.clone(),
+            checklist: self.checklist.clone(),
+            overview: self.overview.clone(),
+            findings,
+            severity_matrix: self.severity_matrix.clone(),
+            finding_order: self.finding_order,
+            export_template: self.export_template.clone(),
+            baseline: self.baseline.clone(),
+            lint_rules: self.lint_rules.clone(),
+            custom_fields: self.custom_fields.clone()
         }
     }
 }
@@ -37,6 +152,18 @@ fn main() {
     // Initialize the local state data
     let mut state = StateData::new();
 
+    // Load the user's local checkouts, which are picked again if they can't be read
+    match config::Checkouts::load() {
+        Err(error) => state.checkouts_error = Some(format!("Failed to load the local checkouts, so the checkout has to be selected again: {}", error)),
+        Ok(checkouts) => state.checkouts = checkouts
+    }
+
+    // Load the user's keyboard shortcuts, keeping the defaults if they can't be read
+    match shortcuts::Shortcuts::load() {
+        Err(error) => state.shortcuts_error = Some(format!("Failed to load the keyboard shortcuts, so the defaults are used until a shortcut is changed: {}", error)),
+        Ok(shortcuts) => state.shortcuts = shortcuts
+    }
+
     // Build the web view
     let view = web_view::builder()
         .title("CertiK Workbook")
@@ -46,44 +173,17 @@ fn main() {
         .debug(true)
         .user_data(())
         .invoke_handler(|view, arg| {
-            println!("Command: {}", arg);
-            
-            // Tokenize the input command and parameters
-            let tokens = tokenize_command_string(arg);
-            let mut iter = tokens.iter();
-
-            // Handle the input command
-            match iter.next().unwrap().as_str() {
-                "load_active_workbook" => load_active_workbook(view, &mut state)?,
-                "load_workbook" => load_workbook(view, &mut state)?,
-                "save_workbook" => save_workbook(&mut state)?,
-                "import_markdown" => import_markdown(view, &mut state)?,
-                "export_markdown" => export_markdown(&mut state)?,
-                "export_pdf" => export_pdf(view)?,
-                "create_checklist_entry" => create_checklist_entry(view, &mut state)?,
-                "remove_checklist_entry" => remove_checklist_entry(view, &mut state, iter.next().unwrap().parse().unwrap())?,
-                "clear_checklist_entries" => clear_checklist_entries(view, &mut state)?,
-                "set_checklist_entry_checked" => set_checklist_entry_checked(view, &mut state, iter.next().unwrap().parse().unwrap(), iter.next().unwrap().parse().unwrap())?,
-                "set_checklist_entry_text" => set_checklist_entry_text(view, &mut state, iter.next().unwrap().parse().unwrap(), iter.next().unwrap())?,
-                "create_finding" => create_finding(view, &mut state)?,
-                "copy_finding" => copy_finding(view, &mut state, iter.next().unwrap().parse().unwrap())?,
-                "paste_finding" => paste_finding(view, &mut state)?,
-                "remove_finding" => remove_finding(view, &mut state, iter.next().unwrap().parse().unwrap())?,
-                "clear_findings" => clear_findings(view, &mut state)?,
-                "set_finding_title" => set_finding_title(view, &mut state, iter.next().unwrap().parse().unwrap(), iter.next().unwrap())?,
-                "set_finding_type" => set_finding_type(view, &mut state, iter.next().unwrap().parse().unwrap(), iter.next().unwrap())?,
-                "set_finding_severity" => set_finding_severity(view, &mut state, iter.next().unwrap().parse().unwrap(), iter.next().unwrap())?,
-                "set_finding_location" => set_finding_location(view, &mut state, iter.next().unwrap().parse().unwrap(), iter.next().unwrap())?,
-                "set_finding_description" => set_finding_description(view, &mut state, iter.next().unwrap().parse().unwrap(), iter.next().unwrap())?,
-                "set_finding_recommendation" => set_finding_recommendation(view, &mut state, iter.next().unwrap().parse().unwrap(), iter.next().unwrap())?,
-                "set_finding_alleviation" => set_finding_alleviation(view, &mut state, iter.next().unwrap().parse().unwrap(), iter.next().unwrap())?,
-                command => view.eval(format!("alert(\"Command not implemented: '{}'\")", command).as_str())?
+            // Previews are sent with the whole field while typing, which would flood the log
+            if !arg.starts_with("preview_finding_field ") {
+                println!("Command: {}", arg);
             }
 
-            // Verify all parameters were used
-            assert!(iter.next().is_none());
+            // Messages from a collaboration session are applied without being shared again
+            if let Some(json) = arg.strip_prefix(collab::MESSAGE_COMMAND) {
+                return apply_collab_message(view, &mut state, json);
+            }
 
-            Ok(())
+            dispatch_command(view, &mut state, arg)
         })
         .build()
         .unwrap();
@@ -120,6 +220,23 @@ fn main() {
         //----------------------------------
  
         let app_menu = NSMenu::new(nil).autorelease();
+
+        // Menu items run commands through a target object, which asks the web view to run them
+        static MENU_HANDLE: std::sync::OnceLock<web_view::Handle<()>> = std::sync::OnceLock::new();
+        let _ = MENU_HANDLE.set(view.handle());
+
+        extern "C" fn show_command_palette(_: &objc::runtime::Object, _: objc::runtime::Sel, _: cocoa::base::id) {
+            if let Some(handle) = MENU_HANDLE.get() {
+                let _ = handle.dispatch(|view| view.eval("external.invoke('show_command_palette')"));
+            }
+        }
+
+        let mut menu_target_class = objc::declare::ClassDecl::new("WorkbookMenuTarget", objc::class!(NSObject)).unwrap();
+        menu_target_class.add_method(
+            objc::sel!(showCommandPalette:),
+            show_command_palette as extern "C" fn(&objc::runtime::Object, objc::runtime::Sel, cocoa::base::id)
+        );
+        let menu_target: cocoa::base::id = objc::msg_send![menu_target_class.register(), new];
         
         let undo_title = NSString::alloc(nil).init_str("Undo");
         let undo_action = selector("undo:");
@@ -146,6 +263,16 @@ fn main() {
             .autorelease();
         app_menu.addItem_(select_all_item);
 
+        // The command palette has no key equivalent, since its shortcut is configured in the page
+        let palette_title = NSString::alloc(nil).init_str("Command Palette...");
+        let palette_action = selector("showCommandPalette:");
+        let palette_key = NSString::alloc(nil).init_str("");
+        let palette_item = NSMenuItem::alloc(nil)
+            .initWithTitle_action_keyEquivalent_(palette_title, palette_action, palette_key)
+            .autorelease();
+        let () = objc::msg_send![palette_item, setTarget: menu_target];
+        app_menu.addItem_(palette_item);
+
         let cut_title = NSString::alloc(nil).init_str("Cut");
         let cut_action = selector("cut:");
         let cut_key = NSString::alloc(nil).init_str("x");
@@ -193,40 +320,396 @@ fn main() {
     view.run().unwrap()
 }
 
-fn tokenize_command_string(string: &str) -> Vec<String> {
-    let mut is_string = false;
-    let mut tokens = vec![];
-    let mut token = String::new();
-
-    // Create an iterator to loop over each character
-    let mut iter = string.chars();
-
-    // Build each token using the character iterator
-    while let Some(c) = iter.next() {
-        if c.is_whitespace() {
-            if is_string {
-                // Push all whitespace characters inside a string to the token
-                token.push(c);
-            } else if token.len() != 0 {
-                // Push the current token to the vector if token is not empty
-                tokens.push(token.replace("''", "\""));
-                token = String::new();
-            }
-        } else if c == '"' {
-            // Flip string mode
-            is_string = !is_string;
-        } else {
-            // Push the character to the token
-            token.push(c);
+/// Handles a command from the web view, sharing it with the collaboration session if there is one.
+fn dispatch_command<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, arg: &str) -> web_view::WVResult {
+    let name = arg.split_whitespace().next().unwrap_or("");
+
+    // A malformed command is reported rather than run or shared
+    if let Err(message) = parse_command(arg) {
+        let text = serde_json::to_string(&message).unwrap_or_default();
+        return view.eval(format!("alert({})", text).as_str());
+    }
+
+    if let Some(ref mut session) = state.session {
+        // Shared commands are applied once the host has put them in order
+        if collab::is_shared_command(name) {
+            return match session.send(collab::Message::Submit { command: arg.to_string() }) {
+                Err(error) => set_message(view, "session_message", error.as_str()),
+                Ok(()) => Ok(())
+            };
+        }
+    }
+
+    if state.session.is_none() || !collab::is_snapshot_command(name) {
+        return handle_command(view, state, arg);
+    }
+
+    // Snapshot commands are only run to build the workbook they result in. Like every other change,
+    // it replaces the workbook once the host has put it in order.
+    let before = state.to_report();
+    handle_command(view, state, arg)?;
+    let snapshot = state.to_report();
+
+    // Nothing changed, such as when a dialog was cancelled
+    if snapshot == before {
+        return Ok(());
+    }
+
+    apply_snapshot(view, state, before)?;
+
+    if let Some(ref mut session) = state.session {
+        let message = collab::Message::SubmitSnapshot { base_seq: session.seq, snapshot: Box::new(snapshot) };
+
+        if let Err(error) = session.send(message) {
+            return set_message(view, "session_message", error.as_str());
         }
     }
 
-    // Push the last token (if any)
-    if token.len() != 0 {
-        tokens.push(token.replace("''", "\""));
+    Ok(())
+}
+
+/// The commands which change the findings or checklist counted by the dashboard.
+const DASHBOARD_COMMANDS: [&str; 36] = [
+    "load_active_workbook",
+    "load_workbook",
+    "merge_workbooks",
+    "resolve_conflict",
+    "import_markdown",
+    "import_slither",
+    "import_sarif",
+    "import_baseline",
+    "import_csv_statuses",
+    "remap_locations",
+    "set_reaudit_verdict",
+    "create_checklist_entry",
+    "remove_checklist_entry",
+    "clear_checklist_entries",
+    "set_checklist_entry_checked",
+    "create_finding",
+    "paste_finding",
+    "accept_finding",
+    "suppress_finding",
+    "merge_finding",
+    "remove_finding",
+    "clear_findings",
+    "set_finding_type",
+    "set_finding_severity",
+    "set_finding_likelihood",
+    "set_finding_impact",
+    "set_finding_status",
+    "set_finding_location",
+    "set_severity_matrix_cell",
+    "replace_in_workbook",
+    "replace_in_field",
+    "open_workspace",
+    "open_workspace_workbook",
+    "create_workspace_workbook",
+    "join_session",
+    "host_session"
+];
+
+/// A command whose parameters have been parsed, ready to be run against the state data.
+type Run = Box<dyn FnOnce(&mut web_view::WebView<()>, &mut StateData) -> web_view::WVResult>;
+
+/// Reads the parameters of a command, failing if they are missing or malformed.
+type Parser = fn(&mut command::Arguments) -> Result<Run, String>;
+
+fn run<F>(f: F) -> Result<Run, String>
+where
+    F: FnOnce(&mut web_view::WebView<()>, &mut StateData) -> web_view::WVResult + 'static
+{
+    Ok(Box::new(f))
+}
+
+/// Returns the parser of a command, or `None` if the command is not implemented.
+fn command_parser(name: &str) -> Option<Parser> {
+    let parser: Parser = match name {
+        "load_active_workbook" => |_| run(load_active_workbook),
+        "load_workbook" => |_| run(load_workbook),
+        "save_workbook" => |_| run(|_, state| save_workbook(state)),
+        "save_workbook_bundle" => |_| run(|_, state| save_workbook_bundle(state)),
+        "merge_workbooks" => |_| run(merge_workbooks),
+        "resolve_conflict" => |args| {
+            let (index, candidate) = (args.parse()?, args.parse()?);
+            run(move |view, state| resolve_conflict(view, state, index, candidate))
+        },
+        "import_markdown" => |_| run(import_markdown),
+        "import_slither" => |_| run(import_slither),
+        "import_sarif" => |_| run(import_sarif),
+        "import_baseline" => |_| run(import_baseline),
+        "export_baseline" => |_| run(|_, state| export_baseline(state)),
+        "remove_suppression" => |args| {
+            let index = args.parse()?;
+            run(move |view, state| remove_suppression(view, state, index))
+        },
+        "export_markdown" => |_| run(export_markdown),
+        "export_html" => |_| run(export_html),
+        "export_sarif" => |_| run(export_sarif),
+        "export_csv" => |_| run(export_csv),
+        "export_xlsx" => |_| run(export_xlsx),
+        "export_issue_bundle" => |_| run(export_issue_bundle),
+        "import_csv_statuses" => |_| run(import_csv_statuses),
+        "select_export_template" => |_| run(select_export_template),
+        "reset_export_template" => |_| run(reset_export_template),
+        "save_default_template" => |_| run(|_, _| save_default_template()),
+        "select_repository" => |_| run(select_repository),
+        "refresh_repository" => |_| run(|view, state| update_repository_in_web_view(view, state)),
+        "set_audited_commit" => |args| {
+            let (hash, audited) = (args.text()?, args.parse()?);
+            run(move |view, state| set_audited_commit(view, state, &hash, audited))
+        },
+        "remap_locations" => |args| {
+            let commit = args.text()?;
+            run(move |view, state| remap_locations(view, state, &commit))
+        },
+        "set_fix_commit" => |args| {
+            let fix_commit = args.text()?;
+            run(move |view, state| set_fix_commit(view, state, &fix_commit))
+        },
+        "run_reaudit" => |_| run(run_reaudit),
+        "set_reaudit_verdict" => |args| {
+            let (id, verdict) = (args.parse()?, args.text()?);
+            run(move |view, state| set_reaudit_verdict(view, state, id, &verdict))
+        },
+        "set_report_field" => |args| {
+            let (field, value) = (args.text()?, args.optional_text());
+            run(move |view, state| set_report_field(view, state, &field, &value))
+        },
+        "export_pdf" => |_| run(|view, _| export_pdf(view)),
+        "create_checklist_entry" => |_| run(create_checklist_entry),
+        "remove_checklist_entry" => |args| {
+            let id = args.parse()?;
+            run(move |view, state| remove_checklist_entry(view, state, id))
+        },
+        "clear_checklist_entries" => |_| run(clear_checklist_entries),
+        "set_checklist_entry_checked" => |args| {
+            let (id, checked) = (args.parse()?, args.parse()?);
+            run(move |view, state| set_checklist_entry_checked(view, state, id, checked))
+        },
+        "set_checklist_entry_text" => |args| {
+            let (id, text) = (args.parse()?, args.optional_text());
+            run(move |view, state| set_checklist_entry_text(view, state, id, &text))
+        },
+        "create_finding" => |_| run(create_finding),
+        "copy_finding" => |args| {
+            let id = args.parse()?;
+            run(move |view, state| copy_finding(view, state, id))
+        },
+        "paste_finding" => |_| run(paste_finding),
+        "accept_finding" => |args| {
+            let id = args.parse()?;
+            run(move |view, state| accept_finding(view, state, id))
+        },
+        "suppress_finding" => |args| {
+            let id = args.parse()?;
+            run(move |view, state| suppress_finding(view, state, id))
+        },
+        "merge_finding" => |args| {
+            let (id, target) = (args.parse()?, args.text()?);
+            run(move |view, state| merge_finding(view, state, id, &target))
+        },
+        "remove_finding" => |args| {
+            let id = args.parse()?;
+            run(move |view, state| remove_finding(view, state, id))
+        },
+        "clear_findings" => |_| run(clear_findings),
+        "set_finding_title" => |args| {
+            let (id, title) = (args.parse()?, args.optional_text());
+            run(move |view, state| set_finding_title(view, state, id, &title))
+        },
+        "set_finding_type" => |args| {
+            let (id, class) = (args.parse()?, args.optional_text());
+            run(move |view, state| set_finding_type(view, state, id, &class))
+        },
+        "set_finding_severity" => |args| {
+            let (id, severity) = (args.parse()?, args.text()?);
+            run(move |view, state| set_finding_severity(view, state, id, &severity))
+        },
+        "set_finding_likelihood" => |args| {
+            let (id, likelihood) = (args.parse()?, args.text()?);
+            run(move |view, state| set_finding_likelihood(view, state, id, &likelihood))
+        },
+        "set_finding_impact" => |args| {
+            let (id, impact) = (args.parse()?, args.text()?);
+            run(move |view, state| set_finding_impact(view, state, id, &impact))
+        },
+        "set_finding_justification" => |args| {
+            let (id, justification) = (args.parse()?, args.optional_text());
+            run(move |view, state| set_finding_justification(view, state, id, &justification))
+        },
+        "set_finding_status" => |args| {
+            let (id, status) = (args.parse()?, args.text()?);
+            run(move |view, state| set_finding_status(view, state, id, &status))
+        },
+        "set_finding_location" => |args| {
+            let (id, location) = (args.parse()?, args.optional_text());
+            run(move |view, state| set_finding_location(view, state, id, &location))
+        },
+        "set_finding_description" => |args| {
+            let (id, description) = (args.parse()?, args.optional_text());
+            run(move |view, state| set_finding_description(view, state, id, &description))
+        },
+        "set_finding_recommendation" => |args| {
+            let (id, recommendation) = (args.parse()?, args.optional_text());
+            run(move |view, state| set_finding_recommendation(view, state, id, &recommendation))
+        },
+        "preview_finding_field" => |args| {
+            let (id, name, text) = (args.parse()?, args.text()?, args.optional_text());
+            run(move |view, state| preview_finding_field(view, state, id, &name, &text))
+        },
+        "hide_finding_preview" => |args| {
+            let (id, name) = (args.parse()?, args.text()?);
+            run(move |_, state| { hide_finding_preview(state, id, &name); Ok(()) })
+        },
+        "set_finding_alleviation" => |args| {
+            let (id, alleviation) = (args.parse()?, args.optional_text());
+            run(move |view, state| set_finding_alleviation(view, state, id, &alleviation))
+        },
+        "add_attachments" => |args| {
+            let id = args.parse()?;
+            run(move |view, state| add_attachments(view, state, id))
+        },
+        "remove_attachment" => |args| {
+            let (id, index) = (args.parse()?, args.parse()?);
+            run(move |view, state| remove_attachment(view, state, id, index))
+        },
+        "set_severity_matrix_cell" => |args| {
+            let (likelihood, impact, severity) = (args.text()?, args.text()?, args.text()?);
+            run(move |view, state| set_severity_matrix_cell(view, state, &likelihood, &impact, &severity))
+        },
+        "set_finding_order" => |args| {
+            let order = args.text()?;
+            run(move |view, state| set_finding_order(view, state, &order))
+        },
+        "set_finding_tags" => |args| {
+            let (id, tags) = (args.parse()?, args.optional_text());
+            run(move |view, state| set_finding_tags(view, state, id, &tags))
+        },
+        "set_finding_field" => |args| {
+            let (id, name, value) = (args.parse()?, args.text()?, args.optional_text());
+            run(move |view, state| set_finding_field(view, state, id, &name, &value))
+        },
+        "create_custom_field" => |args| {
+            let (name, kind, options) = (args.optional_text(), args.optional_text(), args.optional_text());
+            run(move |view, state| create_custom_field(view, state, &name, &kind, &options))
+        },
+        "remove_custom_field" => |args| {
+            let index = args.parse()?;
+            run(move |view, state| remove_custom_field(view, state, index))
+        },
+        "set_custom_field_exported" => |args| {
+            let (index, exported) = (args.parse()?, args.parse()?);
+            run(move |_, state| set_custom_field_exported(state, index, exported))
+        },
+        "set_finding_filter" => |args| {
+            let query = args.optional_text();
+            run(move |view, state| set_finding_filter(view, state, &query))
+        },
+        "set_lint_rule" => |args| {
+            let (rule, level) = (args.text()?, args.text()?);
+            run(move |view, state| set_lint_rule(view, state, &rule, &level))
+        },
+        "run_linter" => |_| run(run_linter),
+        "show_command_palette" => |_| run(show_command_palette),
+        "set_shortcut" => |args| {
+            let (name, shortcut) = (args.text()?, args.optional_text());
+            run(move |view, state| set_shortcut(view, state, &name, &shortcut))
+        },
+        "find_in_workbook" => |args| {
+            let (regex, case_sensitive, pattern, replacement) = (args.parse()?, args.parse()?, args.optional_text(), args.optional_text());
+            run(move |view, state| find_in_workbook(view, state, regex, case_sensitive, &pattern, &replacement))
+        },
+        "replace_in_workbook" => |_| run(|view, state| replace_matches(view, state, None)),
+        "replace_in_field" => |args| {
+            let index = args.parse()?;
+            run(move |view, state| replace_matches(view, state, Some(index)))
+        },
+        "open_workspace" => |_| run(open_workspace),
+        "refresh_workspace" => |_| run(refresh_workspace),
+        "open_workspace_workbook" => |args| {
+            let index = args.parse()?;
+            run(move |view, state| open_workspace_workbook(view, state, index))
+        },
+        "create_workspace_workbook" => |args| {
+            let name = args.text()?;
+            run(move |view, state| create_workspace_workbook(view, state, &name))
+        },
+        "set_workspace_auditors" => |args| {
+            let text = args.optional_text();
+            run(move |view, state| set_workspace_auditors(view, state, &text))
+        },
+        "set_workspace_template" => |args| {
+            let name = args.text()?;
+            run(move |view, state| set_workspace_template(view, state, &name))
+        },
+        "save_workspace_defaults" => |_| run(|_, state| save_workspace_defaults(state)),
+        "host_session" => |args| {
+            let (address, name) = (args.text()?, args.optional_text());
+            run(move |view, state| host_session(view, state, &address, &name))
+        },
+        "join_session" => |args| {
+            let (address, name) = (args.text()?, args.optional_text());
+            run(move |view, state| join_session(view, state, &address, &name))
+        },
+        "leave_session" => |_| run(leave_session),
+        "set_presence" => |args| {
+            let id = args.parse()?;
+            run(move |view, state| set_presence(view, state, id))
+        },
+        _ => return None
+    };
+
+    Some(parser)
+}
+
+/// Parses a command string without running it, so that malformed commands are reported instead of
+/// reaching the state data.
+fn parse_command(arg: &str) -> Result<(String, Run), String> {
+    let tokens = command::tokenize(arg);
+    let name = tokens.first().map(String::as_str).unwrap_or("");
+    let parser = command_parser(name).ok_or_else(|| format!("Command not implemented: '{}'", name))?;
+
+    let mut args = command::Arguments::new(name, &tokens[1..]);
+    let run = parser(&mut args)?;
+    args.finish()?;
+
+    Ok((name.to_string(), run))
+}
+
+/// Checks a command submitted to a collaboration session before the host puts it in order.
+pub fn check_shared_command(arg: &str) -> Result<(), String> {
+    let name = arg.split_whitespace().next().unwrap_or("");
+
+    if !collab::is_shared_command(name) {
+        return Err(format!("'{}' cannot be shared with a collaboration session", name));
+    }
+
+    parse_command(arg).map(|_| ())
+}
+
+/// Handles a command from the web view or an operation from a collaboration session.
+fn handle_command<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, arg: &str) -> web_view::WVResult {
+    let (name, run) = parse_command(arg).map_err(|message| web_view::Error::Custom(Box::new(message)))?;
+    let name = name.as_str();
+
+    run(view, state)?;
+
+    // Findings added or edited by the command are shown or hidden by the active filter
+    if !state.finding_filter.is_empty() {
+        update_finding_filter_in_web_view(view, state)?;
+    }
+
+    // Keep the dashboard in sync with the state data
+    if DASHBOARD_COMMANDS.contains(&name) {
+        update_dashboard_in_web_view(view, state)?;
+    }
+
+    // Previews show the display ids and titles of the findings they refer to, which most commands can change
+    if name != "preview_finding_field" && name != "hide_finding_preview" {
+        update_finding_previews_in_web_view(view, state)?;
     }
 
-    tokens
+    Ok(())
 }
 
 fn load_active_workbook<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
@@ -239,44 +722,69 @@ fn load_active_workbook<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut St
     findings.sort_by(|a, b| a.id.cmp(&b.id));
 
     for ref finding in findings {
-        add_finding_to_web_view(view, finding)?;
+        add_finding_to_web_view(view, finding, &state.severity_matrix, &state.custom_fields)?;
     }
 
-    Ok(())
+    add_severity_matrix_to_web_view(view, &state.severity_matrix)?;
+    update_report_details_in_web_view(view, state)?;
+    update_baseline_in_web_view(view, state)?;
+    update_repository_in_web_view(view, state)?;
+    update_reaudit_in_web_view(view, state)?;
+    update_lint_rules_in_web_view(view, state)?;
+    update_custom_fields_in_web_view(view, state)?;
+    update_merge_conflicts_in_web_view(view, state)?;
+    update_session_in_web_view(view, state)?;
+    update_workspace_in_web_view(view, state)?;
+    update_shortcuts_in_web_view(view, state)?;
+    set_message(view, "shortcuts_message", state.shortcuts_error.as_ref().map(String::as_str).unwrap_or(""))?;
+    set_message(view, "checkouts_message", state.checkouts_error.as_deref().unwrap_or(""))?;
+
+    let mut finding_order_select = HtmlElement::get("finding_order_select");
+    finding_order_select.set_value(state.finding_order.name());
+    finding_order_select.build(view)
 }
 
 fn load_workbook<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
-    if let Some(path) = tinyfiledialogs::open_file_dialog("Select a JSON workbook", "workbook.json", None) {
+    if let Some(path) = tinyfiledialogs::open_file_dialog("Select a JSON workbook or workbook bundle", "workbook.json", None) {
         use std::fs;
 
-        match fs::read_to_string(path) {
+        match fs::read(&path) {
             Err(error) => {
                 return Err(web_view::Error::Custom(Box::new(error)))
             }
 
-            Ok(json) => {
-                match serde_json::from_str(json.as_str()) {
+            Ok(bytes) => {
+                match bundle::parse_workbook(&bytes) {
                     Err(error) => {
                         return Err(web_view::Error::Custom(Box::new(error)))
                     }
 
-                    Ok(report::Report { checklist, mut findings, .. }) => {
-                        clear_checklist_entries(view, state)?;
-                        clear_findings(view, state)?;
-
-                        for (index, entry) in checklist.iter().enumerate() {
-                            state.checklist.push(entry.clone());
-                            add_checklist_entry_to_web_view(view, index, (entry.0, entry.1.as_str()))?;
+                    Ok(mut report) => {
+                        // Edits to a workbook opened from the workspace are saved like when switching from the sidebar
+                        if state.workspace.is_some() {
+                            match leave_workspace_workbook(state) {
+                                Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+                                Ok(false) => return Ok(()),
+                                Ok(true) => {}
+                            }
                         }
-                        
-                        findings.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
 
-                        state.current_finding_id = findings.len();
-                        
-                        for (_, finding) in findings.iter().enumerate() {
-                            assert!(state.findings.insert(finding.id, finding.clone()).is_none());                        
-                            add_finding_to_web_view(view, &finding)?;
+                        state.merge_conflicts.clear();
+                        state.merge_notes.clear();
+                        update_merge_conflicts_in_web_view(view, state)?;
+
+                        // Workbooks opened from the workspace directory are switched like ones picked from the sidebar
+                        if let Some(ref mut workspace) = state.workspace {
+                            let path = std::path::PathBuf::from(path);
+                            workspace.current = workspace.entry(&path).map(|entry| entry.path.clone());
+
+                            if workspace.current.is_some() {
+                                workspace.apply_settings(&mut report);
+                            }
                         }
+
+                        load_report(view, state, report)?;
+                        update_workspace_in_web_view(view, state)?;
                     }
                 }
             }
@@ -286,865 +794,3583 @@ fn load_workbook<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData
     Ok(())
 }
 
-fn save_workbook<'a>(state: &mut StateData) -> web_view::WVResult {
-    if let Some(path) = tinyfiledialogs::save_file_dialog("Select a JSON workbook", "workbook.json") {
-        use std::{fs::File, io::Write};
+/// Replaces the state data and the web view contents with the report.
+fn load_report<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, report: report::Report) -> web_view::WVResult {
+    let report::Report { title, auditors, start_time, delivery_time, repository, commit_hashes, repository_path, fix_commit, checklist, overview, mut findings, severity_matrix, finding_order, export_template, baseline, lint_rules, custom_fields } = report;
+
+    clear_checklist_entries(view, state)?;
+    clear_findings(view, state)?;
+
+    state.title = title;
+    state.auditors = auditors;
+    state.start_time = start_time;
+    state.delivery_time = delivery_time;
+    // Older workbooks carry the checkout of whoever saved them, which is only a fallback for this machine
+    state.repository_path = state.checkouts.get(repository.as_str()).or(repository_path);
+    state.repository = repository;
+    state.commit_hashes = commit_hashes;
+    state.fix_commit = fix_commit;
+    state.reaudit_changes.clear();
+    state.overview = overview;
+    state.export_template = export_template;
+    update_report_details_in_web_view(view, state)?;
+    update_repository_in_web_view(view, state)?;
+    update_reaudit_in_web_view(view, state)?;
+
+    state.severity_matrix = severity_matrix;
+    add_severity_matrix_to_web_view(view, &state.severity_matrix)?;
+
+    set_finding_order(view, state, finding_order.name())?;
+
+    state.baseline = baseline;
+    update_baseline_in_web_view(view, state)?;
+
+    state.lint_rules = lint_rules;
+    update_lint_rules_in_web_view(view, state)?;
+
+    state.custom_fields = custom_fields;
+    update_custom_fields_in_web_view(view, state)?;
+
+    for (index, entry) in checklist.iter().enumerate() {
+        state.checklist.push(entry.clone());
+        add_checklist_entry_to_web_view(view, index, (entry.0, entry.1.as_str()))?;
+    }
 
-        let mut file = match File::create(path) {
-            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
-            Ok(file) => file
-        };
+    findings.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
 
-        let mut report = report::Report {
-            title: "Report Title".to_string(),
-            auditors: vec![
-                report::Auditor {
-                    name: "Camden Smallwood".to_string(),
-                    email: "camden.smallwood@certik.org".to_string()
-                }
-            ],
-            start_time: "Oct. 12, 2020".to_string(),
-            delivery_time: "Oct. 19, 2020".to_string(),
-            repository: "Repository URL".to_string(),
-            commit_hashes: vec!["Commit Hash 1".to_string(), "Commit Hash 2".to_string()],
-            checklist: vec![],
-            overview: "Executive Overview".to_string(),
-            findings: vec![]
-        };
+    // Ids are not contiguous once findings have been removed, so continue after the highest one
+    state.current_finding_id = findings.iter().map(|finding| finding.id).max().unwrap_or(0);
 
-        for entry in &state.checklist {
-            report.checklist.push(entry.clone());
-        }
+    for finding in findings.iter() {
+        assert!(state.findings.insert(finding.id, finding.clone()).is_none());
+        add_finding_to_web_view(view, &finding, &state.severity_matrix, &state.custom_fields)?;
+    }
 
-        for (_, finding) in &state.findings {
-            report.findings.push(finding.clone());
-        }
+    // Results for the previous workbook no longer apply
+    run_linter(view, state)
+}
 
-        report.findings.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
+fn read_workbook(path: &str) -> Result<report::Report, String> {
+    let bytes = std::fs::read(path).map_err(|error| error.to_string())?;
+    bundle::parse_workbook(&bytes).map_err(|error| format!("Failed to read {}: {}", path, error))
+}
 
-        match serde_json::to_string(&report) {
-            Err(error) => {
-                return Err(web_view::Error::Custom(Box::new(error)))
-            }
+fn write_workbook(path: &std::path::Path, report: &report::Report) -> Result<(), String> {
+    let json = serde_json::to_string(report).map_err(|error| error.to_string())?;
+    std::fs::write(path, json).map_err(|error| format!("Failed to write {}: {}", path.display(), error))
+}
 
-            Ok(json) => {
-                if let Err(error) = file.write_all(json.as_bytes()) {
-                    return Err(web_view::Error::Custom(Box::new(error)))
-                }
-            }
+fn open_workspace<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::select_folder_dialog("Select the workspace directory", "") {
+        match workspace::Workspace::open(std::path::Path::new(path.as_str())) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(workspace) => state.workspace = Some(workspace)
         }
+
+        update_report_details_in_web_view(view, state)?;
+        update_workspace_in_web_view(view, state)?;
     }
 
     Ok(())
 }
 
-fn import_markdown<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
-    if let Some(path) = tinyfiledialogs::open_file_dialog("Select a Markdown file", "*.md", None) {
-        match std::fs::read_to_string(path) {
-            Err(error) => {
-                return Err(web_view::Error::Custom(Box::new(error)))
-            }
+fn refresh_workspace<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(ref mut workspace) = state.workspace {
+        if let Err(error) = workspace.scan() {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
 
-            Ok(md) => {
+    update_workspace_in_web_view(view, state)
+}
 
-                let arena = comrak::Arena::new();
-                
-                let mut options = comrak::ComrakOptions::default();
-                options.extension.autolink = true;
-                options.extension.description_lists = true;
-                options.extension.footnotes = true;
-                options.extension.strikethrough = true;
-                options.extension.superscript = true;
-                options.extension.table = true;
-                options.extension.tagfilter = true;
-                options.extension.tasklist = true;
+/// Saves the workbook opened from the workspace before it is replaced, such as by switching away
+/// from it or joining a session. Returns false if the workbook is not part of a workspace and the
+/// user chose to keep it.
+fn leave_workspace_workbook(state: &mut StateData) -> Result<bool, String> {
+    let current = state.workspace.as_ref().and_then(|workspace| workspace.current.clone());
 
-                let root = comrak::parse_document(&arena, md.as_str(), &options);
-                let mut iter = root.children();
-                let mut node = iter.next().unwrap();
-                let mut valid = true;
+    match current {
+        Some(path) => {
+            write_workbook(&path, &state.to_report())?;
+            Ok(true)
+        }
 
-                while valid {
-                    use comrak::{
-                        nodes::{NodeHeading, NodeValue},
-                    };
-                    use report::Severity;
-
-                    let mut finding = Finding {
-                        id: state.current_finding_id + 1,
-                        title: String::new(),
-                        class: String::new(),
-                        severity: None,
-                        location: String::new(),
-                        description: String::new(),
-                        recommendation: String::new(),
-                        alleviation: String::new()
-                    };
+        None if state.findings.is_empty() => Ok(true),
 
-                    if let NodeValue::Heading(NodeHeading { level: 3, setext: false }) = node.data.borrow().value {
-                        for child in node.children() {
-                            if let NodeValue::Text(ref text) = child.data.borrow().value {
-                                finding.title.push_str(std::str::from_utf8(text).unwrap());
-                            }
-                        }
-                    } else {
-                        panic!("Expected heading level 3, found {:?}", node);
-                    }
+        None => Ok(
+            tinyfiledialogs::message_box_yes_no(
+                "Replace workbook",
+                "The current workbook is not part of a workspace. Discard it and replace it anyway?",
+                tinyfiledialogs::MessageBoxIcon::Question,
+                tinyfiledialogs::YesNo::No
+            ) == tinyfiledialogs::YesNo::Yes
+        )
+    }
+}
 
-                    node = match iter.next() {
-                        None => break,
-                        Some(node) => node
-                    };
+/// Loads a workbook of the workspace, applying the workspace settings to it.
+fn switch_workspace_workbook<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, path: std::path::PathBuf, mut report: report::Report) -> web_view::WVResult {
+    if let Some(ref workspace) = state.workspace {
+        workspace.apply_settings(&mut report);
+    }
 
-                    if let NodeValue::Table(_) = node.data.borrow().value {
-                        for row_node in node.children() {
-                            if let NodeValue::TableRow(is_header) = row_node.data.borrow().value {
-                                if !is_header {
-                                    for (cell_index, cell_node) in row_node.children().enumerate() {
-                                        if let NodeValue::TableCell = cell_node.data.borrow().value {
-                                            for child in cell_node.children() {
-                                                if let NodeValue::Text(ref text) = child.data.borrow().value {
-                                                    let text = std::str::from_utf8(text).unwrap();
-
-                                                    match cell_index {
-                                                        0 => {
-                                                            finding.class.push_str(text);
-                                                        }
-
-                                                        1 => {
-                                                            finding.severity = match text {
-                                                                "Critical" => Some(Severity::Critical),
-                                                                "Major" => Some(Severity::Major),
-                                                                "Minor" => Some(Severity::Minor),
-                                                                "Informational" => Some(Severity::Informational),
-                                                                _ => None
-                                                            };
-                                                        }
-
-                                                        2 => {
-                                                            finding.location.push_str(text)
-                                                        },
-                                                        _ => ()
-                                                    }
-                                                }
-                                            }
-                                        } else {
-                                            panic!("Expected table cell");
-                                        }
-                                    }
-                                }
-                            } else {
-                                panic!("Expected table row, found {:?}", row_node);
-                            }
-                        }
-                    }
+    state.merge_conflicts.clear();
+    state.merge_notes.clear();
+    update_merge_conflicts_in_web_view(view, state)?;
 
-                    node = match iter.next() {
-                        None => break,
-                        Some(node) => node
-                    };
+    load_report(view, state, report)?;
 
-                    if let NodeValue::Heading(NodeHeading { level: 4, setext: false }) = node.data.borrow().value {
-                        let mut heading = String::new();
+    if let Some(ref mut workspace) = state.workspace {
+        workspace.current = Some(path);
 
-                        for child in node.children() {
-                            if let NodeValue::Text(ref text) = child.data.borrow().value {
-                                heading.push_str(std::str::from_utf8(text).unwrap());
-                            }
-                        }
+        if let Err(error) = workspace.scan() {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
 
-                        assert!(heading == "Description:", "Expected description heading");
-                    } else {
-                        panic!("Expected heading level 4, found {:?}", node);
-                    }
-                        
-                    node = match iter.next() {
-                        None => break,
-                        Some(node) => node
-                    };
+    update_workspace_in_web_view(view, state)
+}
 
-                    'description: loop {
-                        match node.data.borrow().value {
-                            NodeValue::Heading(NodeHeading { level: 3, setext: false }) => break 'description,
-                            NodeValue::Heading(NodeHeading { level: 4, setext: false }) => break 'description,
-                            NodeValue::Text(ref text) => finding.description.push_str(std::str::from_utf8(text).unwrap()),
-                            NodeValue::Code(ref text) => finding.description.push_str(format!("`{}`", std::str::from_utf8(text).unwrap()).as_str()),
-                            NodeValue::CodeBlock(ref code) => finding.description.push_str(format!("\n\n```\n{}```\n", std::str::from_utf8(code.literal.as_slice()).unwrap()).as_str()),
-                            NodeValue::HtmlBlock(_) => (),
-                            NodeValue::Paragraph => for paranode in node.children() {
-                                match paranode.data.borrow().value {
-                                    NodeValue::Heading(NodeHeading { level: 3, setext: false }) => break 'description,
-                                    NodeValue::Heading(NodeHeading { level: 4, setext: false }) => break 'description,
-                                    NodeValue::Text(ref text) => finding.description.push_str(std::str::from_utf8(text).unwrap()),
-                                    NodeValue::Code(ref text) => finding.description.push_str(format!("`{}`", std::str::from_utf8(text).unwrap()).as_str()),
-                                    NodeValue::CodeBlock(ref code) => finding.description.push_str(format!("\n\n```\n{}```\n", std::str::from_utf8(code.literal.as_slice()).unwrap()).as_str()),
-                                    NodeValue::HtmlBlock(_) => (),
-                                    ref node => println!("Unused description paragraph node: {:?}", node)
-                                }
-                            }
-                            ref node => println!("Unused description node: {:?}", node)
-                        }
-                        
-                        node = match iter.next() {
-                            None => break,
-                            Some(node) => node
-                        };
-                    }
+fn open_workspace_workbook<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, index: usize) -> web_view::WVResult {
+    let path = match state.workspace.as_ref().and_then(|workspace| workspace.workbooks.get(index)) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("No workspace workbook for index {} was found!", index)))),
+        Some(entry) => entry.path.clone()
+    };
 
-                    if let NodeValue::Heading(NodeHeading { level: 4, setext: false }) = node.data.borrow().value {
-                        let mut heading = String::new();
+    if state.workspace.as_ref().and_then(|workspace| workspace.current.as_ref()) == Some(&path) {
+        return Ok(());
+    }
 
-                        for child in node.children() {
-                            if let NodeValue::Text(ref text) = child.data.borrow().value {
-                                heading.push_str(std::str::from_utf8(text).unwrap());
-                            }
-                        }
+    // Switching is local to this instance, so it would leave the session's workbook behind
+    if state.session.is_some() {
+        return view.eval("alert('Leave the collaboration session before switching to another workbook of the workspace')");
+    }
 
-                        assert!(heading == "Recommendation:", "Expected recommendation heading");
-                    } else {
-                        panic!("Expected heading level 4, found {:?}", node);
-                    }
+    match leave_workspace_workbook(state) {
+        Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+        Ok(false) => return Ok(()),
+        Ok(true) => {}
+    }
 
-                    node = match iter.next() {
-                        None => break,
-                        Some(node) => node
-                    };
+    match read_workbook(path.to_string_lossy().as_ref()) {
+        Err(error) => Err(web_view::Error::Custom(Box::new(error))),
+        Ok(report) => switch_workspace_workbook(view, state, path, report)
+    }
+}
 
-                    'recommendation: loop {
-                        match node.data.borrow().value {
-                            NodeValue::Heading(NodeHeading { level: 3, setext: false }) => break 'recommendation,
-                            NodeValue::Heading(NodeHeading { level: 4, setext: false }) => break 'recommendation,
-                            NodeValue::Text(ref text) => finding.recommendation.push_str(std::str::from_utf8(text).unwrap()),
-                            NodeValue::Code(ref text) => finding.recommendation.push_str(format!("`{}`", std::str::from_utf8(text).unwrap()).as_str()),
-                            NodeValue::CodeBlock(ref code) => finding.recommendation.push_str(format!("\n\n```\n{}```\n", std::str::from_utf8(code.literal.as_slice()).unwrap()).as_str()),
-                            NodeValue::HtmlBlock(_) => (),
-                            NodeValue::Paragraph => for paranode in node.children() {
-                                match paranode.data.borrow().value {
-                                    NodeValue::Heading(NodeHeading { level: 3, setext: false }) => break 'recommendation,
-                                    NodeValue::Heading(NodeHeading { level: 4, setext: false }) => break 'recommendation,
-                                    NodeValue::Text(ref text) => finding.recommendation.push_str(std::str::from_utf8(text).unwrap()),
-                                    NodeValue::Code(ref text) => finding.recommendation.push_str(format!("`{}`", std::str::from_utf8(text).unwrap()).as_str()),
-                                    NodeValue::CodeBlock(ref code) => finding.recommendation.push_str(format!("\n\n```\n{}```\n", std::str::from_utf8(code.literal.as_slice()).unwrap()).as_str()),
-                                    NodeValue::HtmlBlock(_) => (),
-                                    ref node => println!("Unused recommendation paragraph node: {:?}", node)
+fn create_workspace_workbook<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, name: &str) -> web_view::WVResult {
+    let (path, report) = match state.workspace {
+        None => return Err(web_view::Error::Custom(Box::new("Open a workspace before creating workbooks in it".to_string()))),
+        Some(ref workspace) => match workspace.workbook_path(name) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(path) => (path, workspace.new_report(name.trim().rsplit('/').next().unwrap_or(name)))
+        }
+    };
+
+    match leave_workspace_workbook(state) {
+        Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+        Ok(false) => return Ok(()),
+        Ok(true) => {}
+    }
+
+    if let Some(parent) = path.parent() {
+        if let Err(error) = std::fs::create_dir_all(parent) {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
+
+    if let Err(error) = write_workbook(&path, &report) {
+        return Err(web_view::Error::Custom(Box::new(error)))
+    }
+
+    switch_workspace_workbook(view, state, path, report)
+}
+
+fn set_workspace_auditors<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, text: &str) -> web_view::WVResult {
+    let workspace = match state.workspace {
+        None => return Err(web_view::Error::Custom(Box::new("No workspace is open".to_string()))),
+        Some(ref mut workspace) => workspace
+    };
+
+    workspace.settings.auditors = workspace::parse_auditors(text);
+
+    if let Err(error) = workspace.save_settings() {
+        return Err(web_view::Error::Custom(Box::new(error)))
+    }
+
+    if workspace.current.is_some() && !workspace.settings.auditors.is_empty() {
+        state.auditors = workspace.settings.auditors.clone();
+    }
+
+    update_workspace_in_web_view(view, state)
+}
+
+fn set_workspace_template<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, name: &str) -> web_view::WVResult {
+    let workspace = match state.workspace {
+        None => return Err(web_view::Error::Custom(Box::new("No workspace is open".to_string()))),
+        Some(ref mut workspace) => workspace
+    };
+
+    workspace.settings.export_template = match name {
+        "none" => None,
+        name => Some(name.to_string())
+    };
+
+    if let Err(error) = workspace.save_settings() {
+        return Err(web_view::Error::Custom(Box::new(error)))
+    }
+
+    update_report_details_in_web_view(view, state)?;
+    update_workspace_in_web_view(view, state)
+}
+
+/// Stores the severity matrix, finding order and checklist of the current workbook as the defaults for new workbooks.
+fn save_workspace_defaults(state: &mut StateData) -> web_view::WVResult {
+    let workspace = match state.workspace {
+        None => return Err(web_view::Error::Custom(Box::new("No workspace is open".to_string()))),
+        Some(ref mut workspace) => workspace
+    };
+
+    workspace.settings.severity_matrix = Some(state.severity_matrix.clone());
+    workspace.settings.finding_order = Some(state.finding_order);
+    workspace.settings.checklist = state.checklist.iter().map(|(_, text)| text.clone()).collect();
+    workspace.settings.custom_fields = state.custom_fields.clone();
+
+    match workspace.save_settings() {
+        Err(error) => Err(web_view::Error::Custom(Box::new(error))),
+        Ok(()) => Ok(())
+    }
+}
+
+fn merge_workbooks<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    let replace = state.findings.is_empty() || tinyfiledialogs::message_box_yes_no(
+        "Merge workbooks",
+        "The merged workbook replaces the current one, and unsaved changes to it are lost. Merge anyway?",
+        tinyfiledialogs::MessageBoxIcon::Question,
+        tinyfiledialogs::YesNo::No
+    ) == tinyfiledialogs::YesNo::Yes;
+
+    if !replace {
+        return Ok(());
+    }
+
+    let base = match tinyfiledialogs::open_file_dialog("Select the common base workbook", "*.json", None) {
+        None => return Ok(()),
+        Some(path) => match read_workbook(path.as_str()) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(base) => base
+        }
+    };
+
+    let paths = match tinyfiledialogs::open_file_dialog_multi("Select the workbooks to merge", "*.json", None) {
+        None => return Ok(()),
+        Some(paths) => paths
+    };
+
+    let mut workbooks = vec![];
+    let names = merge::workbook_names(&paths.iter().map(std::path::Path::new).collect::<Vec<_>>());
+
+    for (path, name) in paths.iter().zip(names) {
+        match read_workbook(path.as_str()) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(report) => workbooks.push(merge::Workbook { name, report })
+        }
+    }
+
+    let result = match merge::merge(&base, &workbooks) {
+        Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+        Ok(result) => result
+    };
+
+    let merge::MergeResult { report, conflicts, notes } = result;
+
+    load_report(view, state, report)?;
+
+    state.merge_conflicts = conflicts;
+    state.merge_notes = notes;
+    update_merge_conflicts_in_web_view(view, state)?;
+
+    view.eval(
+        format!(
+            "alert('Merged {} workbooks: {} conflicts to resolve, {} findings removed or renumbered. See the Merge panel for details.')",
+            workbooks.len(),
+            state.merge_conflicts.len(),
+            state.merge_notes.len()
+        ).as_str()
+    )
+}
+
+fn resolve_conflict<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, index: usize, candidate: usize) -> web_view::WVResult {
+    let conflict = match state.merge_conflicts.get(index) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("No merge conflict for index {} was found!", index)))),
+        Some(conflict) => conflict.clone()
+    };
+
+    let mut report = state.to_report();
+
+    if let Err(error) = merge::resolve(&mut report, &conflict, candidate) {
+        return Err(web_view::Error::Custom(Box::new(error)))
+    }
+
+    let merge_conflicts = std::mem::take(&mut state.merge_conflicts);
+    let merge_notes = std::mem::take(&mut state.merge_notes);
+    load_report(view, state, report)?;
+
+    state.merge_conflicts = merge_conflicts;
+    state.merge_notes = merge_notes;
+    state.merge_conflicts[index].chosen = Some(candidate);
+    update_merge_conflicts_in_web_view(view, state)
+}
+
+fn session_name(state: &StateData, name: &str) -> String {
+    match name.trim() {
+        "" => state.auditors.first().map(|auditor| auditor.name.clone()).unwrap_or_else(|| "Auditor".to_string()),
+        name => name.to_string()
+    }
+}
+
+fn host_session<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, address: &str, name: &str) -> web_view::WVResult {
+    if state.session.is_some() {
+        return set_message(view, "session_message", "Leave the current session before hosting another one");
+    }
+
+    let name = session_name(state, name);
+
+    match collab::Session::host(view.handle(), address.trim(), name.as_str(), state.to_report()) {
+        Err(error) => return set_message(view, "session_message", error.as_str()),
+        Ok(session) => state.session = Some(session)
+    }
+
+    set_message(view, "session_message", "")?;
+    update_session_in_web_view(view, state)
+}
+
+fn join_session<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, address: &str, name: &str) -> web_view::WVResult {
+    if state.session.is_some() {
+        return set_message(view, "session_message", "Leave the current session before joining another one");
+    }
+
+    // The workbook is replaced by the host's once it welcomes this instance
+    match leave_workspace_workbook(state) {
+        Err(error) => return set_message(view, "session_message", format!("Failed to save the workspace workbook: {}", error).as_str()),
+        Ok(false) => return Ok(()),
+        Ok(true) => {}
+    }
+
+    let name = session_name(state, name);
+
+    match collab::Session::join(view.handle(), address.trim(), name.as_str()) {
+        Err(error) => return set_message(view, "session_message", error.as_str()),
+        Ok(session) => state.session = Some(session)
+    }
+
+    set_message(view, "session_message", "")?;
+    update_session_in_web_view(view, state)
+}
+
+fn leave_session<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(session) = state.session.take() {
+        session.leave();
+    }
+
+    update_session_in_web_view(view, state)
+}
+
+fn set_presence<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
+    if let Some(ref mut session) = state.session {
+        if let Err(error) = session.set_presence(Some(id)) {
+            return set_message(view, "session_message", error.as_str());
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces the workbook with one shared in the session, keeping the settings which are local to this instance.
+fn apply_snapshot<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, mut report: report::Report) -> web_view::WVResult {
+    report.repository_path = state.repository_path.clone();
+    report.export_template = state.export_template.clone();
+
+    // The shared workbook is not the one opened from the workspace, which must not be overwritten with
+    // it. Edits made to that workbook since it was last saved are kept in it first.
+    if state.workspace.as_ref().is_some_and(|workspace| workspace.current.is_some()) {
+        if let Err(error) = leave_workspace_workbook(state) {
+            set_message(view, "session_message", format!("Failed to save the workspace workbook before replacing it: {}", error).as_str())?;
+        }
+
+        if let Some(ref mut workspace) = state.workspace {
+            workspace.current = None;
+        }
+
+        update_workspace_in_web_view(view, state)?;
+    }
+
+    load_report(view, state, report)
+}
+
+fn apply_collab_message<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, json: &str) -> web_view::WVResult {
+    // Another instance may run a different version of the workbook
+    let message: collab::Message = match serde_json::from_str(json) {
+        Err(error) => return set_message(view, "session_message", format!("Ignored a message from the session which could not be read: {}", error).as_str()),
+        Ok(message) => message
+    };
+
+    // Messages still in flight when the session ended are dropped
+    let session = match state.session {
+        None => return Ok(()),
+        Some(ref mut session) => session
+    };
+
+    match message {
+        collab::Message::Welcome { client_id, snapshot } => {
+            session.client_id = Some(client_id);
+            apply_snapshot(view, state, *snapshot)?;
+            update_dashboard_in_web_view(view, state)?;
+            update_find_results_in_web_view(view, state)?;
+        }
+
+        collab::Message::Operation { seq, client_id, command } => {
+            session.seq = seq;
+
+            let peer = session.peers.iter()
+                .find(|peer| peer.id == client_id)
+                .map(|peer| peer.name.clone())
+                .unwrap_or_else(|| format!("instance {}", client_id));
+
+            // An operation can fail when it raced with another, such as two auditors removing the same finding
+            if handle_command(view, state, command.as_str()).is_err() {
+                return set_message(view, "session_message", format!("Failed to apply operation {} from {}: {}", seq, peer, command).as_str());
+            }
+
+            // Replacements in a session are only made once the host orders them, so the results are refreshed here
+            if !state.find_replace.pattern.is_empty() {
+                update_find_results_in_web_view(view, state)?;
+            }
+
+            return Ok(());
+        }
+
+        collab::Message::Snapshot { seq, snapshot, .. } => {
+            session.seq = seq;
+            apply_snapshot(view, state, *snapshot)?;
+            update_dashboard_in_web_view(view, state)?;
+            update_find_results_in_web_view(view, state)?;
+        }
+
+        collab::Message::Peers { peers } => session.peers = peers,
+
+        collab::Message::Closed { reason } => {
+            if let Some(session) = state.session.take() {
+                session.leave();
+            }
+
+            view.eval(format!("alert({})", serde_json::to_string(&reason).unwrap_or_default()).as_str())?;
+        }
+
+        // The remaining messages are only sent to the host
+        _ => {}
+    }
+
+    update_session_in_web_view(view, state)
+}
+
+fn save_workbook<'a>(state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::save_file_dialog("Select a JSON workbook", "workbook.json") {
+        use std::{fs::File, io::Write};
+
+        let mut file = match File::create(path) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(file) => file
+        };
+
+        let report = state.to_report();
+
+        match serde_json::to_string(&report) {
+            Err(error) => {
+                return Err(web_view::Error::Custom(Box::new(error)))
+            }
+
+            Ok(json) => {
+                if let Err(error) = file.write_all(json.as_bytes()) {
+                    return Err(web_view::Error::Custom(Box::new(error)))
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn save_workbook_bundle<'a>(state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::save_file_dialog("Select a workbook bundle", "workbook.zip") {
+        let bytes = match bundle::write(&state.to_report()) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(bytes) => bytes
+        };
+
+        if let Err(error) = std::fs::write(path, bytes) {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
+
+    Ok(())
+}
+
+fn import_markdown<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::open_file_dialog("Select a Markdown file", "*.md", None) {
+        match std::fs::read_to_string(path) {
+            Err(error) => {
+                return Err(web_view::Error::Custom(Box::new(error)))
+            }
+
+            Ok(md) => {
+
+                let arena = comrak::Arena::new();
+                let options = markdown::options();
+                let root = comrak::parse_document(&arena, md.as_str(), &options);
+                let mut iter = root.children();
+                let mut node = iter.next().unwrap();
+                let mut valid = true;
+
+                while valid {
+                    use comrak::{
+                        nodes::{NodeHeading, NodeValue},
+                    };
+
+                    let mut finding = Finding::new(state.current_finding_id + 1);
+
+                    if let NodeValue::Heading(NodeHeading { level: 3, setext: false }) = node.data.borrow().value {
+                        for child in node.children() {
+                            if let NodeValue::Text(ref text) = child.data.borrow().value {
+                                finding.title.push_str(std::str::from_utf8(text).unwrap());
+                            }
+                        }
+                    } else {
+                        panic!("Expected heading level 3, found {:?}", node);
+                    }
+
+                    node = match iter.next() {
+                        None => break,
+                        Some(node) => node
+                    };
+
+                    if let NodeValue::Table(_) = node.data.borrow().value {
+                        let mut columns = vec![];
+
+                        for row_node in node.children() {
+                            if let NodeValue::TableRow(is_header) = row_node.data.borrow().value {
+                                for (cell_index, cell_node) in row_node.children().enumerate() {
+                                    if let NodeValue::TableCell = cell_node.data.borrow().value {
+                                        if is_header {
+                                            columns.push(String::new());
+                                        }
+
+                                        for child in cell_node.children() {
+                                            if let NodeValue::Text(ref text) = child.data.borrow().value {
+                                                let text = std::str::from_utf8(text).unwrap();
+
+                                                if is_header {
+                                                    columns[cell_index].push_str(text);
+                                                    continue;
+                                                }
+
+                                                match columns.get(cell_index).map(|column| column.trim()) {
+                                                    Some("Type") => {
+                                                        finding.class.push_str(text);
+                                                    }
+
+                                                    Some("Severity") => {
+                                                        finding.severity = Severity::from_name(text);
+                                                    }
+
+                                                    Some("Likelihood") => {
+                                                        finding.likelihood = Likelihood::from_name(text);
+                                                    }
+
+                                                    Some("Impact") => {
+                                                        finding.impact = Impact::from_name(text);
+                                                    }
+
+                                                    Some("Location") => {
+                                                        finding.location.push_str(text)
+                                                    },
+                                                    _ => ()
+                                                }
+                                            }
+                                        }
+                                    } else {
+                                        panic!("Expected table cell");
+                                    }
                                 }
+                            } else {
+                                panic!("Expected table row, found {:?}", row_node);
                             }
-                            ref node => println!("Unused recommendation node: {:?}", node)
                         }
+                    }
 
-                        node = match iter.next() {
-                            None => {
-                                valid = false;
-                                break;
-                            },
-                            Some(node) => node
-                        };
+                    node = match iter.next() {
+                        None => break,
+                        Some(node) => node
+                    };
+
+                    finding.severity_overridden = match (finding.severity, finding.matrix_severity(&state.severity_matrix)) {
+                        (Some(severity), Some(matrix_severity)) => severity != matrix_severity,
+                        _ => false
+                    };
+
+                    let mut heading = String::new();
+
+                    if let NodeValue::Heading(NodeHeading { level: 4, setext: false }) = node.data.borrow().value {
+                        for child in node.children() {
+                            if let NodeValue::Text(ref text) = child.data.borrow().value {
+                                heading.push_str(std::str::from_utf8(text).unwrap());
+                            }
+                        }
+                    } else {
+                        panic!("Expected heading level 4, found {:?}", node);
                     }
 
-                    assert!(state.findings.insert(finding.id, finding.clone()).is_none());
-                    state.current_finding_id += 1;
+                    if heading == "Severity Justification:" {
+                        'justification: loop {
+                            node = match iter.next() {
+                                None => break,
+                                Some(node) => node
+                            };
+
+                            match node.data.borrow().value {
+                                NodeValue::Heading(NodeHeading { level: 4, setext: false }) => break 'justification,
+                                NodeValue::Paragraph => {
+                                    if !finding.justification.is_empty() {
+                                        finding.justification.push_str("\n\n");
+                                    }
+
+                                    for paranode in node.children() {
+                                        match paranode.data.borrow().value {
+                                            NodeValue::Text(ref text) => finding.justification.push_str(std::str::from_utf8(text).unwrap()),
+                                            NodeValue::Code(ref text) => finding.justification.push_str(format!("`{}`", std::str::from_utf8(text).unwrap()).as_str()),
+                                            ref node => println!("Unused justification paragraph node: {:?}", node)
+                                        }
+                                    }
+                                }
+                                NodeValue::HtmlBlock(_) => (),
+                                ref node => println!("Unused justification node: {:?}", node)
+                            }
+                        }
+
+                        heading.clear();
+
+                        for child in node.children() {
+                            if let NodeValue::Text(ref text) = child.data.borrow().value {
+                                heading.push_str(std::str::from_utf8(text).unwrap());
+                            }
+                        }
+                    }
+
+                    assert!(heading == "Description:", "Expected description heading");
+                        
+                    node = match iter.next() {
+                        None => break,
+                        Some(node) => node
+                    };
+
+                    'description: loop {
+                        match node.data.borrow().value {
+                            NodeValue::Heading(NodeHeading { level: 3, setext: false }) => break 'description,
+                            NodeValue::Heading(NodeHeading { level: 4, setext: false }) => break 'description,
+                            NodeValue::Text(ref text) => finding.description.push_str(std::str::from_utf8(text).unwrap()),
+                            NodeValue::Code(ref text) => finding.description.push_str(format!("`{}`", std::str::from_utf8(text).unwrap()).as_str()),
+                            NodeValue::CodeBlock(ref code) => finding.description.push_str(format!("\n\n```{}\n{}```\n", std::str::from_utf8(code.info.as_slice()).unwrap(), std::str::from_utf8(code.literal.as_slice()).unwrap()).as_str()),
+                            NodeValue::HtmlBlock(_) => (),
+                            NodeValue::Paragraph => for paranode in node.children() {
+                                match paranode.data.borrow().value {
+                                    NodeValue::Heading(NodeHeading { level: 3, setext: false }) => break 'description,
+                                    NodeValue::Heading(NodeHeading { level: 4, setext: false }) => break 'description,
+                                    NodeValue::Text(ref text) => finding.description.push_str(std::str::from_utf8(text).unwrap()),
+                                    NodeValue::Code(ref text) => finding.description.push_str(format!("`{}`", std::str::from_utf8(text).unwrap()).as_str()),
+                                    NodeValue::CodeBlock(ref code) => finding.description.push_str(format!("\n\n```{}\n{}```\n", std::str::from_utf8(code.info.as_slice()).unwrap(), std::str::from_utf8(code.literal.as_slice()).unwrap()).as_str()),
+                                    NodeValue::HtmlBlock(_) => (),
+                                    ref node => println!("Unused description paragraph node: {:?}", node)
+                                }
+                            }
+                            ref node => println!("Unused description node: {:?}", node)
+                        }
+                        
+                        node = match iter.next() {
+                            None => break,
+                            Some(node) => node
+                        };
+                    }
+
+                    if let NodeValue::Heading(NodeHeading { level: 4, setext: false }) = node.data.borrow().value {
+                        let mut heading = String::new();
+
+                        for child in node.children() {
+                            if let NodeValue::Text(ref text) = child.data.borrow().value {
+                                heading.push_str(std::str::from_utf8(text).unwrap());
+                            }
+                        }
+
+                        assert!(heading == "Recommendation:", "Expected recommendation heading");
+                    } else {
+                        panic!("Expected heading level 4, found {:?}", node);
+                    }
+
+                    node = match iter.next() {
+                        None => break,
+                        Some(node) => node
+                    };
+
+                    'recommendation: loop {
+                        match node.data.borrow().value {
+                            NodeValue::Heading(NodeHeading { level: 1..=2, .. }) => {
+                                valid = false;
+                                break 'recommendation;
+                            }
+                            NodeValue::Heading(NodeHeading { level: 3, setext: false }) => break 'recommendation,
+                            NodeValue::Heading(NodeHeading { level: 4, setext: false }) => break 'recommendation,
+                            NodeValue::Text(ref text) => finding.recommendation.push_str(std::str::from_utf8(text).unwrap()),
+                            NodeValue::Code(ref text) => finding.recommendation.push_str(format!("`{}`", std::str::from_utf8(text).unwrap()).as_str()),
+                            NodeValue::CodeBlock(ref code) => finding.recommendation.push_str(format!("\n\n```{}\n{}```\n", std::str::from_utf8(code.info.as_slice()).unwrap(), std::str::from_utf8(code.literal.as_slice()).unwrap()).as_str()),
+                            NodeValue::HtmlBlock(_) => (),
+                            NodeValue::Paragraph => for paranode in node.children() {
+                                match paranode.data.borrow().value {
+                                    NodeValue::Heading(NodeHeading { level: 3, setext: false }) => break 'recommendation,
+                                    NodeValue::Heading(NodeHeading { level: 4, setext: false }) => break 'recommendation,
+                                    NodeValue::Text(ref text) => finding.recommendation.push_str(std::str::from_utf8(text).unwrap()),
+                                    NodeValue::Code(ref text) => finding.recommendation.push_str(format!("`{}`", std::str::from_utf8(text).unwrap()).as_str()),
+                                    NodeValue::CodeBlock(ref code) => finding.recommendation.push_str(format!("\n\n```{}\n{}```\n", std::str::from_utf8(code.info.as_slice()).unwrap(), std::str::from_utf8(code.literal.as_slice()).unwrap()).as_str()),
+                                    NodeValue::HtmlBlock(_) => (),
+                                    ref node => println!("Unused recommendation paragraph node: {:?}", node)
+                                }
+                            }
+                            ref node => println!("Unused recommendation node: {:?}", node)
+                        }
+
+                        node = match iter.next() {
+                            None => {
+                                valid = false;
+                                break;
+                            },
+                            Some(node) => node
+                        };
+                    }
+
+                    assert!(state.findings.insert(finding.id, finding.clone()).is_none());
+                    state.current_finding_id += 1;
+
+                    add_finding_to_web_view(view, &finding, &state.severity_matrix, &state.custom_fields)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn import_slither<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::open_file_dialog("Select a Slither JSON file", "*.json", None) {
+        match std::fs::read_to_string(path).map_err(|error| error.to_string()).and_then(|json| slither::parse(json.as_str())) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(results) => add_tool_results(view, state, results)?
+        }
+    }
+
+    Ok(())
+}
+
+fn import_sarif<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::open_file_dialog("Select a SARIF file", "*.sarif", None) {
+        match std::fs::read_to_string(path).map_err(|error| error.to_string()).and_then(|json| sarif::parse(json.as_str())) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(results) => add_tool_results(view, state, results)?
+        }
+    }
+
+    Ok(())
+}
+
+fn add_tool_results<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, results: Vec<tool::ToolResult>) -> web_view::WVResult {
+    let (mut added, mut suppressed, mut linked) = (0, 0, 0);
+
+    for result in results {
+        let fingerprint = baseline::fingerprint(&result);
+
+        match baseline::disposition(state, fingerprint.as_str()) {
+            Disposition::Suppressed => suppressed += 1,
+
+            Disposition::Linked(id) => {
+                // Keep the fingerprint on the existing finding so future imports recognize it directly
+                let finding = state.findings.get_mut(&id).unwrap();
+
+                if !finding.fingerprints.contains(&fingerprint) {
+                    finding.fingerprints.push(fingerprint);
+                }
+
+                linked += 1;
+            }
+
+            Disposition::New => {
+                state.current_finding_id += 1;
+
+                let finding = result.to_finding(state.current_finding_id);
+                add_finding_to_web_view(view, &finding, &state.severity_matrix, &state.custom_fields)?;
+                assert!(state.findings.insert(finding.id, finding).is_none());
+
+                added += 1;
+            }
+        }
+    }
+
+    view.eval(
+        format!(
+            "alert('Imported {} new draft findings, skipped {} suppressed results and linked {} results to existing findings')",
+            added, suppressed, linked
+        ).as_str()
+    )
+}
+
+fn import_baseline<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::open_file_dialog("Select a baseline file", "*.json", None) {
+        let baseline: Vec<Suppression> = match std::fs::read_to_string(path).map_err(|error| error.to_string()).and_then(|json| serde_json::from_str(json.as_str()).map_err(|error| error.to_string())) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(baseline) => baseline
+        };
+
+        for suppression in baseline {
+            if !state.baseline.iter().any(|existing| existing.fingerprint == suppression.fingerprint) {
+                state.baseline.push(suppression);
+            }
+        }
+
+        update_baseline_in_web_view(view, state)?;
+    }
+
+    Ok(())
+}
+
+fn export_baseline<'a>(state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::save_file_dialog("Select a baseline file", "baseline.json") {
+        let json = match serde_json::to_string_pretty(&state.baseline) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(json) => json
+        };
+
+        if let Err(error) = std::fs::write(path, json) {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_suppression<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, index: usize) -> web_view::WVResult {
+    if index < state.baseline.len() {
+        let _ = state.baseline.remove(index);
+        update_baseline_in_web_view(view, state)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No suppression for index {} was found!", index))))
+    }
+}
+
+/// Lints the report before it is exported, returning false when errors block the export or the
+/// auditor chose not to export with the warnings.
+fn lint_before_export<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> Result<bool, web_view::Error> {
+    let problems = lint::lint(state);
+    update_lint_problems_in_web_view(view, state, &problems)?;
+
+    match lint::count(&problems) {
+        (0, 0) => Ok(true),
+
+        (0, warnings) => Ok(
+            tinyfiledialogs::message_box_yes_no(
+                "Report Linter",
+                format!("The report has {} warnings, which are listed in the Report Linter panel. Export anyway?", warnings).as_str(),
+                tinyfiledialogs::MessageBoxIcon::Warning,
+                tinyfiledialogs::YesNo::No
+            ) == tinyfiledialogs::YesNo::Yes
+        ),
+
+        (errors, _) => {
+            let mut lint_details = HtmlElement::get("lint_details");
+            lint_details.set_field("open", "true");
+            lint_details.build(view)?;
+
+            view.eval(format!("alert('The report has {} errors, which must be fixed before exporting. See the Report Linter panel.')", errors).as_str())?;
+            Ok(false)
+        }
+    }
+}
+
+fn export_markdown<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if !lint_before_export(view, state)? {
+        return Ok(());
+    }
+
+    if let Some(path) = tinyfiledialogs::save_file_dialog("Select a Markdown file", "workbook.md") {
+        let md = match export::markdown(state) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(md) => md
+        };
+
+        if let Err(error) = std::fs::write(path, md) {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
+
+    Ok(())
+}
+
+fn export_html<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if !lint_before_export(view, state)? {
+        return Ok(());
+    }
+
+    if let Some(path) = tinyfiledialogs::save_file_dialog("Select an HTML file", "report.html") {
+        let html = match export::html(state) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(html) => html
+        };
+
+        if let Err(error) = std::fs::write(path, html) {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
+
+    Ok(())
+}
+
+fn export_sarif<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if !lint_before_export(view, state)? {
+        return Ok(());
+    }
+
+    if let Some(path) = tinyfiledialogs::save_file_dialog("Select a SARIF file", "workbook.sarif") {
+        let sarif = match sarif::export(state) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(sarif) => sarif
+        };
+
+        if let Err(error) = std::fs::write(path, sarif) {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
+
+    Ok(())
+}
+
+fn export_csv<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if !lint_before_export(view, state)? {
+        return Ok(());
+    }
+
+    if let Some(path) = tinyfiledialogs::save_file_dialog("Select a CSV file", "findings.csv") {
+        // The checklist is written next to the findings, since a CSV file only holds a single sheet
+        let checklist_path = match path.strip_suffix(".csv") {
+            Some(stem) => format!("{}_checklist.csv", stem),
+            None => format!("{}_checklist.csv", path)
+        };
+
+        for (path, csv) in vec![(path, spreadsheet::findings_csv(state)), (checklist_path, spreadsheet::checklist_csv(state))] {
+            let csv = match csv {
+                Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+                Ok(csv) => csv
+            };
+
+            if let Err(error) = std::fs::write(path, csv) {
+                return Err(web_view::Error::Custom(Box::new(error)))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn export_xlsx<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if !lint_before_export(view, state)? {
+        return Ok(());
+    }
+
+    if let Some(path) = tinyfiledialogs::save_file_dialog("Select an XLSX file", "findings.xlsx") {
+        let xlsx = match spreadsheet::xlsx(state) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(xlsx) => xlsx
+        };
+
+        if let Err(error) = std::fs::write(path, xlsx) {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
+
+    Ok(())
+}
+
+fn export_issue_bundle<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if !lint_before_export(view, state)? {
+        return Ok(());
+    }
+
+    if let Some(path) = tinyfiledialogs::select_folder_dialog("Select a folder for the issue bundle", "") {
+        match tracker::write_bundle(state, std::path::Path::new(path.as_str())) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(count) => view.eval(format!("alert('Wrote {} issues to the bundle')", count).as_str())?
+        }
+    }
+
+    Ok(())
+}
+
+fn import_csv_statuses<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::open_file_dialog("Select a findings CSV file", "*.csv", None) {
+        let statuses = match std::fs::read_to_string(path).map_err(|error| error.to_string()).and_then(|csv| spreadsheet::parse_statuses(csv.as_str())) {
+            Err(error) => return view.eval(format!("alert({})", serde_json::to_string(&error).unwrap_or_default()).as_str()),
+            Ok(statuses) => statuses
+        };
+
+        // A file exported before findings were removed is refused as a whole rather than partly imported
+        let unknown: Vec<String> = statuses.iter()
+            .filter(|(id, _)| !state.findings.contains_key(id))
+            .map(|(id, _)| id.to_string())
+            .collect();
+
+        if !unknown.is_empty() {
+            let text = format!("No statuses were imported, as the workbook has no findings with the ids {}", unknown.join(", "));
+            return view.eval(format!("alert({})", serde_json::to_string(&text).unwrap_or_default()).as_str());
+        }
+
+        let mut updated = 0;
+
+        for (id, status) in statuses {
+            if state.findings[&id].status != status {
+                set_finding_status(view, state, id, status.key())?;
+                updated += 1;
+            }
+        }
+
+        view.eval(format!("alert('Updated the status of {} findings')", updated).as_str())?;
+    }
+
+    Ok(())
+}
+
+fn select_export_template<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::open_file_dialog("Select an export template", "*.hbs", None) {
+        // Validate the template against the current workbook before accepting it
+        if let Err(error) = std::fs::read_to_string(&path).map_err(|error| error.to_string()).and_then(|source| template::render(source.as_str(), &template::ReportContext::new(state))) {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+
+        state.export_template = Some(path);
+        update_report_details_in_web_view(view, state)?;
+    }
+
+    Ok(())
+}
+
+fn reset_export_template<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    state.export_template = None;
+    update_report_details_in_web_view(view, state)
+}
+
+fn save_default_template() -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::save_file_dialog("Save the default export template", "default.md.hbs") {
+        if let Err(error) = std::fs::write(path, template::DEFAULT_TEMPLATE) {
+            return Err(web_view::Error::Custom(Box::new(error)))
+        }
+    }
+
+    Ok(())
+}
+
+fn set_report_field<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, field: &str, value: &str) -> web_view::WVResult {
+    match field {
+        "title" => state.title = value.to_string(),
+        "start_time" => state.start_time = value.to_string(),
+        "delivery_time" => state.delivery_time = value.to_string(),
+        "repository" => state.repository = value.to_string(),
+        "commit_hashes" => {
+            state.commit_hashes = value.split(',')
+                .map(|hash| hash.trim().to_string())
+                .filter(|hash| !hash.is_empty())
+                .collect();
+        }
+        "overview" => state.overview = value.to_string(),
+        _ => return Err(web_view::Error::Custom(Box::new(format!("Invalid report field: {}", field))))
+    }
+
+    update_report_details_in_web_view(view, state)?;
+    update_repository_in_web_view(view, state)
+}
+
+fn select_repository<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    if let Some(path) = tinyfiledialogs::select_folder_dialog("Select the audited git checkout", "") {
+        let path = match git::toplevel(path.as_str()) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(path) => path
+        };
+
+        if let Some(url) = git::remote_url(path.as_str()) {
+            state.repository = url;
+        }
+
+        state.checkouts.set(state.repository.as_str(), path.as_str());
+        state.repository_path = Some(path);
+
+        update_report_details_in_web_view(view, state)?;
+        update_repository_in_web_view(view, state)?;
+
+        match state.checkouts.save() {
+            Err(error) => {
+                let text = format!("Failed to remember the checkout for this repository: {}", error);
+                view.eval(format!("alert({})", serde_json::to_string(&text).unwrap_or_default()).as_str())?;
+            }
+
+            Ok(()) => {
+                state.checkouts_error = None;
+                set_message(view, "checkouts_message", "")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn set_audited_commit<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, hash: &str, audited: bool) -> web_view::WVResult {
+    if audited {
+        // Replace the placeholder hashes the first time a real commit is picked
+        state.commit_hashes.retain(|existing| git::is_commit_hash(existing));
+
+        if !state.commit_hashes.iter().any(|existing| existing == hash) {
+            state.commit_hashes.push(hash.to_string());
+        }
+    } else {
+        state.commit_hashes.retain(|existing| existing != hash);
+    }
+
+    update_report_details_in_web_view(view, state)?;
+    update_repository_in_web_view(view, state)
+}
+
+fn remap_locations<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, commit: &str) -> web_view::WVResult {
+    let path = match state.repository_path {
+        None => return set_message(view, "remap_message", "Select a local checkout before remapping locations"),
+        Some(ref path) => path.clone()
+    };
+
+    let default_commit = match state.commit_hashes.iter().find(|hash| git::is_commit_hash(hash)) {
+        None => return set_message(view, "remap_message", "Pick the initially audited commit before remapping locations"),
+        Some(hash) => hash.clone()
+    };
+
+    let to = match git::resolve_commit(path.as_str(), commit.trim()) {
+        Err(error) => return set_message(view, "remap_message", format!("No commit named '{}' was found in the checkout: {}", commit.trim(), error).as_str()),
+        Ok(to) => to
+    };
+
+    set_message(view, "remap_message", "")?;
+
+    let mut ids: Vec<usize> = state.findings.keys().copied().collect();
+    ids.sort();
+
+    let mut rows = vec![];
+    let mut failed = 0;
+
+    for id in ids {
+        let finding = &state.findings[&id];
+
+        let locations = if finding.locations.is_empty() {
+            Location::parse_all(finding.location.as_str())
+        } else {
+            finding.locations.clone()
+        };
+
+        if locations.is_empty() {
+            continue;
+        }
+
+        let mut remapped = vec![];
+
+        for location in &locations {
+            match remap::remap_location(path.as_str(), location, default_commit.as_str(), to.as_str()) {
+                Ok(new_location) => {
+                    rows.push((id, location.to_string(), new_location.to_string(), new_location.confidence));
+                    remapped.push(new_location);
+                }
+
+                // Locations which cannot be found in the checkout, such as ones without a full path, are kept as they are
+                Err(_) => {
+                    rows.push((id, location.to_string(), location.to_string(), None));
+                    remapped.push(location.clone());
+                    failed += 1;
+                }
+            }
+        }
+
+        set_finding_location(view, state, id, tool::format_locations(&remapped).as_str())?;
+
+        let finding = state.findings.get_mut(&id).unwrap();
+        finding.locations = remapped;
+
+        let mut location_label = HtmlElement::get(format!("finding{}_location_label", id).as_str());
+        location_label.set_inner_html(location_label_text(finding));
+        location_label.build(view)?;
+    }
+
+    let mut remap_table = HtmlElement::new("table", "remap_table");
+    remap_table.set_attribute("style", "margin: 0.5rem; width: 100%");
+
+    let remap_header_row = remap_table.insert_row(0, "remap_header_row");
+
+    for (index, heading) in ["ID", "Before", "After", "Confidence"].iter().enumerate() {
+        let remap_header_cell = remap_header_row.insert_cell(index, "remap_header_cell");
+        remap_header_cell.set_attribute("style", "font-weight: bold");
+        remap_header_cell.set_field("textContent", heading);
+    }
+
+    let display_ids = template::display_ids(state);
+
+    for (index, (id, before, after, confidence)) in rows.iter().enumerate() {
+        let remap_row = remap_table.insert_row(index + 1, "remap_row");
+
+        if confidence.map_or(true, |confidence| confidence != report::Confidence::High) {
+            remap_row.set_attribute("style", "color: tomato");
+        }
+
+        let confidence = confidence.map(|confidence| confidence.name()).unwrap_or("Not found in checkout");
+
+        for (cell_index, text) in [template::finding_label(&display_ids, *id).as_str(), before.as_str(), after.as_str(), confidence].iter().enumerate() {
+            let remap_cell = remap_row.insert_cell(cell_index, "remap_cell");
+            remap_cell.set_field("textContent", text);
+        }
+    }
+
+    let mut remap_results = HtmlElement::get("remap_results");
+    remap_results.set_inner_html("");
+    remap_results.append_child(remap_table);
+    remap_results.build(view)?;
+
+    let flagged = rows.iter().filter(|row| row.3.map_or(false, |confidence| confidence != report::Confidence::High)).count();
+
+    view.eval(format!("alert('Remapped {} locations onto {}: {} flagged for review, {} not found in the checkout')", rows.len() - failed, &to[..10], flagged, failed).as_str())
+}
+
+fn set_fix_commit<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, fix_commit: &str) -> web_view::WVResult {
+    let fix_commit = fix_commit.trim();
+
+    if fix_commit.is_empty() {
+        state.fix_commit = None;
+    } else {
+        let path = match state.repository_path {
+            None => return set_message(view, "reaudit_message", "Select a local checkout before entering the fix commit"),
+            Some(ref path) => path.clone()
+        };
+
+        // The resolved hash is what git is given later, and what verdicts record
+        state.fix_commit = match git::resolve_commit(path.as_str(), fix_commit) {
+            Err(error) => return set_message(view, "reaudit_message", format!("No commit named '{}' was found in the checkout: {}", fix_commit, error).as_str()),
+            Ok(hash) => Some(hash)
+        };
+    }
+
+    state.reaudit_changes.clear();
+    set_message(view, "reaudit_message", "")?;
+
+    update_reaudit_in_web_view(view, state)
+}
+
+fn run_reaudit<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    let path = match state.repository_path {
+        None => return set_message(view, "reaudit_message", "Select a local checkout before running a re-audit"),
+        Some(ref path) => path.clone()
+    };
+
+    let initial_commit = match state.commit_hashes.iter().find(|hash| git::is_commit_hash(hash)) {
+        None => return set_message(view, "reaudit_message", "Pick the initially audited commit before running a re-audit"),
+        Some(hash) => hash.clone()
+    };
+
+    let fix_commit = match state.fix_commit {
+        None => return set_message(view, "reaudit_message", "Enter the fix commit before running a re-audit"),
+        Some(ref fix_commit) => fix_commit.clone()
+    };
+
+    let mut findings: Vec<Finding> = state.findings.values().filter(|finding| !finding.draft).cloned().collect();
+    findings.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
+
+    state.reaudit_changes = match reaudit::compare(path.as_str(), initial_commit.as_str(), fix_commit.as_str(), &findings) {
+        Err(error) => return set_message(view, "reaudit_message", error.trim()),
+        Ok(changes) => changes
+    };
+
+    set_message(view, "reaudit_message", "")?;
+    update_reaudit_in_web_view(view, state)
+}
+
+fn set_reaudit_verdict<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, verdict: &str) -> web_view::WVResult {
+    let verdict = match Verdict::from_name(verdict) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("Invalid verdict: {}", verdict)))),
+        Some(verdict) => verdict
+    };
+
+    let fix_commit = match state.fix_commit {
+        None => return set_message(view, "reaudit_message", "Enter the fix commit before recording a verdict"),
+        Some(ref fix_commit) => fix_commit.clone()
+    };
+
+    // The finding may have been removed by a collaborator since the comparison was run
+    let finding = match state.findings.get_mut(&id) {
+        None => return set_message(view, "reaudit_message", format!("Finding {} no longer exists", id).as_str()),
+        Some(finding) => finding
+    };
+
+    let reaudit = Reaudit { verdict, fix_commit };
+    let alleviation = reaudit::record_verdict(finding.alleviation.as_str(), &reaudit);
+    let previous_status = finding.status;
+    finding.reaudit = Some(reaudit);
+
+    // Resolved findings are closed, while a finding found unresolved is reopened if it had been closed
+    let status = match verdict {
+        Verdict::Resolved => Status::Resolved,
+        Verdict::Unresolved if previous_status == Status::Resolved => Status::Pending,
+        Verdict::Unresolved => previous_status
+    };
+
+    set_finding_alleviation(view, state, id, alleviation.as_str())?;
+    set_finding_status(view, state, id, status.key())?;
+    update_reaudit_in_web_view(view, state)
+}
+
+fn set_finding_order<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, order: &str) -> web_view::WVResult {
+    match FindingOrder::from_name(order) {
+        Some(order) => {
+            state.finding_order = order;
+
+            let mut finding_order_select = HtmlElement::get("finding_order_select");
+            finding_order_select.set_value(order.name());
+            finding_order_select.build(view)
+        }
+
+        None => Err(web_view::Error::Custom(Box::new(format!("Invalid finding order: {}", order))))
+    }
+}
+
+fn set_finding_tags<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, tags: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.tags = report::parse_tags(tags);
+
+        let mut finding_tags = HtmlElement::get(format!("finding{}_tags", id).as_str());
+        finding_tags.set_value(entry.tags.join(", ").as_str());
+        finding_tags.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_field<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, name: &str, value: &str) -> web_view::WVResult {
+    let (index, field) = match state.custom_fields.iter().enumerate().find(|(_, field)| field.name == name) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("No custom field named '{}' was found!", name)))),
+        Some(field) => field
+    };
+
+    let entry = match state.findings.get_mut(&id) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id)))),
+        Some(entry) => entry
+    };
+
+    let value = value.trim();
+
+    if let Err(error) = field.validate(value) {
+        // Put back the previous value, since the input still shows the rejected one
+        let mut finding_field = HtmlElement::get(format!("finding{}_field{}", id, index).as_str());
+        finding_field.set_value(entry.custom_fields.get(name).map(String::as_str).unwrap_or(""));
+        finding_field.build(view)?;
+
+        return view.eval(format!("alert({})", serde_json::to_string(&error).unwrap_or_default()).as_str());
+    }
+
+    if value.is_empty() {
+        entry.custom_fields.remove(name);
+    } else {
+        entry.custom_fields.insert(name.to_string(), value.to_string());
+    }
+
+    let mut finding_field = HtmlElement::get(format!("finding{}_field{}", id, index).as_str());
+    finding_field.set_value(value);
+    finding_field.build(view)
+}
+
+fn create_custom_field<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, name: &str, kind: &str, options: &str) -> web_view::WVResult {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return set_message(view, "custom_field_message", "A custom field needs a name");
+    }
+
+    let kind = match FieldKind::from_name(kind) {
+        None => return set_message(view, "custom_field_message", format!("Unknown custom field kind: '{}'", kind).as_str()),
+        Some(kind) => kind
+    };
+
+    if state.custom_fields.iter().any(|field| field.name.eq_ignore_ascii_case(name)) {
+        return set_message(view, "custom_field_message", format!("A custom field named '{}' already exists", name).as_str());
+    }
+
+    let options = if kind == FieldKind::Enum { report::parse_tags(options) } else { vec![] };
+
+    if kind == FieldKind::Enum && options.is_empty() {
+        return set_message(view, "custom_field_message", "An enum field needs a comma separated list of options");
+    }
+
+    set_message(view, "custom_field_message", "")?;
+
+    state.custom_fields.push(CustomField {
+        name: name.to_string(),
+        kind,
+        options,
+        exported: false
+    });
+
+    update_custom_fields_in_web_view(view, state)
+}
+
+fn remove_custom_field<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, index: usize) -> web_view::WVResult {
+    if index >= state.custom_fields.len() {
+        return Err(web_view::Error::Custom(Box::new(format!("No custom field {} was found!", index))));
+    }
+
+    let field = state.custom_fields.remove(index);
+
+    for finding in state.findings.values_mut() {
+        finding.custom_fields.remove(&field.name);
+    }
+
+    update_custom_fields_in_web_view(view, state)
+}
+
+fn set_custom_field_exported(state: &mut StateData, index: usize, exported: bool) -> web_view::WVResult {
+    match state.custom_fields.get_mut(index) {
+        None => Err(web_view::Error::Custom(Box::new(format!("No custom field {} was found!", index)))),
+
+        Some(field) => {
+            field.exported = exported;
+            Ok(())
+        }
+    }
+}
+
+fn set_finding_filter<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, query: &str) -> web_view::WVResult {
+    // An invalid query keeps the previous filter, so the findings shown do not change while it is fixed
+    match filter::Filter::parse(query) {
+        Err(error) => set_message(view, "finding_filter_message", error.as_str()),
+
+        Ok(finding_filter) => {
+            state.finding_filter = finding_filter;
+            set_message(view, "finding_filter_message", "")?;
+            update_finding_filter_in_web_view(view, state)
+        }
+    }
+}
+
+fn set_lint_rule<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, rule: &str, level: &str) -> web_view::WVResult {
+    match (lint::Rule::from_name(rule), lint::Level::from_name(level)) {
+        (None, _) => set_message(view, "lint_message", format!("Unknown lint rule: '{}'", rule).as_str()),
+        (_, None) => set_message(view, "lint_message", format!("Unknown lint level: '{}'", level).as_str()),
+
+        (Some(rule), Some(level)) => {
+            state.lint_rules.set_level(rule, level);
+            set_message(view, "lint_message", "")?;
+
+            let mut select = HtmlElement::get(format!("lint_rule_{}_select", rule.key()).as_str());
+            select.set_value(level.key());
+            select.build(view)
+        }
+    }
+}
+
+fn run_linter<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    update_lint_problems_in_web_view(view, state, &lint::lint(state))
+}
+
+fn show_command_palette<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    let mut entries: Vec<command::PaletteEntry> = command::registry().into_iter()
+        .filter(|command| command.name != "paste_finding" || state.copied_finding.is_some())
+        .map(|command| command::PaletteEntry {
+            title: command.title.clone(),
+            shortcut: state.shortcuts.get(command.name.as_str()),
+            command: Some(command),
+            finding_id: None
+        })
+        .collect();
+
+    let display_ids = template::display_ids(state);
+
+    // Drafts, which have no display id, are listed after the exported findings
+    let mut findings: Vec<&Finding> = state.findings.values().collect();
+    findings.sort_by(|lhs, rhs| lhs.draft.cmp(&rhs.draft).then_with(|| state.finding_order.compare(lhs, rhs)));
+
+    entries.extend(findings.iter().map(|finding| command::PaletteEntry {
+        title: format!("Go to {}: {}", template::finding_label(&display_ids, finding.id), finding.title),
+        shortcut: String::new(),
+        command: None,
+        finding_id: Some(finding.id)
+    }));
+
+    match serde_json::to_string(&entries) {
+        Err(error) => Err(web_view::Error::Custom(Box::new(error))),
+        Ok(json) => view.eval(format!("showCommandPalette({})", json).as_str())
+    }
+}
+
+fn set_shortcut<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, name: &str, shortcut: &str) -> web_view::WVResult {
+    if command::find(name).is_none() {
+        return set_message(view, "shortcuts_message", format!("No command named '{}' can have a shortcut", name).as_str());
+    }
+
+    if let Err(error) = state.shortcuts.set(name, shortcut) {
+        // Show the shortcut the command still has
+        update_shortcuts_in_web_view(view, state)?;
+        return set_message(view, format!("shortcut_{}_message", name).as_str(), error.as_str());
+    }
+
+    update_shortcuts_in_web_view(view, state)?;
+
+    match state.shortcuts.save() {
+        Err(error) => set_message(view, "shortcuts_message", format!("Failed to save the keyboard shortcuts: {}", error).as_str()),
+
+        Ok(()) => {
+            state.shortcuts_error = None;
+            set_message(view, "shortcuts_message", "")
+        }
+    }
+}
+
+fn find_in_workbook<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, regex: bool, case_sensitive: bool, pattern: &str, replacement: &str) -> web_view::WVResult {
+    state.find_replace = replace::FindReplace {
+        pattern: pattern.to_string(),
+        replacement: replacement.to_string(),
+        regex,
+        case_sensitive
+    };
+
+    update_find_results_in_web_view(view, state)
+}
+
+/// Replaces the matches in every field found, or only in the field at an index of the results.
+fn replace_matches<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, index: Option<usize>) -> web_view::WVResult {
+    // An invalid pattern is shown in place of the results, like when it was searched for
+    let results = match state.find_replace.search(state) {
+        Err(_) => return update_find_results_in_web_view(view, state),
+        Ok(results) => results
+    };
+
+    let commands: Vec<String> = results.iter()
+        .enumerate()
+        .filter(|(result_index, _)| index.map_or(true, |index| index == *result_index))
+        .map(|(_, result)| result.field.command(result.replaced().as_str()))
+        .collect();
+
+    // Replacements are sent like edits by hand, so they update the page and are shared with a collaboration session
+    for command in commands {
+        dispatch_command(view, state, command.as_str())?;
+    }
+
+    update_find_results_in_web_view(view, state)
+}
+
+fn export_pdf<'a>(view: &mut web_view::WebView<'a, ()>) -> web_view::WVResult {
+    view.eval("alert('PDF exporting is not currently supported')")
+}
+
+fn create_checklist_entry<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    let id = state.checklist.len();
+    state.checklist.push((false, String::new()));
+    add_checklist_entry_to_web_view(view, id, (false, ""))
+}
+
+fn remove_checklist_entry<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
+    if id < state.checklist.len() {
+        let _ = state.checklist.remove(id);
+
+        let mut entry_table = HtmlElement::get(format!("checklist{}_table", id).as_str());
+        entry_table.remove();
+        entry_table.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No checklist entry for id {} was found!", id))))
+    }
+}
+
+fn clear_checklist_entries<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    let mut entries = vec![];
+
+    for entry in &state.checklist {
+        entries.push(entry.clone());
+    }
+
+    // Remove entries from the end, since removing an entry shifts the ids of the entries after it
+    for (id, _) in entries.iter().enumerate().rev() {
+        let _ = remove_checklist_entry(view, state, id);
+    }
+
+    Ok(())
+}
+
+fn set_checklist_entry_checked<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, checked: bool) -> web_view::WVResult {
+    if id < state.checklist.len() {
+        state.checklist[id].0 = checked;
+
+        let mut entry_check_input = HtmlElement::get(format!("checklist{}_check_input", id).as_str());
+        entry_check_input.set_checked(checked);
+        entry_check_input.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No checklist entry for id {} was found!", id))))
+    }
+}
+
+fn set_checklist_entry_text<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, text: &str) -> web_view::WVResult {
+    if id < state.checklist.len() {
+        state.checklist[id].1 = text.to_string();
+
+        let mut entry_text_input = HtmlElement::get(format!("checklist{}_text_input", id).as_str());
+        entry_text_input.set_value(text);
+        entry_text_input.build(view)
+    
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No checklist entry for id {} was found!", id))))
+    }
+}
+
+fn create_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    state.current_finding_id += 1;
+
+    let mut finding = Finding::new(state.current_finding_id);
+    finding.title = format!("Finding{}", state.current_finding_id);
+
+    // Attempt to add the new finding to the state data findings map
+    assert!(state.findings.insert(finding.id, finding.clone()).is_none());
+
+    // Attempt to add the new finding to the web view
+    add_finding_to_web_view(view, &finding, &state.severity_matrix, &state.custom_fields)
+}
+
+fn copy_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
+    if let Some(finding) = state.findings.get(&id) {
+        // Set the copied finding in the state data
+        state.copied_finding = Some(finding.clone());
+
+        // Attempt to enable the paste button in the web view
+        let mut paste_button = html::HtmlElement::get("paste_button");
+        paste_button.set_disabled(false);
+        paste_button.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn paste_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    // The shortcut and the command palette can paste before any finding was copied
+    let mut finding = match state.copied_finding.clone() {
+        None => return set_message(view, "paste_message", "Copy a finding before pasting it"),
+        Some(finding) => finding
+    };
+
+    set_message(view, "paste_message", "")?;
+    state.current_finding_id += 1;
+    finding.id = state.current_finding_id;
+    add_finding_to_web_view(view, &finding, &state.severity_matrix, &state.custom_fields)?;
+    assert!(state.findings.insert(finding.id, finding).is_none());
+    Ok(())
+}
+
+fn accept_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
+    if let Some(finding) = state.findings.get_mut(&id) {
+        finding.draft = false;
+
+        for element_id in &["draft_label", "accept_button", "suppress_button", "merge_button", "link_draft"] {
+            let mut element = HtmlElement::get(format!("finding{}_{}", id, element_id).as_str());
+            element.remove();
+            element.build(view)?;
+        }
+
+        Ok(())
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+/// Adds a suppression for each tool result fingerprinted on a draft finding.
+fn add_suppressions(state: &mut StateData, finding: &Finding, kind: SuppressionKind) {
+    let file = finding.locations.first().map(|location| location.file.clone()).unwrap_or_default();
+
+    for fingerprint in &finding.fingerprints {
+        state.baseline.retain(|suppression| &suppression.fingerprint != fingerprint);
+        state.baseline.push(Suppression {
+            fingerprint: fingerprint.clone(),
+            tool: finding.tool.clone().unwrap_or_default(),
+            rule: finding.rule.clone().unwrap_or_default(),
+            file: file.clone(),
+            kind
+        });
+    }
+}
+
+fn suppress_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
+    match state.findings.get(&id).cloned() {
+        Some(finding) if finding.draft => {
+            add_suppressions(state, &finding, SuppressionKind::FalsePositive);
+            remove_finding(view, state, id)?;
+            update_baseline_in_web_view(view, state)
+        }
+
+        Some(_) => Err(web_view::Error::Custom(Box::new(format!("Finding {} is not a draft finding!", id)))),
+        None => Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn merge_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, target: &str) -> web_view::WVResult {
+    let target_id = match target.trim().parse::<usize>() {
+        Ok(target_id) if target_id != id && state.findings.contains_key(&target_id) => target_id,
+
+        // The target is typed by the user, so a mistake leaves the draft as it is
+        _ => {
+            let text = format!("No finding with id '{}' was found to merge into.", target.trim());
+            return view.eval(format!("alert({})", serde_json::to_string(&text).unwrap_or_default()).as_str());
+        }
+    };
+
+    match state.findings.get(&id).cloned() {
+        Some(finding) if finding.draft => {
+            add_suppressions(state, &finding, SuppressionKind::Merged { finding_id: target_id });
+
+            let target = state.findings.get_mut(&target_id).unwrap();
+
+            for fingerprint in finding.fingerprints.iter() {
+                if !target.fingerprints.contains(fingerprint) {
+                    target.fingerprints.push(fingerprint.clone());
+                }
+            }
+
+            remove_finding(view, state, id)?;
+            update_baseline_in_web_view(view, state)
+        }
+
+        Some(_) => Err(web_view::Error::Custom(Box::new(format!("Finding {} is not a draft finding!", id)))),
+        None => Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn remove_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
+    if state.findings.remove(&id).is_some() {
+        let _ = state.findings.remove(&id);
+        state.finding_previews.retain(|(finding_id, _), _| *finding_id != id);
+
+        let mut finding = HtmlElement::get(format!("finding{}", id).as_str());
+        finding.remove();
+        finding.build(view)?;
+
+        let mut link = HtmlElement::get(format!("finding{}_link_p", id).as_str());
+        link.remove();
+        link.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn clear_findings<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
+    let mut ids = vec![];
+
+    for id in state.findings.keys() {
+        ids.push(*id);
+    }
+
+    for id in ids {
+        remove_finding(view, state, id)?;
+    }
+
+    state.current_finding_id = 0;
+
+    Ok(())
+}
+
+fn set_finding_title<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, title: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.title = title.to_string();
+
+        let mut finding_link = HtmlElement::get(format!("finding{}_link", id).as_str());
+        finding_link.set_inner_html(title);
+        finding_link.build(view)?;
+
+        let mut finding_title_id = HtmlElement::get(format!("finding{}_title_id", id).as_str());
+        finding_title_id.set_inner_html(format!("{}: ", id).as_str());
+        finding_title_id.build(view)?;
+
+        let mut finding_title = HtmlElement::get(format!("finding{}_title", id).as_str());
+        finding_title.set_value(title);
+        finding_title.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_type<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, class: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.class = class.to_string();
+
+        let mut finding_type = HtmlElement::get(format!("finding{}_type", id).as_str());
+        finding_type.set_value(class);
+        finding_type.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_severity<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, severity: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.set_severity(Severity::from_name(severity), &state.severity_matrix);
+
+        update_finding_severity_in_web_view(view, entry, &state.severity_matrix)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_likelihood<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, likelihood: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.likelihood = Likelihood::from_name(likelihood);
+        entry.update_severity(&state.severity_matrix);
+
+        let mut option = HtmlElement::get(format!("finding{}_likelihood_{}_option", id, likelihood).as_str());
+        option.set_selected(true);
+        option.build(view)?;
+
+        update_finding_severity_in_web_view(view, entry, &state.severity_matrix)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_impact<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, impact: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.impact = Impact::from_name(impact);
+        entry.update_severity(&state.severity_matrix);
+
+        let mut option = HtmlElement::get(format!("finding{}_impact_{}_option", id, impact).as_str());
+        option.set_selected(true);
+        option.build(view)?;
+
+        update_finding_severity_in_web_view(view, entry, &state.severity_matrix)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_justification<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, justification: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.justification = justification.to_string();
+
+        let mut finding_justification = HtmlElement::get(format!("finding{}_justification", id).as_str());
+        finding_justification.set_inner_html(justification);
+        finding_justification.set_value(justification);
+        finding_justification.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_status<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, status: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.status = match Status::from_name(status) {
+            None => return Err(web_view::Error::Custom(Box::new(format!("Invalid status: {}", status)))),
+            Some(status) => status
+        };
+
+        let mut option = HtmlElement::get(format!("finding{}_status_{}_option", id, entry.status.key()).as_str());
+        option.set_selected(true);
+        option.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_location<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, location: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.location = location.to_string();
+
+        // Edited locations refer to the same commit as before, and have been checked by hand
+        let commit = entry.locations.first().and_then(|location| location.commit.clone());
+        entry.locations = Location::parse_all(location);
+
+        for location in entry.locations.iter_mut() {
+            location.commit = commit.clone();
+        }
+
+        let mut finding_location = HtmlElement::get(format!("finding{}_location", id).as_str());
+        finding_location.set_value(location);
+        finding_location.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_description<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, description: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.description = description.to_string();
+
+        let mut finding_description = HtmlElement::get(format!("finding{}_description", id).as_str());
+        finding_description.set_inner_html(description);
+        finding_description.set_value(description);
+        finding_description.build(view)?;
+
+        // A shown preview follows the text, which is also set by collaborators and find and replace
+        if let Some(text) = state.finding_previews.get_mut(&(id, "description".to_string())) {
+            *text = description.to_string();
+        }
+
+        Ok(())
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_finding_recommendation<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, recommendation: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.recommendation = recommendation.to_string();
+
+        let mut finding_recommendation = HtmlElement::get(format!("finding{}_recommendation", id).as_str());
+        finding_recommendation.set_inner_html(recommendation);
+        finding_recommendation.set_value(recommendation);
+        finding_recommendation.build(view)?;
+
+        // A shown preview follows the text, which is also set by collaborators and find and replace
+        if let Some(text) = state.finding_previews.get_mut(&(id, "recommendation".to_string())) {
+            *text = recommendation.to_string();
+        }
+
+        Ok(())
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn add_attachments<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
+    let paths = match tinyfiledialogs::open_file_dialog_multi("Select files to attach", "", None) {
+        None => return Ok(()),
+        Some(paths) => paths
+    };
+
+    let finding = match state.findings.get_mut(&id) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id)))),
+        Some(finding) => finding
+    };
+
+    for path in paths {
+        let data = match std::fs::read(&path) {
+            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
+            Ok(data) => data
+        };
+
+        let file_name = std::path::Path::new(path.as_str()).file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "attachment".to_string());
+
+        let mut attachment = report::Attachment::new(file_name.as_str(), data);
+
+        // Names must be unique within the finding, since they are used to refer to attachments
+        let base_name = attachment.name.clone();
+        let mut count = 1;
+
+        while finding.attachments.iter().any(|existing| existing.name == attachment.name) {
+            count += 1;
+
+            attachment.name = match base_name.rfind('.') {
+                Some(index) if index > 0 => format!("{}-{}{}", &base_name[..index], count, &base_name[index..]),
+                _ => format!("{}-{}", base_name, count)
+            };
+        }
+
+        finding.attachments.push(attachment);
+    }
+
+    update_attachments_in_web_view(view, finding)
+}
+
+fn remove_attachment<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, index: usize) -> web_view::WVResult {
+    let finding = match state.findings.get_mut(&id) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id)))),
+        Some(finding) => finding
+    };
+
+    if index >= finding.attachments.len() {
+        return Err(web_view::Error::Custom(Box::new(format!("No attachment for index {} was found!", index))))
+    }
+
+    let _ = finding.attachments.remove(index);
+    update_attachments_in_web_view(view, finding)
+}
+
+fn preview_finding_field<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, name: &str, text: &str) -> web_view::WVResult {
+    if !PREVIEW_FIELDS.contains(&name) {
+        return Err(web_view::Error::Custom(Box::new(format!("No preview for the finding field '{}'", name))));
+    }
+
+    if let Some(entry) = state.findings.get(&id) {
+        update_finding_preview_in_web_view(view, &template::reference_targets(state), entry, name, text)?;
+
+        state.finding_previews.insert((id, name.to_string()), text.to_string());
+        Ok(())
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn hide_finding_preview(state: &mut StateData, id: usize, name: &str) {
+    state.finding_previews.remove(&(id, name.to_string()));
+}
+
+fn set_finding_alleviation<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, alleviation: &str) -> web_view::WVResult {
+    if let Some(entry) = state.findings.get_mut(&id) {
+        entry.alleviation = alleviation.to_string();
+
+        let mut finding_alleviation = HtmlElement::get(format!("finding{}_alleviation", id).as_str());
+        finding_alleviation.set_inner_html(alleviation);
+        finding_alleviation.set_value(alleviation);
+        finding_alleviation.build(view)
+    } else {
+        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    }
+}
+
+fn set_severity_matrix_cell<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, likelihood: &str, impact: &str, severity: &str) -> web_view::WVResult {
+    let likelihood = match Likelihood::from_name(likelihood) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("Invalid likelihood: {}", likelihood)))),
+        Some(likelihood) => likelihood
+    };
+
+    let impact = match Impact::from_name(impact) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("Invalid impact: {}", impact)))),
+        Some(impact) => impact
+    };
+
+    let severity = match Severity::from_name(severity) {
+        None => return Err(web_view::Error::Custom(Box::new(format!("Invalid severity: {}", severity)))),
+        Some(severity) => severity
+    };
+
+    state.severity_matrix.set(likelihood, impact, severity);
+
+    // Recompute the severity of every finding rated with the changed matrix
+    for finding in state.findings.values_mut() {
+        finding.update_severity(&state.severity_matrix);
+        update_finding_severity_in_web_view(view, finding, &state.severity_matrix)?;
+    }
+
+    Ok(())
+}
+
+fn severity_style(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::Critical) => "color: red",
+        Some(Severity::Major) => "color: orange",
+        Some(Severity::Minor) => "color: yellow",
+        Some(Severity::Informational) => "color: green",
+        None => "color: inherit",
+    }
+}
+
+fn update_attachments_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, finding: &Finding) -> web_view::WVResult {
+    let mut attachments_table = HtmlElement::new("table", "attachments_table");
+
+    for (index, attachment) in finding.attachments.iter().enumerate() {
+        let attachments_row = attachments_table.insert_row(index, "attachments_row");
+
+        let attachments_preview_cell = attachments_row.insert_cell(0, "attachments_preview_cell");
+
+        if attachment.is_image() {
+            let mut attachments_image = HtmlElement::new("img", "attachments_image");
+            attachments_image.set_attribute("src", attachment.data_uri().as_str());
+            attachments_image.set_attribute("style", "max-width: 8rem; max-height: 4rem");
+            attachments_preview_cell.append_child(attachments_image);
+        }
+
+        let attachments_name_cell = attachments_row.insert_cell(1, "attachments_name_cell");
+        attachments_name_cell.set_field("textContent", format!("{} ({}, {} KB)", attachment.name, attachment.media_type, (attachment.data.len() + 1023) / 1024).as_str());
+
+        let attachments_button_cell = attachments_row.insert_cell(2, "attachments_button_cell");
+
+        let mut attachments_reference_button = HtmlElement::new("button", "attachments_reference_button");
+        attachments_reference_button.set_attribute("onclick", format!("prompt('Refer to the attachment from markdown with:', {})", serde_json::to_string(&attachment.reference()).unwrap_or_default()).as_str());
+        attachments_reference_button.set_inner_html("Reference");
+        attachments_button_cell.append_child(attachments_reference_button);
+
+        let mut attachments_remove_button = HtmlElement::new("button", "attachments_remove_button");
+        attachments_remove_button.set_attribute("onclick", format!("external.invoke(\"remove_attachment {} {}\")", finding.id, index).as_str());
+        attachments_remove_button.set_inner_html("Remove");
+        attachments_button_cell.append_child(attachments_remove_button);
+    }
+
+    let mut attachments = HtmlElement::get(format!("finding{}_attachments", finding.id).as_str());
+    attachments.set_inner_html("");
+
+    if !finding.attachments.is_empty() {
+        attachments.append_child(attachments_table);
+    }
+
+    attachments.build(view)
+}
+
+/// The finding fields with a rendered markdown preview beside their text area.
+const PREVIEW_FIELDS: [&str; 2] = ["description", "recommendation"];
+
+fn update_finding_preview_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, targets: &HashMap<usize, reference::Target>, finding: &Finding, name: &str, text: &str) -> web_view::WVResult {
+    // The rendered page is passed as a JSON string since markdown is full of quotes and backslashes
+    view.eval(
+        format!(
+            "document.getElementById('finding{}_{}_preview').srcdoc = {};",
+            finding.id, name, serde_json::to_string(&export::preview(targets, finding, text)).unwrap_or_default()
+        ).as_str()
+    )
+}
+
+/// Renders the shown previews again from the text they last showed.
+fn update_finding_previews_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    if state.finding_previews.is_empty() {
+        return Ok(());
+    }
+
+    let targets = template::reference_targets(state);
+
+    for ((id, name), text) in state.finding_previews.iter() {
+        if let Some(finding) = state.findings.get(id) {
+            update_finding_preview_in_web_view(view, &targets, finding, name, text)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn update_finding_severity_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, finding: &Finding, matrix: &SeverityMatrix) -> web_view::WVResult {
+    let style = severity_style(finding.severity);
+
+    let mut finding_link = HtmlElement::get(format!("finding{}_link", finding.id).as_str());
+    finding_link.set_attribute("style", style);
+    finding_link.build(view)?;
+
+    for name in &["justification", "description", "recommendation", "alleviation", "attachments"] {
+        let mut header = HtmlElement::get(format!("finding{}_{}_header", finding.id, name).as_str());
+        header.set_attribute("style", style);
+        header.build(view)?;
+    }
+
+    let mut severity_label = HtmlElement::get(format!("finding{}_severity_label", finding.id).as_str());
+    severity_label.set_inner_html(severity_label_text(finding, matrix).as_str());
+    severity_label.build(view)?;
+
+    let option_name = match finding.severity {
+        Some(severity) if finding.severity_overridden => severity.name().to_lowercase(),
+        _ => "matrix".to_string()
+    };
+
+    let mut matrix_option = HtmlElement::get(format!("finding{}_severity_matrix_option", finding.id).as_str());
+    matrix_option.set_inner_html(matrix_option_text(finding, matrix));
+    matrix_option.build(view)?;
+
+    let mut option = HtmlElement::get(format!("finding{}_severity_{}_option", finding.id, option_name).as_str());
+    option.set_selected(true);
+    option.build(view)
+}
+
+fn location_label_text(finding: &Finding) -> &'static str {
+    if finding.locations.iter().any(Location::is_flagged) {
+        "Location (needs review)"
+    } else {
+        "Location"
+    }
+}
+
+fn severity_label_text(finding: &Finding, matrix: &SeverityMatrix) -> String {
+    match finding.matrix_severity(matrix) {
+        Some(matrix_severity) if finding.severity_overridden => format!("Severity (overridden from {})", matrix_severity.name()),
+        None if finding.severity_overridden => "Severity (overridden)".to_string(),
+        _ => "Severity".to_string()
+    }
+}
+
+fn matrix_option_text(finding: &Finding, matrix: &SeverityMatrix) -> &'static str {
+    let severity = if finding.severity_overridden {
+        finding.matrix_severity(matrix)
+    } else {
+        finding.severity
+    };
+
+    severity.map(|severity| severity.name()).unwrap_or("From Matrix")
+}
+
+fn add_severity_matrix_to_web_view<'a>(view: &mut web_view::WebView<'a, ()>, matrix: &SeverityMatrix) -> web_view::WVResult {
+    let mut matrix_table = HtmlElement::new("table", "matrix_table");
+    matrix_table.set_attribute("style", "margin: 0.5rem");
+
+    let matrix_header_row = matrix_table.insert_row(0, "matrix_header_row");
+
+    let matrix_corner_cell = matrix_header_row.insert_cell(0, "matrix_corner_cell");
+    matrix_corner_cell.set_inner_html("Likelihood / Impact");
+
+    for (impact_index, impact) in Impact::ALL.iter().enumerate() {
+        let matrix_impact_cell = matrix_header_row.insert_cell(impact_index + 1, "matrix_impact_cell");
+        matrix_impact_cell.set_attribute("style", "text-align: center");
+        matrix_impact_cell.set_inner_html(impact.name());
+    }
+
+    for (likelihood_index, likelihood) in Likelihood::ALL.iter().enumerate() {
+        let matrix_row = matrix_table.insert_row(likelihood_index + 1, "matrix_row");
+
+        let matrix_likelihood_cell = matrix_row.insert_cell(0, "matrix_likelihood_cell");
+        matrix_likelihood_cell.set_inner_html(likelihood.name());
+
+        for (impact_index, impact) in Impact::ALL.iter().enumerate() {
+            let matrix_cell = matrix_row.insert_cell(impact_index + 1, "matrix_cell");
+
+            let mut matrix_select = HtmlElement::new("select", "matrix_select");
+            matrix_select.set_attribute(
+                "onchange",
+                format!("external.invoke('set_severity_matrix_cell {} {} ' + this.value)", likelihood.name().to_lowercase(), impact.name().to_lowercase()).as_str()
+            );
+
+            for severity in &Severity::ALL {
+                let mut matrix_option = HtmlElement::new("option", "matrix_option");
+                matrix_option.set_attribute("value", severity.name().to_lowercase().as_str());
+                matrix_option.set_inner_html(severity.name());
+                matrix_option.set_selected(matrix.get(*likelihood, *impact) == *severity);
+                matrix_select.append_child(matrix_option);
+            }
+
+            matrix_cell.append_child(matrix_select);
+        }
+    }
+
+    let mut severity_matrix = HtmlElement::get("severity_matrix");
+    severity_matrix.set_inner_html("");
+    severity_matrix.append_child(matrix_table);
+    severity_matrix.build(view)
+}
+
+fn add_finding_to_web_view<'a>(view: &mut web_view::WebView<'a, ()>, finding: &Finding, matrix: &SeverityMatrix, fields: &[CustomField]) -> web_view::WVResult {
+    //
+    // Create a new finding table
+    //
+
+    let mut new_table = HtmlElement::new("table", "new_table");
+    new_table.set_attribute("id", format!("finding{}", finding.id).as_str());
+    new_table.set_attribute("style", "margin: 1rem");
+    new_table.set_attribute("onfocusin", format!("external.invoke(\"set_presence {}\")", finding.id).as_str());
+
+    let new_row = new_table.insert_row(0, "new_row");
+    let new_cell = new_row.insert_cell(0, "new_cell");
+
+    //
+    // Create the toolbar table for the new finding
+    //
+
+    let mut toolbar_table = HtmlElement::new("table", "toolbar_table");
+    toolbar_table.set_attribute("style", "table-layout: fixed; width: 100%");
+
+    let toolbar_row = toolbar_table.insert_row(0, "toolbar_row");
+    
+    let toolbar_cell = toolbar_row.insert_cell(0, "toolbar_cell");
+
+    // ---------------------------------------------------
+
+    let mut toolbar_close_button = HtmlElement::new("button", "toolbar_close_button");
+    toolbar_close_button.set_inner_html("X");
+    toolbar_close_button.set_field("style.backgroundColor", "tomato");
+    toolbar_close_button.set_attribute("onclick", format!("external.invoke(\"remove_finding {}\")", finding.id).as_str());
+    
+    toolbar_cell.append_child(toolbar_close_button);
+
+    // ---------------------------------------------------
+
+    let mut toolbar_copy_button = HtmlElement::new("button", "toolbar_copy_button");
+    toolbar_copy_button.set_attribute("onclick", format!("external.invoke(\"copy_finding {}\")", finding.id).as_str());
+    toolbar_copy_button.set_inner_html("Copy");
+
+    toolbar_cell.append_child(toolbar_copy_button);
+
+    // ---------------------------------------------------
+
+    let mut toolbar_reference_button = HtmlElement::new("button", "toolbar_reference_button");
+    toolbar_reference_button.set_attribute("onclick", format!("prompt('Refer to the finding from markdown with:', {})", serde_json::to_string(&format!("{}{}", reference::FINDING_SCHEME, finding.id)).unwrap_or_default()).as_str());
+    toolbar_reference_button.set_inner_html("Reference");
+
+    toolbar_cell.append_child(toolbar_reference_button);
+
+    // ---------------------------------------------------
+
+    if finding.draft {
+        let mut toolbar_accept_button = HtmlElement::new("button", "toolbar_accept_button");
+        toolbar_accept_button.set_attribute("id", format!("finding{}_accept_button", finding.id).as_str());
+        toolbar_accept_button.set_attribute("onclick", format!("external.invoke(\"accept_finding {}\")", finding.id).as_str());
+        toolbar_accept_button.set_inner_html("Accept");
+
+        toolbar_cell.append_child(toolbar_accept_button);
+
+        let mut toolbar_suppress_button = HtmlElement::new("button", "toolbar_suppress_button");
+        toolbar_suppress_button.set_attribute("id", format!("finding{}_suppress_button", finding.id).as_str());
+        toolbar_suppress_button.set_attribute("onclick", format!("external.invoke(\"suppress_finding {}\")", finding.id).as_str());
+        toolbar_suppress_button.set_inner_html("False Positive");
+
+        toolbar_cell.append_child(toolbar_suppress_button);
+
+        let mut toolbar_merge_button = HtmlElement::new("button", "toolbar_merge_button");
+        toolbar_merge_button.set_attribute("id", format!("finding{}_merge_button", finding.id).as_str());
+        toolbar_merge_button.set_attribute("onclick", format!("var target = prompt('Merge into finding id:'); if (target === null) return; if (/^[0-9]+$/.test(target.trim())) external.invoke('merge_finding {} ' + parseInt(target)); else alert('Enter the id of the finding to merge into.')", finding.id).as_str());
+        toolbar_merge_button.set_inner_html("Merge Into...");
+
+        toolbar_cell.append_child(toolbar_merge_button);
+
+        let mut toolbar_draft_label = HtmlElement::new("span", "toolbar_draft_label");
+        toolbar_draft_label.set_attribute("id", format!("finding{}_draft_label", finding.id).as_str());
+        toolbar_draft_label.set_attribute("style", "margin-left: 0.5rem; font-style: italic");
+        toolbar_draft_label.set_field(
+            "textContent",
+            format!(
+                "Draft generated by {} ({})",
+                finding.tool.as_deref().unwrap_or("an external tool"),
+                finding.rule.as_deref().unwrap_or("unknown rule")
+            ).as_str()
+        );
+
+        toolbar_cell.append_child(toolbar_draft_label);
+    }
+
+    let mut toolbar_presence_label = HtmlElement::new("span", "toolbar_presence_label");
+    toolbar_presence_label.set_attribute("id", format!("finding{}_presence_label", finding.id).as_str());
+    toolbar_presence_label.set_attribute("style", "margin-left: 0.5rem; color: darkorange");
+
+    toolbar_cell.append_child(toolbar_presence_label);
+
+    // ---------------------------------------------------
+
+    new_cell.append_child(toolbar_table);
+
+    //
+    // Create the title table for the new finding
+    //
+
+    let mut title_table = HtmlElement::new("table", "title_table");
+    title_table.set_attribute("style", "width: 100%");
+
+    let title_row = title_table.insert_row(0, "title_row");
+
+    // ---------------------------------------------------
+
+    let title_image_cell = title_row.insert_cell(0, "title_image_cell");
+    title_image_cell.set_attribute("style", "vertical-align: middle");
+    
+    let mut title_image = HtmlElement::new("img", "title_image");
+    title_image.set_attribute("src", "https://i.imgur.com/QwKomxS.png");
+    title_image.set_attribute("style", "width: 40px; height: auto");
+    
+    title_image_cell.append_child(title_image);
+
+    // ---------------------------------------------------
+
+    let title_id_cell = title_row.insert_cell(1, "title_id_cell");
+    title_id_cell.set_inner_html(format!("{}: ", finding.id).as_str());
+    title_id_cell.set_attribute("id", format!("finding{}_title_id", finding.id).as_str());
+    title_id_cell.set_attribute("style", "font-size: 1.5rem; padding-left: 0.25rem; padding-right: 0.25rem; text-align: center; vertical-align: middle");
+    
+    // ---------------------------------------------------
+
+    let title_text_cell = title_row.insert_cell(2, "title_text_cell");
+    title_text_cell.set_attribute("style", "width: 100%; vertical-align: middle");
+    
+    let mut title_text_input = HtmlElement::new("input", "title_text_input");
+    title_text_input.set_attribute("type", "text");
+    title_text_input.set_attribute("id", format!("finding{}_title", finding.id).as_str());
+    title_text_input.set_attribute("value", finding.title.as_str());
+    title_text_input.set_attribute("style", "font-size: 1.5rem");
+    title_text_input.set_attribute("onchange", format!("external.invoke('set_finding_{} {} ' + quote(this.value))", "title", finding.id).as_str());
+    
+    title_text_cell.append_child(title_text_input);
+    
+    // ---------------------------------------------------
+
+    new_cell.append_child(title_table);
+
+    //
+    // Create the header table for the new finding
+    //
+
+    let mut header_table = HtmlElement::new("table", "header_table");
+    header_table.set_attribute("style", "width: 100%");
+
+    // ---------------------------------------------------
+
+    let header_row1 = header_table.insert_row(0, "header_row1");
+
+    let mut create_header_cell = |index, name, text: &str| {
+        let header_cell = header_row1.insert_cell(index, format!("header_cell{}", index).as_str());
+        header_cell.set_attribute("id", format!("finding{}_{}_label", finding.id, name).as_str());
+        header_cell.set_attribute("style", "padding-top: 6.5px; padding-bottom: 6.5px");
+        header_cell.set_inner_html(text);
+    };
+
+    create_header_cell(0, "type", "Type");
+    create_header_cell(1, "likelihood", "Likelihood");
+    create_header_cell(2, "impact", "Impact");
+    create_header_cell(3, "severity", severity_label_text(finding, matrix).as_str());
+    create_header_cell(4, "location", location_label_text(finding));
+    create_header_cell(5, "status", "Status");
+
+    // ---------------------------------------------------
+
+    let header_row2 = header_table.insert_row(1, "header_row2");
+
+    let header_type_cell = header_row2.insert_cell(0, "header_type_cell");
+
+    let mut header_type_input = HtmlElement::new("input", "header_type_input");
+    header_type_input.set_attribute("type", "text");
+    header_type_input.set_attribute("value", finding.class.as_str());
+    header_type_input.set_attribute("id", format!("finding{}_type", finding.id).as_str());
+    header_type_input.set_attribute("onchange", format!("external.invoke('set_finding_{} {} ' + quote(this.value))", "type", finding.id).as_str());
+    
+    header_type_cell.append_child(header_type_input);
+
+    // ---------------------------------------------------
+
+    let mut create_rating_select = |index, name, selected: Option<&str>, options: &[&str]| {
+        let header_rating_cell = header_row2.insert_cell(index, format!("header_{}_cell", name).as_str());
+
+        let mut header_rating_select = HtmlElement::new("select", format!("header_{}_select", name).as_str());
+        header_rating_select.set_attribute("id", format!("finding{}_{}", finding.id, name).as_str());
+        header_rating_select.set_attribute("onchange", format!("external.invoke('set_finding_{} {} ' + quote(this.value))", name, finding.id).as_str());
+
+        let mut option = HtmlElement::new("option", format!("finding{}_{}_none_option", finding.id, name).as_str());
+        option.set_attribute("id", format!("finding{}_{}_none_option", finding.id, name).as_str());
+        option.set_attribute("value", "none");
+        option.set_inner_html("Select...");
+        header_rating_select.append_child(option);
+
+        for text in options {
+            let value = text.to_lowercase();
+
+            let mut option = HtmlElement::new("option", format!("finding{}_{}_{}_option", finding.id, name, value).as_str());
+            option.set_attribute("id", format!("finding{}_{}_{}_option", finding.id, name, value).as_str());
+            option.set_attribute("value", value.as_str());
+            option.set_inner_html(text);
+            option.set_selected(selected == Some(*text));
+            header_rating_select.append_child(option);
+        }
+
+        header_rating_cell.append_child(header_rating_select);
+    };
+
+    create_rating_select(1, "likelihood", finding.likelihood.map(|likelihood| likelihood.name()), &["Low", "Medium", "High"]);
+    create_rating_select(2, "impact", finding.impact.map(|impact| impact.name()), &["Low", "Medium", "High"]);
+
+    // ---------------------------------------------------
+
+    let header_severity_cell = header_row2.insert_cell(3, "header_severity_cell");
+
+    let mut header_severity_select = HtmlElement::new("select", "header_severity_select");
+    header_severity_select.set_attribute("id", format!("finding{}_severity", finding.id).as_str());
+    header_severity_select.set_attribute("onchange", format!("external.invoke('set_finding_{} {} ' + quote(this.value))", "severity", finding.id).as_str());
+
+    let mut create_severity_option = |name: &str, text| {
+        let mut option = HtmlElement::new("option", format!("finding{}_severity_{}_option", finding.id, name).as_str());
+        option.set_attribute("id", format!("finding{}_severity_{}_option", finding.id, name).as_str());
+        option.set_attribute("value", name);
+        option.set_inner_html(text);
+
+        match finding.severity {
+            Some(severity) if finding.severity_overridden => option.set_selected(severity.name().to_lowercase() == name),
+            _ => option.set_selected(name == "matrix")
+        }
+
+        header_severity_select.append_child(option);
+    };
+
+    create_severity_option("matrix", matrix_option_text(finding, matrix));
+
+    for severity in &Severity::ALL {
+        create_severity_option(severity.name().to_lowercase().as_str(), severity.name());
+    }
+
+    header_severity_cell.append_child(header_severity_select);
+    
+    // ---------------------------------------------------
+
+    let header_location_cell = header_row2.insert_cell(4, "header_location_cell");
+
+    let mut header_location_input = HtmlElement::new("input", "header_location_input");
+    header_location_input.set_attribute("type", "text");
+    header_location_input.set_attribute("value", finding.location.as_str());
+    header_location_input.set_attribute("id", format!("finding{}_location", finding.id).as_str());
+    header_location_input.set_attribute("onchange", format!("external.invoke('set_finding_{} {} ' + quote(this.value))", "location", finding.id).as_str());
+
+    header_location_cell.append_child(header_location_input);
+
+    // ---------------------------------------------------
+
+    let header_status_cell = header_row2.insert_cell(5, "header_status_cell");
+
+    let mut header_status_select = HtmlElement::new("select", "header_status_select");
+    header_status_select.set_attribute("id", format!("finding{}_status", finding.id).as_str());
+    header_status_select.set_attribute("onchange", format!("external.invoke('set_finding_{} {} ' + quote(this.value))", "status", finding.id).as_str());
+
+    for status in &Status::ALL {
+        let mut option = HtmlElement::new("option", "header_status_option");
+        option.set_attribute("id", format!("finding{}_status_{}_option", finding.id, status.key()).as_str());
+        option.set_attribute("value", status.key());
+        option.set_inner_html(status.name());
+        option.set_selected(finding.status == *status);
+        header_status_select.append_child(option);
+    }
+
+    header_status_cell.append_child(header_status_select);
+
+    //
+    // Done building the header table for the new finding
+    //
+
+    new_cell.append_child(header_table);
+
+    //
+    // Create the tags and custom fields table for the new finding
+    //
+
+    let mut fields_table = HtmlElement::new("table", "fields_table");
+    fields_table.set_attribute("id", format!("finding{}_fields", finding.id).as_str());
+    fields_table.set_attribute("style", "width: 100%");
+    build_finding_fields_table(&mut fields_table, finding, fields);
+
+    new_cell.append_child(fields_table);
+
+    //
+    // Create the text areas for the new finding
+    //
+
+    let severity_style = severity_style(finding.severity);
+
+    let mut create_text_area = |name, text, string| {
+        let mut header = HtmlElement::new("h4", format!("finding{}_{}_header", finding.id, name).as_str());
+        header.set_inner_html(text);
+        header.set_attribute("style", severity_style);
+        header.set_attribute("id", format!("finding{}_{}_header", finding.id, name).as_str());
+
+        let previewed = PREVIEW_FIELDS.contains(&name);
+
+        if previewed {
+            let mut preview_button = HtmlElement::new("button", format!("finding{}_{}_preview_button", finding.id, name).as_str());
+            preview_button.set_attribute("onclick", format!("togglePreview({}, '{}', this)", finding.id, name).as_str());
+            preview_button.set_inner_html("Preview");
+            header.append_child(preview_button);
+        }
+
+        new_cell.append_child(header);
+    
+        let mut textarea = HtmlElement::new("textarea", format!("finding{}_{}_textarea", finding.id, name).as_str());
+        textarea.set_inner_html(string);
+        textarea.set_attribute("rows", "4");
+        textarea.set_attribute("cols", "80");
+        textarea.set_attribute("maxlength", "9999");
+        textarea.set_field("style.resize", "vertical");
+        textarea.set_attribute("id", format!("finding{}_{}", finding.id, name).as_str());
+        textarea.set_attribute(
+            "onchange",
+            format!("external.invoke('set_finding_{} {} ' + quote(this.value))", name, finding.id).as_str()
+        );
+
+        if !previewed {
+            new_cell.append_child(textarea);
+            return;
+        }
+
+        textarea.set_attribute("oninput", format!("schedulePreview({}, '{}')", finding.id, name).as_str());
+
+        // The preview is shown beside the text area, splitting the width with it
+        let mut split_table = HtmlElement::new("table", format!("finding{}_{}_split_table", finding.id, name).as_str());
+        split_table.set_attribute("style", "width: 100%");
+
+        let split_row = split_table.insert_row(0, format!("finding{}_{}_split_row", finding.id, name).as_str());
+
+        let textarea_cell = split_row.insert_cell(0, format!("finding{}_{}_textarea_cell", finding.id, name).as_str());
+        textarea_cell.set_attribute("style", "border-bottom: none; padding: 0");
+        textarea_cell.append_child(textarea);
+
+        let preview_cell = split_row.insert_cell(1, format!("finding{}_{}_preview_cell", finding.id, name).as_str());
+        preview_cell.set_attribute("id", format!("finding{}_{}_preview_cell", finding.id, name).as_str());
+        preview_cell.set_attribute("style", "display: none; width: 50%; border-bottom: none; padding: 0");
+
+        // The markdown may contain raw HTML, so the preview is sandboxed to keep its scripts from running
+        let mut preview = HtmlElement::new("iframe", format!("finding{}_{}_preview", finding.id, name).as_str());
+        preview.set_attribute("id", format!("finding{}_{}_preview", finding.id, name).as_str());
+        preview.set_attribute("sandbox", "");
+        preview.set_attribute("style", "width: 100%; min-height: 12rem; border: 1px solid #555; background-color: #fff");
+        preview_cell.append_child(preview);
+
+        new_cell.append_child(split_table);
+    };
+
+    create_text_area("justification", "Severity Justification:", finding.justification.as_str());
+    create_text_area("description", "Description:", finding.description.as_str());
+    create_text_area("recommendation", "Recommendation:", finding.recommendation.as_str());
+    create_text_area("alleviation", "Alleviation:", finding.alleviation.as_str());
+
+    let mut attachments_header = HtmlElement::new("h4", "attachments_header");
+    attachments_header.set_attribute("id", format!("finding{}_attachments_header", finding.id).as_str());
+    attachments_header.set_attribute("style", severity_style);
+    attachments_header.set_inner_html("Attachments: ");
+
+    let mut attachments_add_button = HtmlElement::new("button", "attachments_add_button");
+    attachments_add_button.set_attribute("onclick", format!("external.invoke(\"add_attachments {}\")", finding.id).as_str());
+    attachments_add_button.set_inner_html("Attach Files...");
+
+    attachments_header.append_child(attachments_add_button);
+    new_cell.append_child(attachments_header);
+
+    let mut attachments_div = HtmlElement::new("div", "attachments_div");
+    attachments_div.set_attribute("id", format!("finding{}_attachments", finding.id).as_str());
+    new_cell.append_child(attachments_div);
+
+    new_cell.append_child(HtmlElement::new("p", "spacer"));
+
+    //
+    // Done building new finding table
+    //
+
+    let mut findings = HtmlElement::get("findings");
+    findings.append_child(new_table);
+
+    findings.build(view)?;
+
+    update_attachments_in_web_view(view, finding)?;
+
+    // Jump to the new finding
+    view.eval(format!("window.location = '#finding{}'", finding.id).as_str()).unwrap();
+
+    //
+    // Create the table of contents link
+    //
+
+    let mut toc_findings = HtmlElement::get("toc_findings");
+    
+    let mut p = HtmlElement::new("p", "p");
+    p.set_attribute("id", format!("finding{}_link_p", finding.id).as_str());
+
+    let mut link_id = HtmlElement::new("span", "link_id");
+    link_id.set_inner_html(format!("{}: ", finding.id).as_str());
+    p.append_child(link_id);
+
+    let mut link = HtmlElement::new("a", "link");
+    link.set_attribute("style", severity_style);
+    link.set_attribute("id", format!("finding{}_link", finding.id).as_str());
+    link.set_attribute("href", format!("#finding{}", finding.id).as_str());
+    link.set_inner_html(finding.title.as_str());
+    p.append_child(link);
+
+    if finding.draft {
+        let mut link_draft = HtmlElement::new("span", "link_draft");
+        link_draft.set_attribute("id", format!("finding{}_link_draft", finding.id).as_str());
+        link_draft.set_attribute("style", "font-style: italic");
+        link_draft.set_inner_html(" [Draft]");
+        p.append_child(link_draft);
+    }
 
-                    add_finding_to_web_view(view, &finding)?;
-                }
-            }
-        }
+    toc_findings.append_child(p);
+    toc_findings.build(view)
+}
+
+fn update_report_details_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let commit_hashes = state.commit_hashes.join(", ");
+
+    let fields = [
+        ("title", state.title.as_str()),
+        ("start_time", state.start_time.as_str()),
+        ("delivery_time", state.delivery_time.as_str()),
+        ("repository", state.repository.as_str()),
+        ("commit_hashes", commit_hashes.as_str()),
+        ("overview", state.overview.as_str())
+    ];
+
+    for (name, value) in &fields {
+        let mut input = HtmlElement::get(format!("report_{}_input", name).as_str());
+        input.set_value(value);
+        input.build(view)?;
     }
 
-    Ok(())
+    let workspace_template = state.workspace.as_ref()
+        .and_then(|workspace| workspace.settings.export_template.as_ref())
+        .map(|name| format!("Workspace template ({})", name));
+
+    let mut export_template = HtmlElement::get("export_template_text");
+    export_template.set_field("textContent", state.export_template.as_ref().or_else(|| workspace_template.as_ref()).map(String::as_str).unwrap_or("Default template"));
+    export_template.build(view)
 }
 
-fn export_markdown<'a>(state: &mut StateData) -> web_view::WVResult {
-    if let Some(path) = tinyfiledialogs::save_file_dialog("Select a Markdown file", "workbook.md") {
-        use std::{fs::File, io::Write};
+fn update_workspace_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let mut toc_workspace = HtmlElement::get("toc_workspace");
+    toc_workspace.set_inner_html("");
 
-        let mut file = match File::create(path) {
-            Err(error) => return Err(web_view::Error::Custom(Box::new(error))),
-            Ok(file) => file
-        };
+    let mut workspace_directory_text = HtmlElement::get("workspace_directory_text");
+    let mut workspace_auditors_input = HtmlElement::get("workspace_auditors_input");
+
+    let mut workspace_template_select = HtmlElement::get("workspace_template_select");
+    workspace_template_select.set_inner_html("");
 
-        let mut findings = vec![];
+    let workspace = match state.workspace {
+        None => {
+            workspace_directory_text.set_field("textContent", "No workspace open");
+            workspace_auditors_input.set_value("");
 
-        for finding in state.findings.values() {
-            findings.push(finding.clone());
+            toc_workspace.build(view)?;
+            workspace_directory_text.build(view)?;
+            workspace_auditors_input.build(view)?;
+            return workspace_template_select.build(view);
         }
 
-        findings.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
+        Some(ref workspace) => workspace
+    };
 
-        let mut md = String::new();
-        md.push_str("| ID | Title | Type | Severity |\n");
-        md.push_str("|-:|-|-|-|\n");
+    //
+    // Build the workbook list in the sidebar
+    //
 
-        for finding in &findings {
-            let severity = match finding.severity.unwrap() {
-                report::Severity::Critical => "Critical",
-                report::Severity::Major => "Major",
-                report::Severity::Minor => "Minor",
-                report::Severity::Informational => "Informational"
-            };
+    let mut workspace_heading = HtmlElement::new("h4", "workspace_heading");
+    workspace_heading.set_attribute("style", "text-align: center; width: 100%");
+    workspace_heading.set_inner_html("Workspace ");
 
-            md.push_str(
-                format!(
-                    "| <span class=\"{sev_low}\">[XXX-{id:02}](#XXX-{id:02})</span> | <span class=\"{sev_low}\">{title}</span> | <span class=\"{sev_low}\">{kind}</span> | <span class=\"{sev_low}\">{sev}</span> |\n",
-                    sev_low = severity.to_lowercase(), sev = severity, id = finding.id, title = finding.title, kind = finding.class
-                ).as_str()
-            );
-        }
+    let mut workspace_create_button = HtmlElement::new("button", "workspace_create_button");
+    workspace_create_button.set_attribute("onclick", "var name = prompt('Workbook name, such as client/module:'); if (name) external.invoke('create_workspace_workbook ' + quote(name))");
+    workspace_create_button.set_inner_html("+");
 
-        md.push_str("\n");
+    workspace_heading.append_child(workspace_create_button);
+    toc_workspace.append_child(workspace_heading);
 
-        for finding in &findings {
-            let severity = match finding.severity.unwrap() {
-                report::Severity::Critical => "Critical",
-                report::Severity::Major => "Major",
-                report::Severity::Minor => "Minor",
-                report::Severity::Informational => "Informational"
-            };
+    for (index, entry) in workspace.workbooks.iter().enumerate() {
+        let current = workspace.current.as_ref() == Some(&entry.path);
 
-            let severity_low = severity.to_lowercase();
-
-            md.push_str("\n");
-            md.push_str("<div style=\"page-break-after: always\"></div>\n");
-            md.push_str("\n");
-            md.push_str(format!("### ![](https://svgshare.com/i/QKR.svg)XXX-{:02}: {}\n", finding.id, finding.title).as_str());
-            md.push_str("\n");
-            md.push_str("| Type | Severity | Location |\n");
-            md.push_str("|-|-|-|\n");
-            md.push_str(format!("| {} | {} | <span class=\"{}\">{}</span> |\n", finding.class, severity, severity.to_lowercase(), finding.location).as_str());
-            md.push_str("\n");
-            md.push_str(format!("#### <span class=\"{}\">Description:</span>\n", severity_low).as_str());
-            md.push_str("\n");
-            md.push_str(format!("<div class=\"{}\">\n", severity_low).as_str());
-            md.push_str("\n");
-            md.push_str(format!("{}\n", finding.description.as_str()).as_str());
-            md.push_str("\n");
-            md.push_str("</div>\n");
-            md.push_str("\n");
-            md.push_str(format!("#### <span class=\"{}\">Recommendation:</span>\n", severity_low).as_str());
-            md.push_str("\n");
-            md.push_str(format!("<div class=\"{}\">\n", severity_low).as_str());
-            md.push_str("\n");
-            md.push_str(format!("{}\n", finding.recommendation.as_str()).as_str());
-            md.push_str("\n");
-            md.push_str("</div>\n");
-            md.push_str("\n");
-        }
-
-        if let Err(error) = file.write_all(md.as_bytes()) {
-            return Err(web_view::Error::Custom(Box::new(error)))
-        }
+        let mut workspace_entry = HtmlElement::new("div", "workspace_entry");
+        workspace_entry.set_attribute("onclick", format!("external.invoke(\"open_workspace_workbook {}\")", index).as_str());
+        workspace_entry.set_attribute("title", entry.title.as_str());
+        workspace_entry.set_attribute("style", format!("cursor: pointer; padding: 0.1rem 0.5rem; overflow: hidden; text-overflow: ellipsis; white-space: nowrap{}", if current { "; font-weight: bold" } else { "" }).as_str());
+        workspace_entry.set_field("textContent", format!("{} ({})", entry.name, entry.findings).as_str());
+
+        toc_workspace.append_child(workspace_entry);
     }
 
-    Ok(())
+    toc_workspace.build(view)?;
+
+    //
+    // Update the workspace settings
+    //
+
+    workspace_directory_text.set_field("textContent", workspace.directory.to_string_lossy().as_ref());
+    workspace_directory_text.build(view)?;
+
+    workspace_auditors_input.set_value(workspace::format_auditors(&workspace.settings.auditors).as_str());
+    workspace_auditors_input.build(view)?;
+
+    let mut templates = vec![("none".to_string(), "Default template".to_string())];
+    templates.extend(workspace.templates().into_iter().map(|name| (name.clone(), name)));
+
+    for (value, text) in templates {
+        let mut workspace_template_option = HtmlElement::new("option", "workspace_template_option");
+        workspace_template_option.set_attribute("value", value.as_str());
+        workspace_template_option.set_field("textContent", text.as_str());
+        workspace_template_option.set_selected(workspace.settings.export_template.as_deref().unwrap_or("none") == value);
+        workspace_template_select.append_child(workspace_template_option);
+    }
+
+    workspace_template_select.build(view)
 }
 
-fn export_pdf<'a>(view: &mut web_view::WebView<'a, ()>) -> web_view::WVResult {
-    view.eval("alert('PDF exporting is not currently supported')")
+/// Shows a message next to the controls it is about, or clears it when the message is empty.
+fn set_message<'a>(view: &mut web_view::WebView<'a, ()>, id: &str, message: &str) -> web_view::WVResult {
+    // Messages often hold paths and git output, so they are quoted as JSON rather than by HtmlElement
+    view.eval(format!("document.getElementById('{}').textContent = {}", id, serde_json::to_string(message).unwrap_or_default()).as_str())
 }
 
-fn create_checklist_entry<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
-    let id = state.checklist.len();
-    state.checklist.push((false, String::new()));
-    add_checklist_entry_to_web_view(view, id, (false, ""))
+/// Returns true if the commit hash matches one of the audited commits, which may be abbreviated.
+fn is_audited_commit(state: &StateData, hash: &str) -> bool {
+    state.commit_hashes.iter().any(|audited| git::is_commit_hash(audited) && (hash.starts_with(audited.as_str()) || audited.starts_with(hash)))
 }
 
-fn remove_checklist_entry<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
-    if id < state.checklist.len() {
-        let _ = state.checklist.remove(id);
+fn update_repository_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let mut repository_path_text = HtmlElement::get("repository_path_text");
+    repository_path_text.set_field("textContent", state.repository_path.as_ref().map(String::as_str).unwrap_or("No local checkout selected"));
+    repository_path_text.build(view)?;
 
-        let mut entry_table = HtmlElement::get(format!("checklist{}_table", id).as_str());
-        entry_table.remove();
-        entry_table.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No checklist entry for id {} was found!", id))))
+    let mut repository_warning = HtmlElement::get("repository_warning");
+    let mut repository_commits = HtmlElement::get("repository_commits");
+    repository_commits.set_inner_html("");
+
+    let path = match state.repository_path {
+        None => {
+            repository_warning.set_field("textContent", "");
+            repository_warning.build(view)?;
+            return repository_commits.build(view);
+        }
+
+        Some(ref path) => path.as_str()
+    };
+
+    // Warn when the checkout has moved away from the commit the workbook is pinned to
+    let pinned = state.commit_hashes.iter().rev().find(|hash| git::is_commit_hash(hash));
+
+    let warning = match (git::head(path), pinned) {
+        (Err(error), _) => format!("Warning: the repository was not found at {}, select its checkout again ({})", path, error.trim()),
+        (Ok(head), Some(pinned)) if !head.starts_with(pinned.as_str()) => {
+            format!("Warning: the checkout's HEAD ({}) differs from the pinned commit ({})", &head[..head.len().min(10)], pinned)
+        }
+        _ => String::new()
+    };
+
+    repository_warning.set_field("textContent", warning.as_str());
+    repository_warning.build(view)?;
+
+    // The workbook may have been saved on another machine, so a missing checkout only leaves the commits out
+    let mut commits = git::log(path, git::LOG_LIMIT).unwrap_or_default();
+
+    // Audited commits which are older than the listed history are still shown
+    for hash in state.commit_hashes.iter().filter(|hash| git::is_commit_hash(hash)) {
+        if !commits.iter().any(|commit| commit.hash.starts_with(hash.as_str())) {
+            if let Ok(commit) = git::commit(path, hash) {
+                commits.push(commit);
+            }
+        }
     }
-}
 
-fn clear_checklist_entries<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
-    let mut entries = vec![];
+    let mut commits_table = HtmlElement::new("table", "commits_table");
+    commits_table.set_attribute("style", "margin: 0.5rem; width: 100%");
 
-    for entry in &state.checklist {
-        entries.push(entry.clone());
+    let commits_header_row = commits_table.insert_row(0, "commits_header_row");
+
+    for (index, heading) in ["Audited", "Commit", "Date", "Author", "Summary"].iter().enumerate() {
+        let commits_header_cell = commits_header_row.insert_cell(index, "commits_header_cell");
+        commits_header_cell.set_attribute("style", "font-weight: bold");
+        commits_header_cell.set_field("textContent", heading);
     }
 
-    for (id, _) in entries.iter().enumerate() {
-        let _ = remove_checklist_entry(view, state, id);
+    for (index, commit) in commits.iter().enumerate() {
+        let commits_row = commits_table.insert_row(index + 1, "commits_row");
+
+        let commits_check_cell = commits_row.insert_cell(0, "commits_check_cell");
+
+        let mut commits_check_input = HtmlElement::new("input", "commits_check_input");
+        commits_check_input.set_attribute("type", "checkbox");
+        commits_check_input.set_checked(is_audited_commit(state, commit.hash.as_str()));
+        commits_check_input.set_attribute("onclick", format!("external.invoke('set_audited_commit {} ' + this.checked.toString())", commit.hash).as_str());
+
+        commits_check_cell.append_child(commits_check_input);
+
+        for (cell_index, text) in [commit.short_hash.as_str(), commit.date.as_str(), commit.author.as_str(), commit.summary.as_str()].iter().enumerate() {
+            let commits_cell = commits_row.insert_cell(cell_index + 1, "commits_cell");
+            commits_cell.set_field("textContent", text);
+        }
     }
 
-    Ok(())
+    repository_commits.append_child(commits_table);
+    repository_commits.build(view)
 }
 
-fn set_checklist_entry_checked<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, checked: bool) -> web_view::WVResult {
-    if id < state.checklist.len() {
-        state.checklist[id].0 = checked;
+fn update_reaudit_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let mut initial_commit_text = HtmlElement::get("reaudit_initial_commit_text");
+    initial_commit_text.set_field("textContent", state.commit_hashes.iter().find(|hash| git::is_commit_hash(hash)).map(String::as_str).unwrap_or("No audited commit picked"));
+    initial_commit_text.build(view)?;
 
-        let mut entry_check_input = HtmlElement::get(format!("checklist{}_check_input", id).as_str());
-        entry_check_input.set_checked(checked);
-        entry_check_input.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No checklist entry for id {} was found!", id))))
+    let mut fix_commit_input = HtmlElement::get("reaudit_fix_commit_input");
+    fix_commit_input.set_value(state.fix_commit.as_ref().map(String::as_str).unwrap_or(""));
+    fix_commit_input.build(view)?;
+
+    let display_ids = template::display_ids(state);
+
+    let mut reaudit_table = HtmlElement::new("table", "reaudit_table");
+    reaudit_table.set_attribute("style", "margin: 0.5rem; width: 100%");
+
+    let reaudit_header_row = reaudit_table.insert_row(0, "reaudit_header_row");
+
+    for (index, heading) in ["ID", "Title", "Code", "Locations", "Verdict", ""].iter().enumerate() {
+        let reaudit_header_cell = reaudit_header_row.insert_cell(index, "reaudit_header_cell");
+        reaudit_header_cell.set_attribute("style", "font-weight: bold");
+        reaudit_header_cell.set_field("textContent", heading);
     }
-}
 
-fn set_checklist_entry_text<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, text: &str) -> web_view::WVResult {
-    if id < state.checklist.len() {
-        state.checklist[id].1 = text.to_string();
+    for (index, change) in state.reaudit_changes.iter().enumerate() {
+        let finding = match state.findings.get(&change.id) {
+            None => continue,
+            Some(finding) => finding
+        };
 
-        let mut entry_text_input = HtmlElement::get(format!("checklist{}_text_input", id).as_str());
-        entry_text_input.set_value(text);
-        entry_text_input.build(view)
-    
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No checklist entry for id {} was found!", id))))
+        let reaudit_row = reaudit_table.insert_row(index + 1, "reaudit_row");
+
+        let locations = change.locations.iter()
+            .map(|location| format!("{} ({})", location.text, if location.changed { "changed" } else { "unchanged" }))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        let verdict = finding.reaudit.as_ref().map(|reaudit| reaudit.verdict.name()).unwrap_or("-");
+
+        for (cell_index, text) in [template::finding_label(&display_ids, finding.id).as_str(), finding.title.as_str(), change.change().name(), locations.as_str(), verdict].iter().enumerate() {
+            let reaudit_cell = reaudit_row.insert_cell(cell_index, "reaudit_cell");
+            reaudit_cell.set_field("textContent", text);
+        }
+
+        let reaudit_button_cell = reaudit_row.insert_cell(5, "reaudit_button_cell");
+        reaudit_button_cell.set_attribute("style", "white-space: nowrap");
+
+        for verdict in &[Verdict::Resolved, Verdict::Unresolved] {
+            let mut reaudit_verdict_button = HtmlElement::new("button", "reaudit_verdict_button");
+            reaudit_verdict_button.set_attribute("onclick", format!("external.invoke(\"set_reaudit_verdict {} {}\")", finding.id, verdict.name().to_lowercase()).as_str());
+            reaudit_verdict_button.set_inner_html(verdict.name());
+
+            reaudit_button_cell.append_child(reaudit_verdict_button);
+        }
     }
+
+    let mut reaudit = HtmlElement::get("reaudit");
+    reaudit.set_inner_html("");
+
+    if !state.reaudit_changes.is_empty() {
+        reaudit.append_child(reaudit_table);
+    }
+
+    reaudit.build(view)
 }
 
-fn create_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
-    state.current_finding_id += 1;
+fn update_dashboard_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let summary = Summary::new(state);
+
+    let mut dashboard_table = HtmlElement::new("table", "dashboard_table");
+    dashboard_table.set_attribute("style", "margin: 0.5rem; width: 100%; table-layout: fixed");
+
+    let dashboard_row = dashboard_table.insert_row(0, "dashboard_row");
+
+    let mut create_count_cell = |index, heading: &str, counts: Vec<(String, usize)>| {
+        let dashboard_cell = dashboard_row.insert_cell(index, "dashboard_cell");
+
+        let mut count_table = HtmlElement::new("table", "count_table");
+        count_table.set_attribute("style", "width: 100%; padding-right: 1rem");
+
+        let count_header_row = count_table.insert_row(0, "count_header_row");
+        let count_header_cell = count_header_row.insert_cell(0, "count_header_cell");
+        count_header_cell.set_attribute("colspan", "2");
+        count_header_cell.set_attribute("style", "font-weight: bold");
+        count_header_cell.set_field("textContent", heading);
+
+        for (row_index, (name, count)) in counts.iter().enumerate() {
+            let count_row = count_table.insert_row(row_index + 1, "count_row");
 
-    let finding = Finding {
-        id: state.current_finding_id,
-        title: format!("Finding{}", state.current_finding_id),
-        class: String::new(),
-        severity: None,
-        location: String::new(),
-        description: String::new(),
-        recommendation: String::new(),
-        alleviation: String::new()
+            let count_name_cell = count_row.insert_cell(0, "count_name_cell");
+            count_name_cell.set_field("textContent", name);
+
+            let count_value_cell = count_row.insert_cell(1, "count_value_cell");
+            count_value_cell.set_attribute("style", "text-align: right");
+            count_value_cell.set_inner_html(count.to_string().as_str());
+        }
+
+        dashboard_cell.append_child(count_table);
     };
 
-    // Attempt to add the new finding to the state data findings map
-    assert!(state.findings.insert(finding.id, finding.clone()).is_none());
+    let to_pairs = |counts: &[summary::Count]| counts.iter().map(|count| (count.name.clone(), count.count)).collect::<Vec<_>>();
 
-    // Attempt to add the new finding to the web view
-    add_finding_to_web_view(view, &finding)
+    let mut severities = to_pairs(&summary.severities);
+    severities.push(("Unrated".to_string(), summary.unrated));
+
+    let severity_heading = match summary.drafts {
+        0 => format!("Severity ({} total)", summary.total),
+        drafts => format!("Severity ({} total, {} drafts)", summary.total, drafts)
+    };
+
+    create_count_cell(0, severity_heading.as_str(), severities);
+    create_count_cell(1, "Status", to_pairs(&summary.statuses));
+    create_count_cell(2, "Type", to_pairs(&summary.classes));
+    create_count_cell(3, "Most Affected Files", to_pairs(&summary.files));
+    create_count_cell(4, "Checklist", vec![
+        ("Completed".to_string(), summary.checklist_checked),
+        ("Remaining".to_string(), summary.checklist_total - summary.checklist_checked),
+        ("Percent Complete".to_string(), summary.checklist_percent())
+    ]);
+
+    let mut dashboard = HtmlElement::get("dashboard");
+    dashboard.set_inner_html("");
+    dashboard.append_child(dashboard_table);
+    dashboard.build(view)
 }
 
-fn copy_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
-    if let Some(finding) = state.findings.get(&id) {
-        // Set the copied finding in the state data
-        state.copied_finding = Some(finding.clone());
+fn update_baseline_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let mut baseline_table = HtmlElement::new("table", "baseline_table");
+    baseline_table.set_attribute("style", "margin: 0.5rem; width: 100%");
 
-        // Attempt to enable the paste button in the web view
-        let mut paste_button = html::HtmlElement::get("paste_button");
-        paste_button.set_disabled(false);
-        paste_button.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    let baseline_header_row = baseline_table.insert_row(0, "baseline_header_row");
+
+    for (index, heading) in ["Tool", "Rule", "File", "Disposition", "Fingerprint", ""].iter().enumerate() {
+        let baseline_header_cell = baseline_header_row.insert_cell(index, "baseline_header_cell");
+        baseline_header_cell.set_attribute("style", "font-weight: bold");
+        baseline_header_cell.set_field("textContent", heading);
     }
-}
 
-fn paste_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
-    let mut finding = state.copied_finding.clone().unwrap();
-    state.current_finding_id += 1;
-    finding.id = state.current_finding_id;
-    add_finding_to_web_view(view, &finding)?;
-    assert!(state.findings.insert(finding.id, finding).is_none());
-    Ok(())
+    for (index, suppression) in state.baseline.iter().enumerate() {
+        let baseline_row = baseline_table.insert_row(index + 1, "baseline_row");
+
+        let fingerprint = &suppression.fingerprint[..suppression.fingerprint.len().min(12)];
+        let description = suppression.description();
+
+        for (cell_index, text) in [suppression.tool.as_str(), suppression.rule.as_str(), suppression.file.as_str(), description.as_str(), fingerprint].iter().enumerate() {
+            let baseline_cell = baseline_row.insert_cell(cell_index, "baseline_cell");
+            baseline_cell.set_field("textContent", text);
+        }
+
+        let baseline_button_cell = baseline_row.insert_cell(5, "baseline_button_cell");
+
+        let mut baseline_remove_button = HtmlElement::new("button", "baseline_remove_button");
+        baseline_remove_button.set_attribute("onclick", format!("external.invoke(\"remove_suppression {}\")", index).as_str());
+        baseline_remove_button.set_inner_html("Remove");
+
+        baseline_button_cell.append_child(baseline_remove_button);
+    }
+
+    let mut baseline = HtmlElement::get("baseline");
+    baseline.set_inner_html(if state.baseline.is_empty() { "No suppressed results" } else { "" });
+
+    if !state.baseline.is_empty() {
+        baseline.append_child(baseline_table);
+    }
+
+    baseline.build(view)
 }
 
-fn remove_finding<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize) -> web_view::WVResult {
-    if state.findings.remove(&id).is_some() {
-        let _ = state.findings.remove(&id);
+/// Fills a finding's fields table with its tags and the workbook's custom fields.
+fn build_finding_fields_table(fields_table: &mut HtmlElement, finding: &Finding, fields: &[CustomField]) {
+    let fields_label_row = fields_table.insert_row(0, "fields_label_row");
 
-        let mut finding = HtmlElement::get(format!("finding{}", id).as_str());
-        finding.remove();
-        finding.build(view)?;
+    let fields_tags_label_cell = fields_label_row.insert_cell(0, "fields_tags_label_cell");
+    fields_tags_label_cell.set_attribute("style", "padding-top: 6.5px; padding-bottom: 6.5px");
+    fields_tags_label_cell.set_inner_html("Tags");
 
-        let mut link = HtmlElement::get(format!("finding{}_link_p", id).as_str());
-        link.remove();
-        link.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    for (index, field) in fields.iter().enumerate() {
+        let fields_label_cell = fields_label_row.insert_cell(index + 1, "fields_label_cell");
+        fields_label_cell.set_attribute("style", "padding-top: 6.5px; padding-bottom: 6.5px");
+        fields_label_cell.set_field("textContent", field.name.as_str());
+    }
+
+    let fields_input_row = fields_table.insert_row(1, "fields_input_row");
+
+    let fields_tags_cell = fields_input_row.insert_cell(0, "fields_tags_cell");
+
+    let mut fields_tags_input = HtmlElement::new("input", "fields_tags_input");
+    fields_tags_input.set_attribute("type", "text");
+    fields_tags_input.set_attribute("id", format!("finding{}_tags", finding.id).as_str());
+    fields_tags_input.set_attribute("placeholder", "security, needs PoC");
+    fields_tags_input.set_attribute("value", finding.tags.join(", ").as_str());
+    fields_tags_input.set_attribute("onchange", format!("external.invoke('set_finding_tags {} ' + quote(this.value))", finding.id).as_str());
+
+    fields_tags_cell.append_child(fields_tags_input);
+
+    for (index, field) in fields.iter().enumerate() {
+        let fields_input_cell = fields_input_row.insert_cell(index + 1, "fields_input_cell");
+
+        let value = finding.custom_fields.get(&field.name).map(String::as_str).unwrap_or("");
+        let onchange = format!("external.invoke('set_finding_field {} ' + quote({}) + ' ' + quote(this.value))", finding.id, serde_json::to_string(&field.name).unwrap_or_default());
+
+        let mut fields_input = match field.kind {
+            FieldKind::Enum => {
+                let mut fields_select = HtmlElement::new("select", "fields_select");
+
+                let mut option = HtmlElement::new("option", "fields_option");
+                option.set_attribute("value", "");
+                option.set_inner_html("Select...");
+                fields_select.append_child(option);
+
+                for text in &field.options {
+                    let mut option = HtmlElement::new("option", "fields_option");
+                    option.set_attribute("value", text.as_str());
+                    option.set_field("textContent", text.as_str());
+                    option.set_selected(text == value);
+                    fields_select.append_child(option);
+                }
+
+                fields_select
+            }
+
+            kind => {
+                let mut fields_input = HtmlElement::new("input", "fields_input");
+                fields_input.set_attribute("type", if kind == FieldKind::Number { "number" } else { "text" });
+                fields_input.set_attribute("value", value);
+                fields_input
+            }
+        };
+
+        fields_input.set_attribute("id", format!("finding{}_field{}", finding.id, index).as_str());
+        fields_input.set_attribute("onchange", onchange.as_str());
+
+        fields_input_cell.append_child(fields_input);
     }
 }
 
-fn clear_findings<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData) -> web_view::WVResult {
-    let mut ids = vec![];
+fn update_custom_fields_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let mut custom_fields_table = HtmlElement::new("table", "custom_fields_table");
+    custom_fields_table.set_attribute("style", "margin: 0.5rem; width: 100%");
 
-    for id in state.findings.keys() {
-        ids.push(*id);
+    let custom_fields_header_row = custom_fields_table.insert_row(0, "custom_fields_header_row");
+
+    for (index, heading) in ["Name", "Kind", "Options", "Exported", ""].iter().enumerate() {
+        let custom_fields_header_cell = custom_fields_header_row.insert_cell(index, "custom_fields_header_cell");
+        custom_fields_header_cell.set_attribute("style", "font-weight: bold");
+        custom_fields_header_cell.set_field("textContent", heading);
     }
 
-    for id in ids {
-        remove_finding(view, state, id)?;
+    for (index, field) in state.custom_fields.iter().enumerate() {
+        let custom_field_row = custom_fields_table.insert_row(index + 1, "custom_field_row");
+
+        let options = field.options.join(", ");
+
+        for (cell_index, text) in [field.name.as_str(), field.kind.name(), options.as_str()].iter().enumerate() {
+            let custom_field_cell = custom_field_row.insert_cell(cell_index, "custom_field_cell");
+            custom_field_cell.set_field("textContent", text);
+        }
+
+        let custom_field_exported_cell = custom_field_row.insert_cell(3, "custom_field_exported_cell");
+
+        let mut custom_field_exported_input = HtmlElement::new("input", "custom_field_exported_input");
+        custom_field_exported_input.set_attribute("type", "checkbox");
+        custom_field_exported_input.set_attribute("onchange", format!("external.invoke('set_custom_field_exported {} ' + this.checked)", index).as_str());
+        custom_field_exported_input.set_checked(field.exported);
+
+        custom_field_exported_cell.append_child(custom_field_exported_input);
+
+        let custom_field_button_cell = custom_field_row.insert_cell(4, "custom_field_button_cell");
+
+        let mut custom_field_remove_button = HtmlElement::new("button", "custom_field_remove_button");
+        custom_field_remove_button.set_attribute("onclick", format!("if (confirm('Remove the field and its values from every finding?')) external.invoke(\"remove_custom_field {}\")", index).as_str());
+        custom_field_remove_button.set_inner_html("Remove");
+
+        custom_field_button_cell.append_child(custom_field_remove_button);
     }
 
-    state.current_finding_id = 0;
+    let mut custom_fields = HtmlElement::get("custom_fields");
+    custom_fields.set_inner_html(if state.custom_fields.is_empty() { "No custom fields" } else { "" });
+
+    if !state.custom_fields.is_empty() {
+        custom_fields.append_child(custom_fields_table);
+    }
+
+    custom_fields.build(view)?;
+
+    // Every finding shows an input for each field
+    for finding in state.findings.values() {
+        let mut fields_table = HtmlElement::get(format!("finding{}_fields", finding.id).as_str());
+        fields_table.set_inner_html("");
+        build_finding_fields_table(&mut fields_table, finding, &state.custom_fields);
+        fields_table.build(view)?;
+    }
 
     Ok(())
 }
 
-fn set_finding_title<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, title: &str) -> web_view::WVResult {
-    if let Some(entry) = state.findings.get_mut(&id) {
-        entry.title = title.to_string();
-
-        let mut finding_link = HtmlElement::get(format!("finding{}_link", id).as_str());
-        finding_link.set_inner_html(title);
-        finding_link.build(view)?;
+fn update_finding_filter_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let mut shown = 0;
 
-        let mut finding_title_id = HtmlElement::get(format!("finding{}_title_id", id).as_str());
-        finding_title_id.set_inner_html(format!("{}: ", id).as_str());
-        finding_title_id.build(view)?;
+    for finding in state.findings.values() {
+        let display = if state.finding_filter.matches(finding) {
+            shown += 1;
+            ""
+        } else {
+            "none"
+        };
 
-        let mut finding_title = HtmlElement::get(format!("finding{}_title", id).as_str());
-        finding_title.set_inner_html(title);
-        finding_title.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+        for id in [format!("finding{}", finding.id), format!("finding{}_link_p", finding.id)].iter() {
+            let mut element = HtmlElement::get(id.as_str());
+            element.set_field("style.display", display);
+            element.build(view)?;
+        }
     }
+
+    let mut finding_filter_text = HtmlElement::get("finding_filter_text");
+
+    finding_filter_text.set_field(
+        "textContent",
+        if state.finding_filter.is_empty() { String::new() } else { format!("Showing {} of {}", shown, state.findings.len()) }.as_str()
+    );
+
+    finding_filter_text.build(view)
 }
 
-fn set_finding_type<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, class: &str) -> web_view::WVResult {
-    if let Some(entry) = state.findings.get_mut(&id) {
-        entry.class = class.to_string();
+fn update_lint_rules_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let mut lint_rules_table = HtmlElement::new("table", "lint_rules_table");
+    lint_rules_table.set_attribute("style", "margin: 0.5rem");
 
-        let mut finding_type = HtmlElement::get(format!("finding{}_type", id).as_str());
-        finding_type.set_inner_html(class);
-        finding_type.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    for (index, rule) in lint::Rule::ALL.iter().enumerate() {
+        let lint_rule_row = lint_rules_table.insert_row(index, "lint_rule_row");
+
+        let lint_rule_name_cell = lint_rule_row.insert_cell(0, "lint_rule_name_cell");
+        lint_rule_name_cell.set_attribute("style", "white-space: nowrap; padding-right: 0.5rem");
+        lint_rule_name_cell.set_field("textContent", rule.name());
+
+        let lint_rule_level_cell = lint_rule_row.insert_cell(1, "lint_rule_level_cell");
+
+        let mut lint_rule_select = HtmlElement::new("select", "lint_rule_select");
+        lint_rule_select.set_attribute("id", format!("lint_rule_{}_select", rule.key()).as_str());
+        lint_rule_select.set_attribute("onchange", format!("external.invoke('set_lint_rule {} ' + this.value)", rule.key()).as_str());
+
+        for level in &lint::Level::ALL {
+            let mut option = HtmlElement::new("option", "lint_rule_option");
+            option.set_attribute("value", level.key());
+            option.set_inner_html(level.name());
+            option.set_selected(state.lint_rules.level(*rule) == *level);
+            lint_rule_select.append_child(option);
+        }
+
+        lint_rule_level_cell.append_child(lint_rule_select);
     }
+
+    let mut lint_rules = HtmlElement::get("lint_rules");
+    lint_rules.set_inner_html("");
+    lint_rules.append_child(lint_rules_table);
+    lint_rules.build(view)
 }
 
-fn set_finding_severity<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, severity: &str) -> web_view::WVResult {
-    if let Some(entry) = state.findings.get_mut(&id) {
-        entry.severity = match severity {
-            "critical" => Some(report::Severity::Critical),
-            "major" => Some(report::Severity::Major),
-            "minor" => Some(report::Severity::Minor),
-            "informational" => Some(report::Severity::Informational),
-            _ => None
-        };
+fn update_lint_problems_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData, problems: &[lint::Problem]) -> web_view::WVResult {
+    let display_ids = template::display_ids(state);
+
+    let mut lint_problems_table = HtmlElement::new("table", "lint_problems_table");
+    lint_problems_table.set_attribute("style", "margin: 0.5rem; width: 100%");
+
+    let lint_problems_header_row = lint_problems_table.insert_row(0, "lint_problems_header_row");
+
+    for (index, heading) in ["Finding", "Level", "Rule", "Problem"].iter().enumerate() {
+        let lint_problems_header_cell = lint_problems_header_row.insert_cell(index, "lint_problems_header_cell");
+        lint_problems_header_cell.set_attribute("style", "font-weight: bold");
+        lint_problems_header_cell.set_field("textContent", heading);
+    }
+
+    for (index, problem) in problems.iter().enumerate() {
+        let lint_problem_row = lint_problems_table.insert_row(index + 1, "lint_problem_row");
+
+        let lint_problem_finding_cell = lint_problem_row.insert_cell(0, "lint_problem_finding_cell");
+        lint_problem_finding_cell.set_attribute("style", "white-space: nowrap");
+
+        match problem.finding_id {
+            None => lint_problem_finding_cell.set_field("textContent", "Report"),
+
+            Some(id) => {
+                let mut lint_problem_link = HtmlElement::new("a", "lint_problem_link");
+                lint_problem_link.set_attribute("href", format!("#finding{}", id).as_str());
+                lint_problem_link.set_field("textContent", template::finding_label(&display_ids, id).as_str());
+                lint_problem_finding_cell.append_child(lint_problem_link);
+            }
+        }
 
-        let style = match entry.severity {
-            Some(report::Severity::Critical) => "color: red",
-            Some(report::Severity::Major) => "color: orange",
-            Some(report::Severity::Minor) => "color: yellow",
-            Some(report::Severity::Informational) => "color: green",
-            None => "color: inherit",
-        };
+        let lint_problem_level_cell = lint_problem_row.insert_cell(1, "lint_problem_level_cell");
+        lint_problem_level_cell.set_attribute("style", if problem.level == lint::Level::Error { "color: red" } else { "color: orange" });
+        lint_problem_level_cell.set_field("textContent", problem.level.name());
 
-        let mut finding_link = HtmlElement::get(format!("finding{}_link", id).as_str());
-        finding_link.set_attribute("style", style);
-        finding_link.build(view)?;
+        for (cell_index, text) in [problem.rule.name(), problem.message.as_str()].iter().enumerate() {
+            let lint_problem_cell = lint_problem_row.insert_cell(cell_index + 2, "lint_problem_cell");
+            lint_problem_cell.set_field("textContent", text);
+        }
+    }
 
-        let mut description_header = HtmlElement::get(format!("finding{}_description_header", id).as_str());
-        description_header.set_attribute("style", style);
-        description_header.build(view)?;
+    let (errors, warnings) = lint::count(problems);
 
-        let mut recommendation_header = HtmlElement::get(format!("finding{}_recommendation_header", id).as_str());
-        recommendation_header.set_attribute("style", style);
-        recommendation_header.build(view)?;
+    let mut lint_summary_text = HtmlElement::get("lint_summary_text");
+    lint_summary_text.set_field("textContent", format!("{} errors, {} warnings", errors, warnings).as_str());
+    lint_summary_text.build(view)?;
 
-        let mut alleviation_header = HtmlElement::get(format!("finding{}_alleviation_header", id).as_str());
-        alleviation_header.set_attribute("style", style);
-        alleviation_header.build(view)?;
+    let mut lint_problems = HtmlElement::get("lint_problems");
+    lint_problems.set_inner_html("");
 
-        let mut option = HtmlElement::get(format!("finding{}_severity_{}_option", id, severity).as_str());
-        option.set_selected(true);
-        option.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    if !problems.is_empty() {
+        lint_problems.append_child(lint_problems_table);
     }
+
+    lint_problems.build(view)
 }
 
-fn set_finding_location<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, location: &str) -> web_view::WVResult {
-    if let Some(entry) = state.findings.get_mut(&id) {
-        entry.location = location.to_string();
+fn update_shortcuts_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let commands = command::registry();
 
-        let mut finding_location = HtmlElement::get(format!("finding{}_location", id).as_str());
-        finding_location.set_inner_html(location);
-        finding_location.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
-    }
-}
+    let mut shortcuts_table = HtmlElement::new("table", "shortcuts_table");
+    shortcuts_table.set_attribute("style", "margin: 0.5rem");
 
-fn set_finding_description<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, description: &str) -> web_view::WVResult {
-    if let Some(entry) = state.findings.get_mut(&id) {
-        entry.description = description.to_string();
+    let mut bindings = std::collections::BTreeMap::new();
 
-        let mut finding_description = HtmlElement::get(format!("finding{}_description", id).as_str());
-        finding_description.set_inner_html(description);
-        finding_description.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
-    }
-}
+    for (index, command) in commands.iter().enumerate() {
+        let shortcut = state.shortcuts.get(command.name.as_str());
 
-fn set_finding_recommendation<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, recommendation: &str) -> web_view::WVResult {
-    if let Some(entry) = state.findings.get_mut(&id) {
-        entry.recommendation = recommendation.to_string();
+        let shortcut_row = shortcuts_table.insert_row(index, "shortcut_row");
 
-        let mut finding_recommendation = HtmlElement::get(format!("finding{}_recommendation", id).as_str());
-        finding_recommendation.set_inner_html(recommendation);
-        finding_recommendation.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+        let shortcut_title_cell = shortcut_row.insert_cell(0, "shortcut_title_cell");
+        shortcut_title_cell.set_attribute("style", "white-space: nowrap; padding-right: 0.5rem");
+        shortcut_title_cell.set_field("textContent", command.title.as_str());
+
+        let shortcut_input_cell = shortcut_row.insert_cell(1, "shortcut_input_cell");
+
+        let mut shortcut_input = HtmlElement::new("input", "shortcut_input");
+        shortcut_input.set_attribute("type", "text");
+        shortcut_input.set_attribute("placeholder", "None");
+        shortcut_input.set_attribute("value", shortcut.as_str());
+        shortcut_input.set_attribute("onchange", format!("external.invoke('set_shortcut {} ' + quote(this.value))", command.name).as_str());
+        shortcut_input_cell.append_child(shortcut_input);
+
+        let shortcut_message_cell = shortcut_row.insert_cell(2, "shortcut_message_cell");
+        shortcut_message_cell.set_attribute("id", format!("shortcut_{}_message", command.name).as_str());
+        shortcut_message_cell.set_attribute("style", "padding-left: 0.5rem; color: tomato");
+
+        if !shortcut.is_empty() {
+            bindings.insert(shortcut, command);
+        }
     }
-}
 
-fn set_finding_alleviation<'a>(view: &mut web_view::WebView<'a, ()>, state: &mut StateData, id: usize, alleviation: &str) -> web_view::WVResult {
-    if let Some(entry) = state.findings.get_mut(&id) {
-        entry.alleviation = alleviation.to_string();
+    let mut shortcuts = HtmlElement::get("shortcuts");
+    shortcuts.set_inner_html("");
+    shortcuts.append_child(shortcuts_table);
+    shortcuts.build(view)?;
 
-        let mut finding_alleviation = HtmlElement::get(format!("finding{}_alleviation", id).as_str());
-        finding_alleviation.set_inner_html(alleviation);
-        finding_alleviation.build(view)
-    } else {
-        Err(web_view::Error::Custom(Box::new(format!("No finding for id {} was found!", id))))
+    match serde_json::to_string(&bindings) {
+        Err(error) => Err(web_view::Error::Custom(Box::new(error))),
+        Ok(json) => view.eval(format!("setShortcuts({})", json).as_str())
     }
 }
 
-fn add_finding_to_web_view<'a>(view: &mut web_view::WebView<'a, ()>, finding: &Finding) -> web_view::WVResult {
-    //
-    // Create a new finding table
-    //
+fn update_find_results_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let mut find_summary_text = HtmlElement::get("find_summary_text");
+    let mut find_results = HtmlElement::get("find_results");
+    find_results.set_inner_html("");
 
-    let mut new_table = HtmlElement::new("table", "new_table");
-    new_table.set_attribute("id", format!("finding{}", finding.id).as_str());
-    new_table.set_attribute("style", "margin: 1rem");
+    let results = match state.find_replace.search(state) {
+        Err(error) => {
+            find_summary_text.set_field("textContent", error.as_str());
+            find_summary_text.build(view)?;
+            return find_results.build(view);
+        }
 
-    let new_row = new_table.insert_row(0, "new_row");
-    let new_cell = new_row.insert_cell(0, "new_cell");
+        Ok(results) => results
+    };
 
-    //
-    // Create the toolbar table for the new finding
-    //
+    let mut find_results_table = HtmlElement::new("table", "find_results_table");
+    find_results_table.set_attribute("style", "margin: 0.5rem; width: 100%");
 
-    let mut toolbar_table = HtmlElement::new("table", "toolbar_table");
-    toolbar_table.set_attribute("style", "table-layout: fixed; width: 100%");
+    let find_results_header_row = find_results_table.insert_row(0, "find_results_header_row");
 
-    let toolbar_row = toolbar_table.insert_row(0, "toolbar_row");
-    
-    let toolbar_cell = toolbar_row.insert_cell(0, "toolbar_cell");
+    for (index, heading) in ["Field", "Matches", ""].iter().enumerate() {
+        let find_results_header_cell = find_results_header_row.insert_cell(index, "find_results_header_cell");
+        find_results_header_cell.set_attribute("style", "font-weight: bold");
+        find_results_header_cell.set_field("textContent", heading);
+    }
 
-    // ---------------------------------------------------
+    let display_ids = template::display_ids(state);
 
-    let mut toolbar_close_button = HtmlElement::new("button", "toolbar_close_button");
-    toolbar_close_button.set_inner_html("X");
-    toolbar_close_button.set_field("style.backgroundColor", "tomato");
-    toolbar_close_button.set_attribute("onclick", format!("external.invoke(\"remove_finding {}\")", finding.id).as_str());
-    
-    toolbar_cell.append_child(toolbar_close_button);
+    for (index, result) in results.iter().enumerate() {
+        let find_result_row = find_results_table.insert_row(index + 1, "find_result_row");
 
-    // ---------------------------------------------------
+        let find_result_field_cell = find_result_row.insert_cell(0, "find_result_field_cell");
+        find_result_field_cell.set_attribute("style", "white-space: nowrap; vertical-align: top");
 
-    let mut toolbar_copy_button = HtmlElement::new("button", "toolbar_copy_button");
-    toolbar_copy_button.set_attribute("onclick", format!("external.invoke(\"copy_finding {}\")", finding.id).as_str());
-    toolbar_copy_button.set_inner_html("Copy");
+        match result.field {
+            replace::Field::Finding(id, _) => {
+                let mut find_result_link = HtmlElement::new("a", "find_result_link");
+                find_result_link.set_attribute("href", format!("#finding{}", id).as_str());
+                find_result_link.set_field("textContent", result.field.label(&display_ids).as_str());
+                find_result_field_cell.append_child(find_result_link);
+            }
 
-    toolbar_cell.append_child(toolbar_copy_button);
+            _ => find_result_field_cell.set_field("textContent", result.field.label(&display_ids).as_str())
+        }
 
-    // ---------------------------------------------------
+        let find_result_matches_cell = find_result_row.insert_cell(1, "find_result_matches_cell");
 
-    new_cell.append_child(toolbar_table);
+        for entry in &result.matches {
+            let (before, after) = result.context(entry);
 
-    //
-    // Create the title table for the new finding
-    //
+            let mut find_match_div = HtmlElement::new("div", "find_match_div");
+            find_match_div.set_attribute("style", "font-family: monospace; white-space: pre-wrap");
 
-    let mut title_table = HtmlElement::new("table", "title_table");
-    title_table.set_attribute("style", "width: 100%");
+            let mut find_match_before = HtmlElement::new("span", "find_match_before");
+            find_match_before.set_field("textContent", before.as_str());
+            find_match_div.append_child(find_match_before);
 
-    let title_row = title_table.insert_row(0, "title_row");
+            let mut find_match_text = HtmlElement::new("del", "find_match_text");
+            find_match_text.set_attribute("style", "background-color: #fdd");
+            find_match_text.set_field("textContent", &result.text[entry.start..entry.end]);
+            find_match_div.append_child(find_match_text);
 
-    // ---------------------------------------------------
+            let mut find_match_replacement = HtmlElement::new("ins", "find_match_replacement");
+            find_match_replacement.set_attribute("style", "background-color: #dfd");
+            find_match_replacement.set_field("textContent", entry.replacement.as_str());
+            find_match_div.append_child(find_match_replacement);
 
-    let title_image_cell = title_row.insert_cell(0, "title_image_cell");
-    title_image_cell.set_attribute("style", "vertical-align: middle");
-    
-    let mut title_image = HtmlElement::new("img", "title_image");
-    title_image.set_attribute("src", "https://i.imgur.com/QwKomxS.png");
-    title_image.set_attribute("style", "width: 40px; height: auto");
-    
-    title_image_cell.append_child(title_image);
+            let mut find_match_after = HtmlElement::new("span", "find_match_after");
+            find_match_after.set_field("textContent", after.as_str());
+            find_match_div.append_child(find_match_after);
 
-    // ---------------------------------------------------
+            find_result_matches_cell.append_child(find_match_div);
+        }
 
-    let title_id_cell = title_row.insert_cell(1, "title_id_cell");
-    title_id_cell.set_inner_html(format!("{}: ", finding.id).as_str());
-    title_id_cell.set_attribute("id", format!("finding{}_title_id", finding.id).as_str());
-    title_id_cell.set_attribute("style", "font-size: 1.5rem; padding-left: 0.25rem; padding-right: 0.25rem; text-align: center; vertical-align: middle");
-    
-    // ---------------------------------------------------
+        let find_result_button_cell = find_result_row.insert_cell(2, "find_result_button_cell");
+        find_result_button_cell.set_attribute("style", "vertical-align: top");
 
-    let title_text_cell = title_row.insert_cell(2, "title_text_cell");
-    title_text_cell.set_attribute("style", "width: 100%; vertical-align: middle");
-    
-    let mut title_text_input = HtmlElement::new("input", "title_text_input");
-    title_text_input.set_attribute("type", "text");
-    title_text_input.set_attribute("id", format!("finding{}_title", finding.id).as_str());
-    title_text_input.set_attribute("value", finding.title.as_str());
-    title_text_input.set_attribute("style", "font-size: 1.5rem");
-    title_text_input.set_attribute("onchange", format!("external.invoke('set_finding_{} {} \"' + this.value.toString().replaceAll('\"', '\\\\'\\\\'') + '\"')", "title", finding.id).as_str());
-    
-    title_text_cell.append_child(title_text_input);
-    
-    // ---------------------------------------------------
+        let mut find_result_replace_button = HtmlElement::new("button", "find_result_replace_button");
+        find_result_replace_button.set_attribute("onclick", format!("external.invoke('replace_in_field {}')", index).as_str());
+        find_result_replace_button.set_inner_html("Replace");
+        find_result_button_cell.append_child(find_result_replace_button);
+    }
 
-    new_cell.append_child(title_table);
+    let count: usize = results.iter().map(|result| result.matches.len()).sum();
 
-    //
-    // Create the header table for the new finding
-    //
+    find_summary_text.set_field(
+        "textContent",
+        if state.find_replace.pattern.is_empty() { String::new() } else { format!("{} matches in {} fields", count, results.len()) }.as_str()
+    );
 
-    let mut header_table = HtmlElement::new("table", "header_table");
-    header_table.set_attribute("style", "width: 100%");
+    find_summary_text.build(view)?;
 
-    // ---------------------------------------------------
+    if !results.is_empty() {
+        find_results.append_child(find_results_table);
+    }
 
-    let header_row1 = header_table.insert_row(0, "header_row1");
+    find_results.build(view)
+}
 
-    let mut create_header_cell = |index, text| {
-        let header_cell = header_row1.insert_cell(index, format!("header_cell{}", index).as_str());
-        header_cell.set_attribute("style", "padding-top: 6.5px; padding-bottom: 6.5px");
-        header_cell.set_inner_html(text);
-    };
+fn update_merge_conflicts_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let mut conflicts_table = HtmlElement::new("table", "conflicts_table");
+    conflicts_table.set_attribute("style", "margin: 0.5rem; width: 100%");
 
-    create_header_cell(0, "Type");
-    create_header_cell(1, "Severity");
-    create_header_cell(2, "Location");
+    let conflicts_header_row = conflicts_table.insert_row(0, "conflicts_header_row");
 
-    // ---------------------------------------------------
+    for (index, heading) in ["Finding", "Field", "Base", "Candidates"].iter().enumerate() {
+        let conflicts_header_cell = conflicts_header_row.insert_cell(index, "conflicts_header_cell");
+        conflicts_header_cell.set_attribute("style", "font-weight: bold");
+        conflicts_header_cell.set_field("textContent", heading);
+    }
 
-    let header_row2 = header_table.insert_row(1, "header_row2");
+    let display_ids = template::display_ids(state);
+    let mut row_index = 1;
 
-    let header_type_cell = header_row2.insert_cell(0, "header_type_cell");
+    for (index, conflict) in state.merge_conflicts.iter().enumerate() {
+        let finding_text = match conflict.finding_id {
+            None => "Report".to_string(),
 
-    let mut header_type_input = HtmlElement::new("input", "header_type_input");
-    header_type_input.set_attribute("type", "text");
-    header_type_input.set_attribute("value", finding.class.as_str());
-    header_type_input.set_attribute("id", format!("finding{}_type", finding.id).as_str());
-    header_type_input.set_attribute("onchange", format!("external.invoke('set_finding_{} {} \"' + this.value.toString().replaceAll('\"', '\\\\'\\\\'') + '\"')", "type", finding.id).as_str());
-    
-    header_type_cell.append_child(header_type_input);
+            // Findings removed while resolving an earlier conflict have nothing left to resolve
+            Some(id) if !state.findings.contains_key(&id) => continue,
+            Some(id) => format!("{} {}", template::finding_label(&display_ids, id), state.findings[&id].title)
+        };
 
-    // ---------------------------------------------------
+        let conflicts_row = conflicts_table.insert_row(row_index, "conflicts_row");
+        row_index += 1;
 
-    let header_severity_cell = header_row2.insert_cell(1, "header_severity_cell");
+        let base = merge::display_value(&conflict.base);
 
-    let mut header_severity_select = HtmlElement::new("select", "header_severity_select");
-    header_severity_select.set_attribute("id", format!("finding{}_severity", finding.id).as_str());
-    header_severity_select.set_attribute("onchange", format!("external.invoke('set_finding_{} {} \"' + this.value.toString().replaceAll('\"', '\\\\'\\\\'') + '\"')", "severity", finding.id).as_str());
+        for (cell_index, text) in [finding_text.as_str(), conflict.field.as_str(), base.as_str()].iter().enumerate() {
+            let conflicts_cell = conflicts_row.insert_cell(cell_index, "conflicts_cell");
+            conflicts_cell.set_attribute("style", "white-space: pre-wrap; vertical-align: top");
+            conflicts_cell.set_field("textContent", text);
+        }
 
-    let mut create_severity_option = |name, text| {
-        let mut option = HtmlElement::new("option", format!("finding{}_severity_{}_option", finding.id, name).as_str());
-        option.set_attribute("value", name);
-        option.set_inner_html(text);
+        let conflicts_candidates_cell = conflicts_row.insert_cell(3, "conflicts_candidates_cell");
 
-        if let Some(severity) = finding.severity {
-            match severity {
-                report::Severity::Critical => if name == "critical" { option.set_selected(true) },
-                report::Severity::Major => if name == "major" { option.set_selected(true) },
-                report::Severity::Minor => if name == "minor" { option.set_selected(true) },
-                report::Severity::Informational => if name == "informational" { option.set_selected(true) }
-            }
-        }
+        for (candidate_index, (name, value)) in conflict.candidates.iter().enumerate() {
+            let chosen = conflict.chosen == Some(candidate_index);
 
-        header_severity_select.append_child(option);
-    };
+            let mut conflicts_candidate_button = HtmlElement::new("button", "conflicts_candidate_button");
+            conflicts_candidate_button.set_attribute("onclick", format!("external.invoke(\"resolve_conflict {} {}\")", index, candidate_index).as_str());
+            conflicts_candidate_button.set_attribute("style", format!("display: block; width: 100%; text-align: left; white-space: pre-wrap; margin-bottom: 0.25rem{}", if chosen { "; background-color: palegreen" } else { "" }).as_str());
 
-    create_severity_option("none", "Select...");
-    create_severity_option("critical", "Critical");
-    create_severity_option("major", "Major");
-    create_severity_option("minor", "Minor");
-    create_severity_option("informational", "Informational");
+            let text = if conflict.field == merge::DELETED_FIELD {
+                format!("{}{}", if chosen { "\u{2713} " } else { "" }, name)
+            } else {
+                format!("{}{}:\n{}", if chosen { "\u{2713} " } else { "" }, name, merge::display_value(value))
+            };
 
-    header_severity_cell.append_child(header_severity_select);
-    
-    // ---------------------------------------------------
+            conflicts_candidate_button.set_field("textContent", text.as_str());
+            conflicts_candidates_cell.append_child(conflicts_candidate_button);
+        }
+    }
 
-    let header_location_cell = header_row2.insert_cell(2, "header_location_cell");
+    let mut merge_conflicts = HtmlElement::get("merge_conflicts");
+    merge_conflicts.set_inner_html(if row_index == 1 { "No merge conflicts" } else { "" });
 
-    let mut header_location_input = HtmlElement::new("input", "header_location_input");
-    header_location_input.set_attribute("type", "text");
-    header_location_input.set_attribute("value", finding.location.as_str());
-    header_location_input.set_attribute("id", format!("finding{}_location", finding.id).as_str());
-    header_location_input.set_attribute("onchange", format!("external.invoke('set_finding_{} {} \"' + this.value.toString().replaceAll('\"', '\\\\'\\\\'') + '\"')", "location", finding.id).as_str());
+    if row_index > 1 {
+        merge_conflicts.append_child(conflicts_table);
+    }
 
-    header_location_cell.append_child(header_location_input);
+    // Findings which were removed or renumbered by the merge are listed below the conflicts
+    if !state.merge_notes.is_empty() {
+        let mut merge_notes_list = HtmlElement::new("ul", "merge_notes_list");
+        merge_notes_list.set_attribute("style", "margin: 0.5rem");
 
-    //
-    // Done building the header table for the new finding
-    //
+        for note in &state.merge_notes {
+            let mut merge_note_item = HtmlElement::new("li", "merge_note_item");
+            merge_note_item.set_field("textContent", note);
+            merge_notes_list.append_child(merge_note_item);
+        }
 
-    new_cell.append_child(header_table);
+        merge_conflicts.append_child(merge_notes_list);
+    }
 
-    //
-    // Create the text areas for the new finding
-    //
+    merge_conflicts.build(view)
+}
 
-    let severity_style = match finding.severity {
-        Some(report::Severity::Critical) => "color: red",
-        Some(report::Severity::Major) => "color: orange",
-        Some(report::Severity::Minor) => "color: yellow",
-        Some(report::Severity::Informational) => "color: green",
-        None => "color: inherit",
+fn update_session_in_web_view<'a>(view: &mut web_view::WebView<'a, ()>, state: &StateData) -> web_view::WVResult {
+    let status = match state.session {
+        None => "Not in a session".to_string(),
+        Some(ref session) if session.is_host() => format!("Hosting on {} as {}", session.address, session.name),
+        Some(ref session) if session.client_id.is_none() => format!("Joining {} as {}...", session.address, session.name),
+        Some(ref session) => format!("Joined {} as {}", session.address, session.name)
     };
 
-    let mut create_text_area = |name, text, string| {
-        let mut header = HtmlElement::new("h4", format!("finding{}_{}_header", finding.id, name).as_str());
-        header.set_inner_html(text);
-        header.set_attribute("style", severity_style);
-        header.set_attribute("id", format!("finding{}_{}_header", finding.id, name).as_str());
-        new_cell.append_child(header);
-    
-        let mut textarea = HtmlElement::new("textarea", format!("finding{}_{}_textarea", finding.id, name).as_str());
-        textarea.set_inner_html(string);
-        textarea.set_attribute("rows", "4");
-        textarea.set_attribute("cols", "80");
-        textarea.set_attribute("maxlength", "9999");
-        textarea.set_field("style.resize", "vertical");
-        textarea.set_attribute("id", format!("finding{}_{}", finding.id, name).as_str());
-        textarea.set_attribute(
-            "onchange",
-            format!("external.invoke('set_finding_{} {} \"' + this.value.toString().replaceAll('\"', '\\\\'\\\\'') + '\"')", name, finding.id).as_str()
-        );
+    let mut session_status_text = HtmlElement::get("session_status_text");
+    session_status_text.set_field("textContent", status.as_str());
+    session_status_text.build(view)?;
 
-        new_cell.append_child(textarea);
-    };
+    let mut peers_table = HtmlElement::new("table", "peers_table");
+    peers_table.set_attribute("style", "margin: 0.5rem; width: 100%");
 
-    create_text_area("description", "Description:", finding.description.as_str());
-    create_text_area("recommendation", "Recommendation:", finding.recommendation.as_str());
-    //create_text_area("alleviation", "Alleviation:", finding.alleviation.as_str());
+    let peers_header_row = peers_table.insert_row(0, "peers_header_row");
 
-    new_cell.append_child(HtmlElement::new("p", "spacer"));
+    for (index, heading) in ["Name", "Role", "Editing"].iter().enumerate() {
+        let peers_header_cell = peers_header_row.insert_cell(index, "peers_header_cell");
+        peers_header_cell.set_attribute("style", "font-weight: bold");
+        peers_header_cell.set_field("textContent", heading);
+    }
 
-    //
-    // Done building new finding table
-    //
+    let peers = state.session.as_ref().map(|session| session.peers.as_slice()).unwrap_or(&[]);
 
-    let mut findings = HtmlElement::get("findings");
-    findings.append_child(new_table);
+    let display_ids = template::display_ids(state);
 
-    findings.build(view)?;
+    for (index, peer) in peers.iter().enumerate() {
+        let peers_row = peers_table.insert_row(index + 1, "peers_row");
 
-    // Jump to the new finding
-    view.eval(format!("window.location = '#finding{}'", finding.id).as_str()).unwrap();
+        let role = if peer.id == collab::HOST_ID { "Host" } else { "Guest" };
 
-    //
-    // Create the table of contents link
-    //
+        let editing = match peer.finding_id.and_then(|id| state.findings.get(&id)) {
+            None => "-".to_string(),
+            Some(finding) => format!("{} {}", template::finding_label(&display_ids, finding.id), finding.title)
+        };
 
-    let mut toc_findings = HtmlElement::get("toc_findings");
-    
-    let mut p = HtmlElement::new("p", "p");
-    p.set_attribute("id", format!("finding{}_link_p", finding.id).as_str());
+        for (cell_index, text) in [peer.name.as_str(), role, editing.as_str()].iter().enumerate() {
+            let peers_cell = peers_row.insert_cell(cell_index, "peers_cell");
+            peers_cell.set_field("textContent", text);
+        }
+    }
 
-    let mut link_id = HtmlElement::new("span", "link_id");
-    link_id.set_inner_html(format!("{}: ", finding.id).as_str());
-    p.append_child(link_id);
+    let mut session_peers = HtmlElement::get("session_peers");
+    session_peers.set_inner_html("");
 
-    let mut link = HtmlElement::new("a", "link");
-    link.set_attribute("style", severity_style);
-    link.set_attribute("id", format!("finding{}_link", finding.id).as_str());
-    link.set_attribute("href", format!("#finding{}", finding.id).as_str());
-    link.set_inner_html(finding.title.as_str());
-    p.append_child(link);
+    if !peers.is_empty() {
+        session_peers.append_child(peers_table);
+    }
 
-    toc_findings.append_child(p);
-    toc_findings.build(view)
+    session_peers.build(view)?;
+
+    // Show who else is editing each finding in its toolbar
+    for id in state.findings.keys() {
+        let editors = state.session.as_ref().map(|session| session.editors(*id)).unwrap_or_default();
+
+        let mut presence_label = HtmlElement::get(format!("finding{}_presence_label", id).as_str());
+        presence_label.set_field("textContent", if editors.is_empty() { String::new() } else { format!("{} editing", editors.join(", ")) }.as_str());
+        presence_label.build(view)?;
+    }
+
+    Ok(())
 }
 
 fn add_checklist_entry_to_web_view<'a>(view: &mut web_view::WebView<'a, ()>, id: usize, entry: (bool, &str)) -> web_view::WVResult {
@@ -1158,7 +4384,7 @@ fn add_checklist_entry_to_web_view<'a>(view: &mut web_view::WebView<'a, ()>, id:
     entry_text_input.set_attribute("type", "text");
     entry_text_input.set_attribute("id", format!("checklist{}_text_input", id).as_str());
     entry_text_input.set_attribute("style", "line-height: 1rem; margin: 0%; padding: 0%");
-    entry_text_input.set_attribute("onchange", format!("external.invoke('set_checklist_entry_text {} \"' + this.value.toString().replaceAll('\"', '\\\\'\\\\'') + '\"')", id).as_str());
+    entry_text_input.set_attribute("onchange", format!("external.invoke('set_checklist_entry_text {} ' + quote(this.value))", id).as_str());
     entry_text_input.set_value(entry.1);
 
     let mut entry_close_button = HtmlElement::new("button", "entry_close_button");
@@ -1186,3 +4412,61 @@ fn add_checklist_entry_to_web_view<'a>(view: &mut web_view::WebView<'a, ()>, id:
     toc_checklist.append_child(entry_table);
     toc_checklist.build(view)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_commands_are_dispatched() {
+        // Commands are parsed as the palette and shortcuts send them, with the parameters they ask for
+        for command in command::registry() {
+            let mut invocation = command.name.clone();
+
+            for parameter in &command.parameters {
+                invocation.push(' ');
+                invocation.push_str(match parameter.kind {
+                    command::DataKind::Int => "1".to_string(),
+                    command::DataKind::Float => "1.5".to_string(),
+                    command::DataKind::String => command::quote("client/module")
+                }.as_str());
+            }
+
+            assert!(parse_command(invocation.as_str()).is_ok(), "{} is not handled", invocation);
+        }
+    }
+
+    #[test]
+    fn session_commands_are_handled() {
+        for name in collab::SHARED_COMMANDS.iter().chain(collab::SNAPSHOT_COMMANDS.iter()) {
+            assert!(command_parser(name).is_some(), "{} is not handled", name);
+        }
+    }
+
+    #[test]
+    fn malformed_commands_are_rejected() {
+        assert!(parse_command("copy_finding").is_err());
+        assert!(parse_command("copy_finding -1").is_err());
+        assert!(parse_command("copy_finding 1 2").is_err());
+        assert!(parse_command("unknown_command").is_err());
+        assert!(parse_command("").is_err());
+
+        assert!(check_shared_command("set_finding_title 1 \"Reentrancy\"").is_ok());
+        assert!(check_shared_command("save_workbook").is_err());
+    }
+
+    #[test]
+    fn dashboard_commands_are_handled() {
+        for name in DASHBOARD_COMMANDS.iter() {
+            assert!(command_parser(name).is_some(), "{} is not handled", name);
+        }
+    }
+
+    #[test]
+    fn commands_changing_the_dashboard_refresh_it() {
+        // Commands which set the fields counted by the dashboard, including through find and replace
+        for name in ["set_finding_severity", "set_finding_status", "set_finding_type", "set_finding_location", "replace_in_workbook", "replace_in_field"].iter() {
+            assert!(DASHBOARD_COMMANDS.contains(name), "{} does not refresh the dashboard", name);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::highlight;
+
+use comrak::ComrakOptions;
+
+/// Returns the comrak options shared by the markdown importer and the rendered exports.
+pub fn options() -> ComrakOptions {
+    let mut options = ComrakOptions::default();
+    options.extension.autolink = true;
+    options.extension.description_lists = true;
+    options.extension.footnotes = true;
+    options.extension.strikethrough = true;
+    options.extension.superscript = true;
+    options.extension.table = true;
+    options.extension.tasklist = true;
+
+    // Reports embed their own markup (severity spans, page breaks and charts), so raw HTML is passed through
+    options.render.unsafe_ = true;
+
+    options
+}
+
+/// Renders markdown to an HTML fragment, with the code blocks highlighted by their language hint.
+pub fn render_html(md: &str) -> String {
+    let options = options();
+    let arena = comrak::Arena::new();
+    let root = comrak::parse_document(&arena, md, &options);
+
+    highlight::highlight_code_blocks(root);
+
+    let mut html = vec![];
+    comrak::format_html(root, &options, &mut html).unwrap();
+
+    String::from_utf8(html).unwrap_or_default()
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Renders markdown to plain text, keeping paragraph breaks and dropping all formatting.
+pub fn to_plain_text(md: &str) -> String {
+    use comrak::nodes::{AstNode, NodeValue};
+
+    fn collect<'a>(node: &'a AstNode<'a>, text: &mut String) {
+        match node.data.borrow().value {
+            NodeValue::Text(ref literal) | NodeValue::Code(ref literal) => {
+                text.push_str(std::str::from_utf8(literal).unwrap_or(""));
+                return;
+            }
+
+            NodeValue::CodeBlock(ref code_block) => {
+                text.push_str(std::str::from_utf8(&code_block.literal).unwrap_or(""));
+                return;
+            }
+
+            NodeValue::SoftBreak => {
+                text.push(' ');
+                return;
+            }
+
+            NodeValue::LineBreak => {
+                text.push('\n');
+                return;
+            }
+
+            NodeValue::HtmlBlock(_) | NodeValue::HtmlInline(_) => return,
+
+            _ => {}
+        }
+
+        for child in node.children() {
+            collect(child, text);
+        }
+
+        match node.data.borrow().value {
+            NodeValue::Paragraph | NodeValue::Heading(_) | NodeValue::Item(_) | NodeValue::TableRow(_) => {
+                if !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+
+            NodeValue::TableCell => text.push('\t'),
+
+            _ => {}
+        }
+    }
+
+    let arena = comrak::Arena::new();
+    let root = comrak::parse_document(&arena, md, &options());
+
+    let mut text = String::new();
+    collect(root, &mut text);
+
+    text.trim().to_string()
+}
@@ -0,0 +1,564 @@
+use crate::{
+    reference,
+    report::{Finding, Report}
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use std::{collections::HashMap, path::Path};
+
+/// The finding fields which are merged individually. Other fields are taken from the first
+/// workbook containing the finding.
+pub const FINDING_FIELDS: [&str; 17] = [
+    "title", "class", "severity", "severity_overridden", "likelihood", "impact", "justification",
+    "status", "location", "locations", "description", "recommendation", "alleviation", "reaudit", "attachments",
+    "tags", "custom_fields"
+];
+
+/// The report fields which are merged individually. Local settings such as the checkout path
+/// are taken from the first workbook.
+pub const REPORT_FIELDS: [&str; 9] = [
+    "title", "start_time", "delivery_time", "repository", "overview", "severity_matrix", "finding_order", "lint_rules",
+    "custom_fields"
+];
+
+/// The pseudo field of a conflict between deleting a finding in one workbook and editing it in another.
+pub const DELETED_FIELD: &str = "deleted";
+
+/// A workbook taking part in a merge, named after its file for conflict labels and notes.
+///
+/// Workbooks are identified by their index in the merge, since their names need not be unique.
+#[derive(Clone, Debug)]
+pub struct Workbook {
+    pub name: String,
+    pub report: Report
+}
+
+/// Names the workbooks of a merge after their files, adding the parent directories of files with
+/// the same name until the names are unique, so `alice/workbook` and `bob/workbook` can be told apart.
+pub fn workbook_names(paths: &[&Path]) -> Vec<String> {
+    let name = |path: &Path, depth: usize| -> String {
+        let mut parts: Vec<String> = vec![path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default()];
+
+        for ancestor in path.ancestors().skip(1).take(depth) {
+            if let Some(ancestor) = ancestor.file_name() {
+                parts.insert(0, ancestor.to_string_lossy().to_string());
+            }
+        }
+
+        parts.join("/")
+    };
+
+    let mut depths = vec![0; paths.len()];
+
+    loop {
+        let names: Vec<String> = paths.iter().zip(&depths).map(|(path, depth)| name(path, *depth)).collect();
+        let mut changed = false;
+
+        for (index, path) in paths.iter().enumerate() {
+            let duplicate = names.iter().enumerate().any(|(other, other_name)| other != index && *other_name == names[index]);
+
+            if duplicate && depths[index] < path.ancestors().count() {
+                depths[index] += 1;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return names;
+        }
+    }
+}
+
+/// A field which was changed to different values in more than one workbook.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Conflict {
+    /// The merged id of the finding the field belongs to, or `None` for a report field.
+    pub finding_id: Option<usize>,
+    pub field: String,
+    pub base: Value,
+    pub candidates: Vec<(String, Value)>,
+    pub chosen: Option<usize>
+}
+
+#[derive(Clone, Debug)]
+pub struct MergeResult {
+    pub report: Report,
+    pub conflicts: Vec<Conflict>,
+    pub notes: Vec<String>
+}
+
+fn to_map<T: Serialize>(value: &T) -> Map<String, Value> {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new()
+    }
+}
+
+fn from_map<T: DeserializeOwned>(map: Map<String, Value>) -> Result<T, String> {
+    serde_json::from_value(Value::Object(map)).map_err(|error| format!("Failed to apply merged fields: {}", error))
+}
+
+/// Merges the values of a field, returning the distinct changes from the base if they conflict.
+fn merge_value(base: &Value, sides: &[(String, Value)]) -> Result<Value, Vec<(String, Value)>> {
+    let mut changes: Vec<(String, Value)> = vec![];
+
+    for (name, value) in sides {
+        if value != base && !changes.iter().any(|(_, existing)| existing == value) {
+            changes.push((name.clone(), value.clone()));
+        }
+    }
+
+    match changes.len() {
+        0 => Ok(base.clone()),
+        1 => Ok(changes.remove(0).1),
+        _ => Err(changes)
+    }
+}
+
+fn merge_fields(
+    base: &Map<String, Value>,
+    sides: &[(String, Map<String, Value>)],
+    fields: &[&str],
+    finding_id: Option<usize>,
+    merged: &mut Map<String, Value>,
+    conflicts: &mut Vec<Conflict>
+) {
+    for field in fields {
+        let base_value = base.get(*field).cloned().unwrap_or(Value::Null);
+
+        let side_values: Vec<(String, Value)> = sides.iter()
+            .map(|(name, map)| (name.clone(), map.get(*field).cloned().unwrap_or(Value::Null)))
+            .collect();
+
+        match merge_value(&base_value, &side_values) {
+            Ok(value) => {
+                merged.insert(field.to_string(), value);
+            }
+
+            Err(candidates) => {
+                // The first change is used until the conflict is resolved
+                merged.insert(field.to_string(), candidates[0].1.clone());
+
+                conflicts.push(Conflict {
+                    finding_id,
+                    field: field.to_string(),
+                    base: base_value,
+                    candidates,
+                    chosen: None
+                });
+            }
+        }
+    }
+}
+
+fn same_title(lhs: &Finding, rhs: &Finding) -> bool {
+    !lhs.title.trim().is_empty() && lhs.title.trim().eq_ignore_ascii_case(rhs.title.trim())
+}
+
+fn same_location(lhs: &Finding, rhs: &Finding) -> bool {
+    !lhs.location.trim().is_empty() && lhs.location.trim() == rhs.location.trim()
+}
+
+/// Finds the finding matching a base finding, first by id, then by title and then by location.
+/// Ids only match when the title or location also agree, since auditors number their new
+/// findings independently.
+fn find_match<'a>(findings: &'a [Finding], finding: &Finding, taken: &[usize]) -> Option<&'a Finding> {
+    let candidates = || findings.iter().filter(|candidate| !taken.contains(&candidate.id));
+
+    candidates().find(|candidate| candidate.id == finding.id && (same_title(candidate, finding) || same_location(candidate, finding)))
+        .or_else(|| candidates().find(|candidate| same_title(candidate, finding)))
+        .or_else(|| candidates().find(|candidate| same_location(candidate, finding)))
+}
+
+/// Returns true if two findings added independently are the same finding.
+fn same_new_finding(lhs: &Finding, rhs: &Finding) -> bool {
+    lhs.fingerprints.iter().any(|fingerprint| rhs.fingerprints.contains(fingerprint))
+        || (same_title(lhs, rhs) && same_location(lhs, rhs))
+}
+
+fn is_modified(base: &Finding, finding: &Finding) -> bool {
+    let (base, finding) = (to_map(base), to_map(finding));
+    FINDING_FIELDS.iter().any(|field| base.get(*field) != finding.get(*field))
+}
+
+fn union<T: Clone, K: PartialEq>(lists: Vec<&Vec<T>>, key: impl Fn(&T) -> K) -> Vec<T> {
+    let mut result: Vec<T> = vec![];
+
+    for list in lists {
+        for item in list {
+            if !result.iter().any(|existing| key(existing) == key(item)) {
+                result.push(item.clone());
+            }
+        }
+    }
+
+    result
+}
+
+/// Merges the checklists by entry text. Entries removed in any workbook are removed, and an
+/// entry is checked if any workbook changed it to be checked.
+fn merge_checklists(base: &[(bool, String)], workbooks: &[Workbook]) -> Vec<(bool, String)> {
+    let mut checklist = vec![];
+
+    for (checked, text) in base {
+        let sides: Vec<Option<bool>> = workbooks.iter()
+            .map(|workbook| workbook.report.checklist.iter().find(|entry| &entry.1 == text).map(|entry| entry.0))
+            .collect();
+
+        if sides.iter().any(Option::is_none) {
+            continue;
+        }
+
+        let checked = sides.iter().flatten().find(|side| *side != checked).copied().unwrap_or(*checked);
+        checklist.push((checked, text.clone()));
+    }
+
+    for workbook in workbooks {
+        for entry in &workbook.report.checklist {
+            if !base.iter().any(|base_entry| base_entry.1 == entry.1) && !checklist.iter().any(|existing: &(bool, String)| existing.1 == entry.1) {
+                checklist.push(entry.clone());
+            }
+        }
+    }
+
+    checklist
+}
+
+/// Merges two or more workbooks which were each edited from a common base workbook.
+pub fn merge(base: &Report, workbooks: &[Workbook]) -> Result<MergeResult, String> {
+    if workbooks.len() < 2 {
+        return Err("At least two workbooks are needed for a merge".to_string());
+    }
+
+    let (result, renumbered) = merge_once(base, workbooks)?;
+
+    if renumbered.iter().all(HashMap::is_empty) {
+        return Ok(result);
+    }
+
+    // Findings are matched without looking at their text, so after rewriting the references to
+    // renumbered findings in each workbook, merging again gives the same ids
+    let workbooks: Vec<Workbook> = workbooks.iter()
+        .zip(&renumbered)
+        .map(|(workbook, ids)| {
+            let mut workbook = workbook.clone();
+
+            for finding in workbook.report.findings.iter_mut() {
+                reference::renumber_finding(finding, ids);
+            }
+
+            workbook
+        })
+        .collect();
+
+    Ok(merge_once(base, &workbooks)?.0)
+}
+
+/// Merges the workbooks, also returning the new ids given to each workbook's added findings.
+fn merge_once(base: &Report, workbooks: &[Workbook]) -> Result<(MergeResult, Vec<HashMap<usize, usize>>), String> {
+    let mut renumbered = vec![HashMap::new(); workbooks.len()];
+
+    let mut conflicts = vec![];
+    let mut notes = vec![];
+
+    //
+    // Merge the report fields
+    //
+
+    let mut report_map = to_map(&workbooks[0].report);
+
+    let report_sides: Vec<(String, Map<String, Value>)> = workbooks.iter()
+        .map(|workbook| (workbook.name.clone(), to_map(&workbook.report)))
+        .collect();
+
+    merge_fields(&to_map(base), &report_sides, &REPORT_FIELDS, None, &mut report_map, &mut conflicts);
+
+    let mut report: Report = from_map(report_map)?;
+
+    report.auditors = union(workbooks.iter().map(|workbook| &workbook.report.auditors).collect(), |auditor| auditor.email.clone());
+    report.commit_hashes = union(workbooks.iter().map(|workbook| &workbook.report.commit_hashes).collect(), |hash| hash.clone());
+    report.baseline = union(workbooks.iter().map(|workbook| &workbook.report.baseline).collect(), |suppression| suppression.fingerprint.clone());
+    report.checklist = merge_checklists(&base.checklist, workbooks);
+
+    //
+    // Merge the findings of the base workbook
+    //
+
+    let mut taken: Vec<Vec<usize>> = vec![vec![]; workbooks.len()];
+    let mut findings: Vec<Finding> = vec![];
+
+    let mut base_findings = base.findings.clone();
+    base_findings.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
+
+    for base_finding in &base_findings {
+        let mut present: Vec<(String, Finding)> = vec![];
+        let mut deleted_in: Vec<String> = vec![];
+
+        for (index, workbook) in workbooks.iter().enumerate() {
+            match find_match(&workbook.report.findings, base_finding, &taken[index]) {
+                Some(finding) => {
+                    taken[index].push(finding.id);
+                    present.push((workbook.name.clone(), finding.clone()));
+                }
+
+                None => deleted_in.push(workbook.name.clone())
+            }
+        }
+
+        if present.is_empty() || (!deleted_in.is_empty() && !present.iter().any(|(_, finding)| is_modified(base_finding, finding))) {
+            notes.push(format!("Removed finding {} ({}), which was deleted in {}", base_finding.id, base_finding.title, deleted_in.join(", ")));
+            continue;
+        }
+
+        let sides: Vec<(String, Map<String, Value>)> = present.iter().map(|(name, finding)| (name.clone(), to_map(finding))).collect();
+
+        let mut merged = to_map(&present[0].1);
+        merge_fields(&to_map(base_finding), &sides, &FINDING_FIELDS, Some(base_finding.id), &mut merged, &mut conflicts);
+
+        let mut finding: Finding = from_map(merged)?;
+        finding.id = base_finding.id;
+        finding.fingerprints = union(present.iter().map(|(_, finding)| &finding.fingerprints).collect(), |fingerprint| fingerprint.clone());
+
+        if !deleted_in.is_empty() {
+            // Edits win over deletions until the conflict is resolved
+            let mut candidates = vec![(format!("Kept and edited in {}", present.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", ")), Value::Bool(false))];
+            candidates.push((format!("Deleted in {}", deleted_in.join(", ")), Value::Bool(true)));
+
+            conflicts.push(Conflict {
+                finding_id: Some(finding.id),
+                field: DELETED_FIELD.to_string(),
+                base: Value::Bool(false),
+                candidates,
+                chosen: None
+            });
+        }
+
+        findings.push(finding);
+    }
+
+    //
+    // Merge the findings added in each workbook, grouping ones added by more than one auditor
+    //
+
+    let mut groups: Vec<Vec<(usize, Finding)>> = vec![];
+
+    for (index, workbook) in workbooks.iter().enumerate() {
+        for finding in workbook.report.findings.iter().filter(|finding| !taken[index].contains(&finding.id)) {
+            match groups.iter_mut().find(|group| !group.iter().any(|(other, _)| *other == index) && same_new_finding(&group[0].1, finding)) {
+                Some(group) => group.push((index, finding.clone())),
+                None => groups.push(vec![(index, finding.clone())])
+            }
+        }
+    }
+
+    for group in groups {
+        let (first_index, first) = &group[0];
+
+        // Keep the id the auditor gave the finding unless another finding already uses it
+        let id = if findings.iter().any(|finding| finding.id == first.id) {
+            let id = findings.iter().map(|finding| finding.id).max().unwrap_or(0) + 1;
+            notes.push(format!("Renumbered finding {} ({}) from {} to {}", first.id, first.title, workbooks[*first_index].name, id));
+            id
+        } else {
+            first.id
+        };
+
+        let sides: Vec<(String, Map<String, Value>)> = group.iter().map(|(index, finding)| (workbooks[*index].name.clone(), to_map(finding))).collect();
+
+        // Findings added independently have no base, so every field which differs between them conflicts
+        let mut merged = to_map(first);
+        merge_fields(&Map::new(), &sides, &FINDING_FIELDS, Some(id), &mut merged, &mut conflicts);
+
+        let mut finding: Finding = from_map(merged)?;
+        finding.id = id;
+        finding.fingerprints = union(group.iter().map(|(_, finding)| &finding.fingerprints).collect(), |fingerprint| fingerprint.clone());
+
+        for (index, added) in group.iter().filter(|(_, added)| added.id != id) {
+            renumbered[*index].insert(added.id, id);
+        }
+
+        findings.push(finding);
+    }
+
+    findings.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
+    report.findings = findings;
+
+    Ok((MergeResult { report, conflicts, notes }, renumbered))
+}
+
+/// Applies the chosen candidate of a conflict to the merged report.
+pub fn resolve(report: &mut Report, conflict: &Conflict, candidate: usize) -> Result<(), String> {
+    let value = match conflict.candidates.get(candidate) {
+        None => return Err(format!("Invalid candidate {} for the conflict on '{}'", candidate, conflict.field)),
+        Some((_, value)) => value.clone()
+    };
+
+    match conflict.finding_id {
+        None => {
+            let mut map = to_map(report);
+            map.insert(conflict.field.clone(), value);
+            *report = from_map(map)?;
+        }
+
+        Some(id) if conflict.field == DELETED_FIELD => {
+            if value == Value::Bool(true) {
+                report.findings.retain(|finding| finding.id != id);
+            }
+        }
+
+        Some(id) => {
+            let finding = match report.findings.iter_mut().find(|finding| finding.id == id) {
+                None => return Err(format!("No finding for id {} was found!", id)),
+                Some(finding) => finding
+            };
+
+            let mut map = to_map(finding);
+            map.insert(conflict.field.clone(), value);
+            *finding = from_map(map)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats a conflicting value for display.
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "(none)".to_string(),
+        Value::String(string) if string.is_empty() => "(empty)".to_string(),
+        Value::String(string) => string.clone(),
+        value => value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(findings: Vec<Finding>) -> Report {
+        let mut report: Report = serde_json::from_value(serde_json::json!({
+            "title": "Audit",
+            "auditors": [],
+            "start_time": "",
+            "delivery_time": "",
+            "repository": "",
+            "commit_hashes": [],
+            "checklist": [],
+            "overview": "",
+            "findings": []
+        })).unwrap();
+
+        report.findings = findings;
+        report
+    }
+
+    fn finding(id: usize, title: &str, location: &str) -> Finding {
+        let mut finding = Finding::new(id);
+        finding.title = title.to_string();
+        finding.location = location.to_string();
+        finding
+    }
+
+    fn workbook(name: &str, findings: Vec<Finding>) -> Workbook {
+        Workbook { name: name.to_string(), report: report(findings) }
+    }
+
+    #[test]
+    fn merge_detects_conflicting_edits() {
+        let base = report(vec![finding(1, "Reentrancy", "Vault.sol")]);
+
+        let mut alice = finding(1, "Reentrancy", "Vault.sol");
+        alice.description = "Calls back into withdraw".to_string();
+
+        let mut bob = finding(1, "Reentrancy", "Vault.sol");
+        bob.description = "State is updated after the call".to_string();
+        bob.recommendation = "Use checks-effects-interactions".to_string();
+
+        let result = merge(&base, &[workbook("alice/workbook", vec![alice]), workbook("bob/workbook", vec![bob])]).unwrap();
+
+        assert_eq!(result.report.findings.len(), 1);
+        assert_eq!(result.report.findings[0].recommendation, "Use checks-effects-interactions");
+
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].finding_id, Some(1));
+        assert_eq!(result.conflicts[0].field, "description");
+        assert_eq!(
+            result.conflicts[0].candidates.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(),
+            vec!["alice/workbook", "bob/workbook"]
+        );
+    }
+
+    #[test]
+    fn merge_groups_findings_added_in_both_workbooks() {
+        let base = report(vec![]);
+
+        let alice = vec![finding(1, "Missing zero check", "Token.sol")];
+        let bob = vec![finding(1, "Missing Zero Check", "Token.sol"), finding(2, "Unused import", "Vault.sol")];
+
+        let result = merge(&base, &[workbook("workbook", alice), workbook("workbook", bob)]).unwrap();
+
+        let titles: Vec<(usize, &str)> = result.report.findings.iter().map(|finding| (finding.id, finding.title.as_str())).collect();
+        assert_eq!(titles, vec![(1, "Missing zero check"), (2, "Unused import")]);
+
+        // The titles only differ in case, which is a conflict between the two additions
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].field, "title");
+    }
+
+    #[test]
+    fn merge_keeps_edited_findings_deleted_elsewhere() {
+        let base = report(vec![finding(1, "Reentrancy", "Vault.sol"), finding(2, "Gas", "Token.sol")]);
+
+        let mut edited = finding(1, "Reentrancy", "Vault.sol");
+        edited.alleviation = "Fixed in a later commit".to_string();
+
+        let alice = vec![edited, finding(2, "Gas", "Token.sol")];
+        let bob = vec![];
+
+        let result = merge(&base, &[workbook("alice", alice), workbook("bob", bob)]).unwrap();
+
+        assert_eq!(result.report.findings.iter().map(|finding| finding.id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(result.notes, vec!["Removed finding 2 (Gas), which was deleted in bob".to_string()]);
+
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].field, DELETED_FIELD);
+        assert_eq!(result.conflicts[0].candidates[0].0, "Kept and edited in alice");
+        assert_eq!(result.conflicts[0].candidates[1].0, "Deleted in bob");
+
+        let mut report = result.report.clone();
+        resolve(&mut report, &result.conflicts[0], 1).unwrap();
+        assert!(report.findings.is_empty());
+    }
+
+    #[test]
+    fn merge_renumbers_findings_and_their_references() {
+        let base = report(vec![finding(1, "Reentrancy", "Vault.sol")]);
+
+        let alice = vec![finding(1, "Reentrancy", "Vault.sol"), finding(2, "Overflow", "Math.sol")];
+
+        let mut referencing = finding(3, "Unchecked call", "Vault.sol L40");
+        referencing.description = "Related to finding:2 and finding:1".to_string();
+
+        // Both workbooks have the same file name, so only their position tells them apart
+        let bob = vec![finding(1, "Reentrancy", "Vault.sol"), finding(2, "Front-running", "Auction.sol"), referencing];
+
+        let result = merge(&base, &[workbook("workbook", alice), workbook("workbook", bob)]).unwrap();
+
+        let titles: Vec<(usize, &str)> = result.report.findings.iter().map(|finding| (finding.id, finding.title.as_str())).collect();
+        assert_eq!(titles, vec![(1, "Reentrancy"), (2, "Overflow"), (3, "Front-running"), (4, "Unchecked call")]);
+
+        assert_eq!(result.report.findings[3].description, "Related to finding:3 and finding:1");
+        assert!(result.conflicts.is_empty());
+    }
+
+    #[test]
+    fn workbook_names_are_disambiguated_by_directory() {
+        let paths = [Path::new("/audits/alice/workbook.json"), Path::new("/audits/bob/workbook.json"), Path::new("/audits/carol.json")];
+        assert_eq!(workbook_names(&paths), vec!["alice/workbook", "bob/workbook", "carol"]);
+
+        let paths = [Path::new("/a/shared/workbook.json"), Path::new("/b/shared/workbook.json")];
+        assert_eq!(workbook_names(&paths), vec!["a/shared/workbook", "b/shared/workbook"]);
+    }
+}
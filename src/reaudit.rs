@@ -0,0 +1,197 @@
+use crate::{
+    git,
+    report::{Finding, Reaudit},
+    summary
+};
+
+use std::collections::HashMap;
+
+/// Marks the line of the alleviation which records the re-audit verdict, so it can be replaced.
+pub const VERDICT_PREFIX: &str = "**Re-audit:**";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodeChange {
+    Changed,
+    Unchanged,
+    Unknown
+}
+
+impl CodeChange {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CodeChange::Changed => "Changed",
+            CodeChange::Unchanged => "Unchanged",
+            CodeChange::Unknown => "No locations"
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocationChange {
+    pub text: String,
+    pub changed: bool
+}
+
+/// Whether the code at each location of a finding changed between the initial and fix commits.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FindingChange {
+    pub id: usize,
+    pub locations: Vec<LocationChange>
+}
+
+impl FindingChange {
+    pub fn change(&self) -> CodeChange {
+        if self.locations.is_empty() {
+            CodeChange::Unknown
+        } else if self.locations.iter().any(|location| location.changed) {
+            CodeChange::Changed
+        } else {
+            CodeChange::Unchanged
+        }
+    }
+}
+
+fn normalize_path(file: &str) -> &str {
+    file.trim().trim_start_matches("./").trim_start_matches('/')
+}
+
+/// Compares the code at the locations of each finding between two commits of the checkout. Locations
+/// pinned to another commit, such as remapped ones, are compared from that commit instead.
+pub fn compare(path: &str, from: &str, to: &str, findings: &[Finding]) -> Result<Vec<FindingChange>, String> {
+    let mut changed_files: HashMap<String, Vec<git::FileChange>> = HashMap::new();
+    changed_files.insert(from.to_string(), git::changed_files(path, from, to)?);
+
+    let mut changed_lines: HashMap<(String, String), Vec<(usize, usize)>> = HashMap::new();
+    let mut changes = vec![];
+
+    for finding in findings {
+        let mut locations = vec![];
+
+        if finding.locations.is_empty() {
+            // Without line information only whether the file changed at all is known
+            for file in summary::location_files(finding.location.as_str()) {
+                locations.push(LocationChange {
+                    changed: changed_files[from].iter().any(|change| change.old_file == normalize_path(file.as_str()) && change.modified),
+                    text: file
+                });
+            }
+        }
+
+        for location in &finding.locations {
+            let file = normalize_path(location.file.as_str());
+            let base = location.commit.as_deref().unwrap_or(from);
+
+            if !changed_files.contains_key(base) {
+                changed_files.insert(base.to_string(), git::changed_files(path, base, to)?);
+            }
+
+            let changed = match changed_files[base].iter().find(|change| change.old_file == file) {
+                Some(change) if change.modified => match change.new_file {
+                    // Deleted files changed every line
+                    None => true,
+                    Some(_) if location.lines.is_empty() => true,
+
+                    Some(ref new_file) => {
+                        let key = (base.to_string(), file.to_string());
+
+                        if !changed_lines.contains_key(&key) {
+                            changed_lines.insert(key.clone(), git::changed_lines(path, base, file, to, new_file.as_str())?);
+                        }
+
+                        location.ranges().iter().any(|(start, end)| {
+                            changed_lines[&key].iter().any(|(changed_start, changed_end)| changed_start <= end && start <= changed_end)
+                        })
+                    }
+                },
+
+                // Files which were only renamed keep their lines
+                _ => false
+            };
+
+            locations.push(LocationChange { text: location.to_string(), changed });
+        }
+
+        changes.push(FindingChange { id: finding.id, locations });
+    }
+
+    Ok(changes)
+}
+
+/// Replaces the re-audit verdict recorded in an alleviation, keeping the rest of its text.
+pub fn record_verdict(alleviation: &str, reaudit: &Reaudit) -> String {
+    let mut alleviation = alleviation.lines()
+        .filter(|line| !line.starts_with(VERDICT_PREFIX))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+
+    if !alleviation.is_empty() {
+        alleviation.push_str("\n\n");
+    }
+
+    alleviation.push_str(
+        format!(
+            "{} {} as of commit `{}`.",
+            VERDICT_PREFIX,
+            reaudit.verdict.name(),
+            reaudit.fix_commit.chars().take(10).collect::<String>()
+        ).as_str()
+    );
+
+    alleviation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::report::{Location, Verdict};
+
+    fn commit(path: &str, contents: &str) -> String {
+        std::fs::write(format!("{}/Vault.sol", path), contents).unwrap();
+        git::run(path, &["add", "Vault.sol"]).unwrap();
+        git::run(path, &["-c", "user.name=Auditor", "-c", "user.email=auditor@example.com", "commit", "-q", "--allow-empty", "-m", "Change vault"]).unwrap();
+        git::head(path).unwrap()
+    }
+
+    #[test]
+    fn compare_starts_from_the_commit_of_each_location() {
+        let path = std::env::temp_dir().join(format!("workbook_reaudit_compare_{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+        let path = path.to_str().unwrap();
+        git::run(path, &["init", "-q"]).unwrap();
+
+        let audited = commit(path, "a\nb\nc\n");
+        let remapped = commit(path, "A\nb\nc\n");
+        let fixed = commit(path, "A\nb\nc\n");
+
+        let mut finding = Finding::new(1);
+        finding.locations = vec![Location::new("Vault.sol".to_string(), vec![1]), Location::new("Vault.sol".to_string(), vec![1])];
+        finding.locations[1].commit = Some(remapped);
+
+        let changes = compare(path, audited.as_str(), fixed.as_str(), &[finding]).unwrap();
+        let changed: Vec<bool> = changes[0].locations.iter().map(|location| location.changed).collect();
+
+        // The first line changed since the audit, but not since the location was remapped
+        assert_eq!(changed, vec![true, false]);
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn record_verdict_replaces_the_previous_verdict() {
+        let resolved = Reaudit { verdict: Verdict::Resolved, fix_commit: "0123456789abcdef".to_string() };
+        let alleviation = record_verdict("Fixed in the vault.\n\n**Re-audit:** Unresolved as of commit `fedcba9876`.", &resolved);
+
+        assert_eq!(alleviation, "Fixed in the vault.\n\n**Re-audit:** Resolved as of commit `0123456789`.");
+    }
+
+    #[test]
+    fn record_verdict_shortens_commits_by_character() {
+        // Workbooks saved before fix commits were resolved may hold any text
+        let reaudit = Reaudit { verdict: Verdict::Resolved, fix_commit: "fix/überprüfung".to_string() };
+
+        assert_eq!(record_verdict("", &reaudit), "**Re-audit:** Resolved as of commit `fix/überpr`.");
+    }
+}
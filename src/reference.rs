@@ -0,0 +1,200 @@
+use crate::report::Finding;
+
+use std::collections::HashMap;
+
+/// Refers to another finding from markdown, either bare as `finding:4` or as a link target such
+/// as `[the withdrawal issue](finding:4)`. References use the stable finding id and are resolved
+/// to the current display id and title when rendered.
+pub const FINDING_SCHEME: &str = "finding:";
+
+/// The finding a reference resolves to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Target {
+    pub display_id: String,
+    pub title: String
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Reference {
+    /// The byte range of `finding:<id>` in the text.
+    start: usize,
+    end: usize,
+    id: usize,
+    /// Whether the reference is the target of a markdown link rather than bare text.
+    linked: bool
+}
+
+fn find_references(text: &str) -> Vec<Reference> {
+    let mut references = vec![];
+    let mut offset = 0;
+
+    while let Some(index) = text[offset..].find(FINDING_SCHEME) {
+        let start = offset + index;
+        let digits_start = start + FINDING_SCHEME.len();
+        let digits_end = digits_start + text[digits_start..].chars().take_while(|c| c.is_ascii_digit()).count();
+
+        offset = digits_end;
+
+        // Skip words ending in `finding:`, such as `subfinding:2`
+        if text[..start].chars().next_back().map_or(false, |c| c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+
+        if let Ok(id) = text[digits_start..digits_end].parse() {
+            references.push(Reference {
+                start,
+                end: digits_end,
+                id,
+                linked: text[..start].ends_with("](")
+            });
+        }
+    }
+
+    references
+}
+
+/// Returns the ids of the findings referenced in a text, in order of appearance.
+pub fn referenced_ids(text: &str) -> Vec<usize> {
+    find_references(text).iter().map(|reference| reference.id).collect()
+}
+
+/// Escapes the characters which would end the text of a markdown link.
+fn escape_link_text(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('[', "\\[").replace(']', "\\]")
+}
+
+/// Replaces references in a markdown text with links to their targets.
+///
+/// Bare references become links showing the target's display id and title, while link targets are
+/// only replaced with the URL. References to findings which do not exist are marked as missing.
+pub fn resolve(text: &str, find: impl Fn(usize) -> Option<Target>, url: impl Fn(usize, &Target) -> String) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut offset = 0;
+
+    for reference in find_references(text) {
+        result.push_str(&text[offset..reference.start]);
+        offset = reference.end;
+
+        match find(reference.id) {
+            Some(ref target) if reference.linked => result.push_str(url(reference.id, target).as_str()),
+            Some(ref target) => result.push_str(format!("[{}: {}]({})", target.display_id, escape_link_text(target.title.as_str()), url(reference.id, target)).as_str()),
+
+            // Dangling link targets are left as they are, since the link text still reads correctly
+            None if reference.linked => result.push_str(&text[reference.start..reference.end]),
+            None => result.push_str(format!("*missing finding {}*", reference.id).as_str())
+        }
+    }
+
+    result.push_str(&text[offset..]);
+    result
+}
+
+/// Rewrites the references in a text to findings which were given new ids.
+pub fn renumber(text: &str, ids: &HashMap<usize, usize>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut offset = 0;
+
+    for reference in find_references(text) {
+        if let Some(id) = ids.get(&reference.id) {
+            result.push_str(&text[offset..reference.start]);
+            result.push_str(format!("{}{}", FINDING_SCHEME, id).as_str());
+            offset = reference.end;
+        }
+    }
+
+    result.push_str(&text[offset..]);
+    result
+}
+
+/// Rewrites the references in all of a finding's markdown fields to findings which were given new ids.
+pub fn renumber_finding(finding: &mut Finding, ids: &HashMap<usize, usize>) {
+    for text in [&mut finding.justification, &mut finding.description, &mut finding.recommendation, &mut finding.alleviation].iter_mut() {
+        **text = renumber(text.as_str(), ids);
+    }
+}
+
+/// Returns the ids referenced from a finding's markdown fields.
+pub fn finding_references(finding: &Finding) -> Vec<usize> {
+    [&finding.justification, &finding.description, &finding.recommendation, &finding.alleviation].iter()
+        .flat_map(|text| referenced_ids(text.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{report::{FindingOrder, Severity}, template, StateData};
+
+    fn target(display_id: &str, title: &str) -> Target {
+        Target { display_id: display_id.to_string(), title: title.to_string() }
+    }
+
+    fn resolve_with(text: &str, targets: &HashMap<usize, Target>) -> String {
+        resolve(text, |id| targets.get(&id).cloned(), |_, target| format!("#{}", target.display_id))
+    }
+
+    #[test]
+    fn finds_references() {
+        assert_eq!(referenced_ids("See finding:4, [the fee issue](finding:12) and finding:4."), vec![4, 12, 4]);
+
+        // Words ending in the scheme and references without an id are not references
+        assert!(referenced_ids("subfinding:2 my_finding:3 finding: findings:5").is_empty());
+    }
+
+    #[test]
+    fn resolves_references() {
+        let mut targets = HashMap::new();
+        targets.insert(4, target("XXX-01", "Reentrancy in [withdraw]"));
+
+        assert_eq!(
+            resolve_with("See finding:4.", &targets),
+            "See [XXX-01: Reentrancy in \\[withdraw\\]](#XXX-01)."
+        );
+
+        assert_eq!(resolve_with("As [above](finding:4)", &targets), "As [above](#XXX-01)");
+        assert_eq!(resolve_with("See finding:7", &targets), "See *missing finding 7*");
+        assert_eq!(resolve_with("As [before](finding:7)", &targets), "As [before](finding:7)");
+    }
+
+    #[test]
+    fn resolves_to_display_order() {
+        let mut state = StateData::new();
+
+        for (id, title, severity, draft) in [(1, "Rounding", Severity::Minor, false), (2, "Reentrancy", Severity::Critical, false), (3, "Gas", Severity::Major, true)].iter() {
+            let mut finding = Finding::new(*id);
+            finding.title = title.to_string();
+            finding.severity = Some(*severity);
+            finding.draft = *draft;
+            state.findings.insert(*id, finding);
+        }
+
+        let text = "finding:1, finding:2 and finding:3";
+
+        state.finding_order = FindingOrder::Severity;
+        assert_eq!(
+            resolve_with(text, &template::reference_targets(&state)),
+            "[XXX-02: Rounding](#XXX-02), [XXX-01: Reentrancy](#XXX-01) and *missing finding 3*"
+        );
+
+        // Reordering or accepting findings renumbers the references to them
+        state.finding_order = FindingOrder::Creation;
+        state.findings.get_mut(&3).unwrap().draft = false;
+        assert_eq!(
+            resolve_with(text, &template::reference_targets(&state)),
+            "[XXX-01: Rounding](#XXX-01), [XXX-02: Reentrancy](#XXX-02) and [XXX-03: Gas](#XXX-03)"
+        );
+
+        // Removing a finding renumbers the ones after it
+        state.findings.remove(&1);
+        assert_eq!(template::finding_label(&template::display_ids(&state), 3), "XXX-02");
+    }
+
+    #[test]
+    fn renumbers_references() {
+        let mut ids = HashMap::new();
+        ids.insert(1, 5);
+        ids.insert(5, 6);
+
+        assert_eq!(renumber("finding:1, [it](finding:5) and finding:2", &ids), "finding:5, [it](finding:6) and finding:2");
+    }
+}
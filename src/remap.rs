@@ -0,0 +1,154 @@
+use crate::{
+    git::{self, Hunk},
+    report::{Confidence, Location}
+};
+
+/// Maps a line of the old version of a file onto the new version using the hunks between them.
+pub fn map_line(hunks: &[Hunk], line: usize) -> (Option<usize>, Confidence) {
+    let mut delta: isize = 0;
+
+    for hunk in hunks {
+        // Insertions have no old lines and add their lines after `old_start`
+        if hunk.old_count == 0 {
+            if line > hunk.old_start {
+                delta += hunk.new_count as isize;
+                continue;
+            }
+
+            break;
+        }
+
+        if line < hunk.old_start {
+            break;
+        }
+
+        if line >= hunk.old_start + hunk.old_count {
+            delta += hunk.new_count as isize - hunk.old_count as isize;
+            continue;
+        }
+
+        let offset = line - hunk.old_start;
+
+        return match hunk.new_count {
+            0 => (None, Confidence::Lost),
+            count if count == hunk.old_count => (Some(hunk.new_start + offset), Confidence::Medium),
+            count => (Some(hunk.new_start + offset * count / hunk.old_count), Confidence::Low)
+        };
+    }
+
+    (Some((line as isize + delta) as usize), Confidence::High)
+}
+
+/// Remaps a location onto the `to` commit, following renames of its file. Locations without a
+/// pinned commit are assumed to refer to `default_commit`.
+pub fn remap_location(path: &str, location: &Location, default_commit: &str, to: &str) -> Result<Location, String> {
+    let from = location.commit.as_ref().map(String::as_str).unwrap_or(default_commit);
+    let file = location.file.trim_start_matches("./");
+
+    if from == to {
+        return Ok(location.clone());
+    }
+
+    let new_file = match git::renamed_file(path, from, to, file)? {
+        Some(new_file) => new_file,
+        None => {
+            return Ok(Location {
+                file: location.file.clone(),
+                lines: vec![],
+                commit: Some(to.to_string()),
+                confidence: Some(Confidence::Lost)
+            });
+        }
+    };
+
+    let hunks = git::diff_hunks(path, from, file, to, new_file.as_str())?;
+
+    let mut lines = vec![];
+    let mut confidence = Confidence::High;
+
+    for line in &location.lines {
+        let (new_line, line_confidence) = map_line(&hunks, *line);
+
+        if let Some(new_line) = new_line {
+            lines.push(new_line);
+        }
+
+        confidence = confidence.min(line_confidence);
+    }
+
+    lines.sort();
+    lines.dedup();
+
+    // Partially deleted locations keep their remaining lines, but are only lost when nothing remains
+    if confidence == Confidence::Lost && !lines.is_empty() {
+        confidence = Confidence::Low;
+    }
+
+    Ok(Location {
+        file: new_file,
+        lines,
+        commit: Some(to.to_string()),
+        confidence: Some(confidence)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(old_start: usize, old_count: usize, new_start: usize, new_count: usize) -> Hunk {
+        Hunk { old_start, old_count, new_start, new_count }
+    }
+
+    #[test]
+    fn map_line_without_hunks_keeps_lines() {
+        assert_eq!(map_line(&[], 10), (Some(10), Confidence::High));
+    }
+
+    #[test]
+    fn map_line_shifts_across_hunks() {
+        // Two lines inserted after line 5, then lines 20-21 replaced by one line
+        let hunks = [hunk(5, 0, 6, 2), hunk(20, 2, 22, 1)];
+
+        assert_eq!(map_line(&hunks, 5), (Some(5), Confidence::High));
+        assert_eq!(map_line(&hunks, 6), (Some(8), Confidence::High));
+        assert_eq!(map_line(&hunks, 19), (Some(21), Confidence::High));
+        assert_eq!(map_line(&hunks, 22), (Some(23), Confidence::High));
+        assert_eq!(map_line(&hunks, 40), (Some(41), Confidence::High));
+    }
+
+    #[test]
+    fn map_line_within_hunks() {
+        let hunks = [hunk(10, 2, 10, 2), hunk(20, 4, 20, 2), hunk(30, 3, 29, 0)];
+
+        assert_eq!(map_line(&hunks, 11), (Some(11), Confidence::Medium));
+        assert_eq!(map_line(&hunks, 22), (Some(21), Confidence::Low));
+        assert_eq!(map_line(&hunks, 31), (None, Confidence::Lost));
+        assert_eq!(map_line(&hunks, 35), (Some(30), Confidence::High));
+    }
+
+    #[test]
+    fn map_line_uses_parsed_diff_hunks() {
+        let diff = "diff --git a/Vault.sol b/Vault.sol\n\
+            --- a/Vault.sol\n\
+            +++ b/Vault.sol\n\
+            @@ -3,0 +4,3 @@ contract Vault {\n\
+            +    uint256 a;\n\
+            +    uint256 b;\n\
+            +    uint256 c;\n\
+            @@ -12 +15 @@ function withdraw() {\n\
+            -        send();\n\
+            +        transfer();\n\
+            @@ -30,2 +32,0 @@\n\
+            -    }\n\
+            -}\n";
+
+        let hunks = git::parse_hunks(diff);
+
+        assert_eq!(hunks, vec![hunk(3, 0, 4, 3), hunk(12, 1, 15, 1), hunk(30, 2, 32, 0)]);
+        assert_eq!(map_line(&hunks, 10), (Some(13), Confidence::High));
+        assert_eq!(map_line(&hunks, 12), (Some(15), Confidence::Medium));
+        assert_eq!(map_line(&hunks, 30), (None, Confidence::Lost));
+        assert_eq!(map_line(&hunks, 40), (Some(41), Confidence::High));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{command, template, StateData};
+
+use regex::{Regex, RegexBuilder};
+
+use std::collections::HashMap;
+
+/// The report fields searched by find and replace, keyed as in the `set_report_field` command.
+pub const REPORT_FIELDS: [(&str, &str); 5] = [
+    ("title", "Report title"),
+    ("start_time", "Start time"),
+    ("delivery_time", "Delivery time"),
+    ("repository", "Repository"),
+    ("overview", "Executive overview")
+];
+
+/// The finding fields searched by find and replace, named as in their `set_finding_*` commands.
+pub const FINDING_FIELDS: [(&str, &str); 7] = [
+    ("title", "title"),
+    ("type", "type"),
+    ("location", "location"),
+    ("justification", "severity justification"),
+    ("description", "description"),
+    ("recommendation", "recommendation"),
+    ("alleviation", "alleviation")
+];
+
+/// The number of characters shown on each side of a match in the preview.
+const CONTEXT_LENGTH: usize = 40;
+
+/// A text field of the workbook.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Field {
+    Report(&'static str),
+    Checklist(usize),
+    Finding(usize, &'static str)
+}
+
+impl Field {
+    /// Returns every text field of the workbook, with the findings in id order.
+    pub fn all(state: &StateData) -> Vec<Field> {
+        let mut fields: Vec<Field> = REPORT_FIELDS.iter().map(|(name, _)| Field::Report(name)).collect();
+        fields.extend((0..state.checklist.len()).map(Field::Checklist));
+
+        let mut ids: Vec<usize> = state.findings.keys().copied().collect();
+        ids.sort();
+
+        for id in ids {
+            fields.extend(FINDING_FIELDS.iter().map(|(name, _)| Field::Finding(id, name)));
+        }
+
+        fields
+    }
+
+    pub fn text<'a>(&self, state: &'a StateData) -> Option<&'a str> {
+        match *self {
+            Field::Report(name) => match name {
+                "title" => Some(state.title.as_str()),
+                "start_time" => Some(state.start_time.as_str()),
+                "delivery_time" => Some(state.delivery_time.as_str()),
+                "repository" => Some(state.repository.as_str()),
+                "overview" => Some(state.overview.as_str()),
+                _ => None
+            },
+
+            Field::Checklist(index) => state.checklist.get(index).map(|(_, text)| text.as_str()),
+
+            Field::Finding(id, name) => state.findings.get(&id).and_then(|finding| match name {
+                "title" => Some(finding.title.as_str()),
+                "type" => Some(finding.class.as_str()),
+                "location" => Some(finding.location.as_str()),
+                "justification" => Some(finding.justification.as_str()),
+                "description" => Some(finding.description.as_str()),
+                "recommendation" => Some(finding.recommendation.as_str()),
+                "alleviation" => Some(finding.alleviation.as_str()),
+                _ => None
+            })
+        }
+    }
+
+    pub fn label(&self, display_ids: &HashMap<usize, String>) -> String {
+        match *self {
+            Field::Report(name) => REPORT_FIELDS.iter()
+                .find(|(key, _)| *key == name)
+                .map_or_else(|| name.to_string(), |(_, label)| label.to_string()),
+
+            Field::Checklist(index) => format!("Checklist item {}", index + 1),
+
+            Field::Finding(id, name) => format!(
+                "{} {}",
+                template::finding_label(display_ids, id),
+                FINDING_FIELDS.iter().find(|(key, _)| *key == name).map_or(name, |(_, label)| *label)
+            )
+        }
+    }
+
+    /// Returns the command which sets the field to a text, as sent when the field is edited by hand.
+    pub fn command(&self, text: &str) -> String {
+        let text = command::quote(text);
+
+        match *self {
+            Field::Report(name) => format!("set_report_field {} {}", name, text),
+            Field::Checklist(index) => format!("set_checklist_entry_text {} {}", index, text),
+            Field::Finding(id, name) => format!("set_finding_{} {} {}", name, id, text)
+        }
+    }
+}
+
+/// A match in a field and the text replacing it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    /// The byte range of the match in the field's text.
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String
+}
+
+/// The matches in one field of the workbook.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldMatches {
+    pub field: Field,
+    pub text: String,
+    pub matches: Vec<Match>
+}
+
+impl FieldMatches {
+    /// Returns the field's text with every match replaced.
+    pub fn replaced(&self) -> String {
+        let mut result = String::with_capacity(self.text.len());
+        let mut offset = 0;
+
+        for entry in &self.matches {
+            result.push_str(&self.text[offset..entry.start]);
+            result.push_str(entry.replacement.as_str());
+            offset = entry.end;
+        }
+
+        result.push_str(&self.text[offset..]);
+        result
+    }
+
+    /// Returns the text before and after a match on the same line, shortened to a few words.
+    pub fn context(&self, entry: &Match) -> (String, String) {
+        let line_start = self.text[..entry.start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = self.text[entry.end..].find('\n').map_or(self.text.len(), |index| entry.end + index);
+
+        let before = &self.text[line_start..entry.start];
+        let after = &self.text[entry.end..line_end];
+
+        let before = match before.char_indices().rev().nth(CONTEXT_LENGTH - 1) {
+            Some((index, _)) => format!("…{}", &before[index..]),
+            None => before.to_string()
+        };
+
+        let after = match after.char_indices().nth(CONTEXT_LENGTH) {
+            Some((index, _)) => format!("{}…", &after[..index]),
+            None => after.to_string()
+        };
+
+        (before, after)
+    }
+}
+
+/// The options of a workbook-wide find and replace.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FindReplace {
+    pub pattern: String,
+    pub replacement: String,
+    /// Whether the pattern is a regular expression, with `$1` or `${name}` groups usable in the replacement.
+    pub regex: bool,
+    pub case_sensitive: bool
+}
+
+impl FindReplace {
+    fn build_regex(&self) -> Result<Regex, String> {
+        let pattern = if self.regex { self.pattern.clone() } else { regex::escape(self.pattern.as_str()) };
+
+        RegexBuilder::new(pattern.as_str())
+            .case_insensitive(!self.case_sensitive)
+            .multi_line(true)
+            .build()
+            .map_err(|error| format!("Invalid pattern: {}", error))
+    }
+
+    /// Finds the matches in every text field of the workbook.
+    pub fn search(&self, state: &StateData) -> Result<Vec<FieldMatches>, String> {
+        if self.pattern.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let regex = self.build_regex()?;
+        let mut results = vec![];
+
+        for field in Field::all(state) {
+            let text = match field.text(state) {
+                None => continue,
+                Some(text) => text
+            };
+
+            let matches: Vec<Match> = regex.captures_iter(text)
+                .map(|captures| {
+                    let whole = captures.get(0).unwrap();
+
+                    let replacement = if self.regex {
+                        let mut replacement = String::new();
+                        captures.expand(self.replacement.as_str(), &mut replacement);
+                        replacement
+                    } else {
+                        self.replacement.clone()
+                    };
+
+                    Match { start: whole.start(), end: whole.end(), replacement }
+                })
+                .collect();
+
+            if !matches.is_empty() {
+                results.push(FieldMatches { field, text: text.to_string(), matches });
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Finding;
+
+    fn state(descriptions: &[&str]) -> StateData {
+        let mut state = StateData::new();
+        state.title = String::new();
+        state.start_time = String::new();
+        state.delivery_time = String::new();
+        state.repository = String::new();
+        state.overview = String::new();
+
+        for (index, description) in descriptions.iter().enumerate() {
+            let mut finding = Finding::new(index + 1);
+            finding.description = description.to_string();
+            state.findings.insert(finding.id, finding);
+        }
+
+        state
+    }
+
+    fn find(pattern: &str, replacement: &str, regex: bool, case_sensitive: bool) -> FindReplace {
+        FindReplace { pattern: pattern.to_string(), replacement: replacement.to_string(), regex, case_sensitive }
+    }
+
+    fn replaced(find_replace: &FindReplace, state: &StateData) -> Vec<(Field, String)> {
+        find_replace.search(state).unwrap().iter().map(|result| (result.field, result.replaced())).collect()
+    }
+
+    #[test]
+    fn replaces_text() {
+        let state = state(&["Call withdraw() before Withdraw()", "Nothing here"]);
+
+        assert_eq!(
+            replaced(&find("withdraw()", "claim()", false, true), &state),
+            vec![(Field::Finding(1, "description"), "Call claim() before Withdraw()".to_string())]
+        );
+
+        // Patterns are matched literally, so `$1` in the replacement is kept as it is
+        assert_eq!(
+            replaced(&find("before", "$1", false, true), &state),
+            vec![(Field::Finding(1, "description"), "Call withdraw() $1 Withdraw()".to_string())]
+        );
+    }
+
+    #[test]
+    fn replaces_case_insensitively() {
+        let state = state(&["Call withdraw() before Withdraw()"]);
+
+        assert_eq!(
+            replaced(&find("WITHDRAW", "claim", false, false), &state),
+            vec![(Field::Finding(1, "description"), "Call claim() before claim()".to_string())]
+        );
+
+        assert!(find("WITHDRAW", "claim", false, true).search(&state).unwrap().is_empty());
+    }
+
+    #[test]
+    fn replaces_regex_groups() {
+        let state = state(&["Lines L10-12 and L20-25", "See L3-4\nl7-9"]);
+
+        assert_eq!(
+            replaced(&find(r"L(\d+)-(?P<end>\d+)", "L$1–${end}", true, true), &state),
+            vec![
+                (Field::Finding(1, "description"), "Lines L10–12 and L20–25".to_string()),
+                (Field::Finding(2, "description"), "See L3–4\nl7-9".to_string())
+            ]
+        );
+
+        // Anchors match at every line of a field
+        assert_eq!(
+            replaced(&find(r"^l", "Line ", true, false), &state),
+            vec![
+                (Field::Finding(1, "description"), "Line ines L10-12 and L20-25".to_string()),
+                (Field::Finding(2, "description"), "See L3-4\nLine 7-9".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_regex() {
+        let state = state(&["(unclosed"]);
+
+        assert!(find("(unclosed", "", true, true).search(&state).unwrap_err().starts_with("Invalid pattern"));
+
+        // The same text is matched literally when it is not a regex
+        assert_eq!(find("(unclosed", "", false, true).search(&state).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn replacement_commands_keep_quotes() {
+        // Text which already holds doubled single quotes, such as Solidity's empty string `''`
+        let state = state(&["require(name != '', \"empty\") before a\\b"]);
+        let results = find("empty", "blank", false, true).search(&state).unwrap();
+
+        let command = results[0].field.command(results[0].replaced().as_str());
+        let tokens = command::tokenize(command.as_str());
+
+        assert_eq!(tokens, vec!["set_finding_description", "1", "require(name != '', \"blank\") before a\\b"]);
+    }
+
+    #[test]
+    fn match_context() {
+        let state = state(&["first line\nsome text with a match in it\nlast line"]);
+        let results = find("match", "", false, true).search(&state).unwrap();
+
+        assert_eq!(results[0].context(&results[0].matches[0]), ("some text with a ".to_string(), " in it".to_string()));
+    }
+}
@@ -1,4 +1,8 @@
-#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+use crate::{baseline::Suppression, lint};
+
+use std::{cmp::Ordering, collections::BTreeMap};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Severity {
     Minor,
     Major,
@@ -6,10 +10,336 @@ pub enum Severity {
     Informational
 }
 
+impl Severity {
+    pub const ALL: [Severity; 4] = [Severity::Critical, Severity::Major, Severity::Minor, Severity::Informational];
+
+    /// Returns the rank of the severity, where 0 is the most severe.
+    pub fn rank(&self) -> usize {
+        match self {
+            Severity::Critical => 0,
+            Severity::Major => 1,
+            Severity::Minor => 2,
+            Severity::Informational => 3
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Critical => "Critical",
+            Severity::Major => "Major",
+            Severity::Minor => "Minor",
+            Severity::Informational => "Informational"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|severity| severity.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl Ord for Severity {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // More severe findings compare greater
+        other.rank().cmp(&self.rank())
+    }
+}
+
+impl PartialOrd for Severity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub enum Likelihood {
+    Low,
+    Medium,
+    High
+}
+
+impl Likelihood {
+    pub const ALL: [Likelihood; 3] = [Likelihood::Low, Likelihood::Medium, Likelihood::High];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Likelihood::Low => "Low",
+            Likelihood::Medium => "Medium",
+            Likelihood::High => "High"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|likelihood| likelihood.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub enum Impact {
+    Low,
+    Medium,
+    High
+}
+
+impl Impact {
+    pub const ALL: [Impact; 3] = [Impact::Low, Impact::Medium, Impact::High];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Impact::Low => "Low",
+            Impact::Medium => "Medium",
+            Impact::High => "High"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|impact| impact.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// The severity assigned to each likelihood (row) and impact (column) pairing.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct SeverityMatrix {
+    pub cells: [[Severity; 3]; 3]
+}
+
+impl Default for SeverityMatrix {
+    fn default() -> Self {
+        use Severity::*;
+
+        Self {
+            cells: [
+                [Informational, Minor, Major],
+                [Minor, Major, Major],
+                [Minor, Major, Critical]
+            ]
+        }
+    }
+}
+
+impl SeverityMatrix {
+    pub fn get(&self, likelihood: Likelihood, impact: Impact) -> Severity {
+        self.cells[likelihood as usize][impact as usize]
+    }
+
+    pub fn set(&mut self, likelihood: Likelihood, impact: Impact, severity: Severity) {
+        self.cells[likelihood as usize][impact as usize] = severity;
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub enum Status {
+    Pending,
+    Acknowledged,
+    PartiallyResolved,
+    Resolved,
+    Declined
+}
+
+impl Default for Status {
+    fn default() -> Self {
+        Status::Pending
+    }
+}
+
+impl Status {
+    pub const ALL: [Status; 5] = [Status::Pending, Status::Acknowledged, Status::PartiallyResolved, Status::Resolved, Status::Declined];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Pending => "Pending",
+            Status::Acknowledged => "Acknowledged",
+            Status::PartiallyResolved => "Partially Resolved",
+            Status::Resolved => "Resolved",
+            Status::Declined => "Declined"
+        }
+    }
+
+    /// Returns the identifier used for the status in commands and element ids.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Status::Pending => "pending",
+            Status::Acknowledged => "acknowledged",
+            Status::PartiallyResolved => "partially_resolved",
+            Status::Resolved => "resolved",
+            Status::Declined => "declined"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|status| status.key() == name || status.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Deserialize, Serialize)]
+pub enum Verdict {
+    Resolved,
+    Unresolved
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Resolved => "Resolved",
+            Verdict::Unresolved => "Unresolved"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "resolved" => Some(Verdict::Resolved),
+            "unresolved" => Some(Verdict::Unresolved),
+            _ => None
+        }
+    }
+}
+
+/// The outcome of re-checking a finding against the commit containing the client's fixes.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct Reaudit {
+    pub verdict: Verdict,
+    pub fix_commit: String
+}
+
+/// How confidently a location was carried over from the commit it was pinned to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum Confidence {
+    /// The code was deleted, so the location no longer exists.
+    Lost,
+
+    /// The code was rewritten and the lines were mapped by their position within the change.
+    Low,
+
+    /// The code was edited in place without adding or removing lines.
+    Medium,
+
+    /// The code is unchanged, although it may have moved.
+    High
+}
+
+impl Confidence {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Confidence::Lost => "Lost",
+            Confidence::Low => "Low",
+            Confidence::Medium => "Medium",
+            Confidence::High => "High"
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
 pub struct Location {
     pub file: String,
-    pub lines: Vec<usize>
+    pub lines: Vec<usize>,
+    /// The commit the lines refer to, or the first audited commit when unset.
+    #[serde(default)]
+    pub commit: Option<String>,
+    /// How confidently the location was last remapped, or unset if it never was.
+    #[serde(default)]
+    pub confidence: Option<Confidence>
+}
+
+impl Location {
+    pub fn new(file: String, lines: Vec<usize>) -> Self {
+        Self {
+            file,
+            lines,
+            commit: None,
+            confidence: None
+        }
+    }
+
+    /// Returns true if the location's code was rewritten or deleted since it was written down.
+    pub fn is_flagged(&self) -> bool {
+        self.confidence.map_or(false, |confidence| confidence != Confidence::High)
+    }
+
+    /// Parses locations written as text, such as `Vault.sol L10-12, L20; Token.sol#L5`, where
+    /// bare line references belong to the file before them.
+    pub fn parse_all(text: &str) -> Vec<Location> {
+        let mut locations: Vec<Location> = vec![];
+
+        for part in text.split(|c| c == ',' || c == ';' || c == '\n') {
+            for token in part.split_whitespace() {
+                let (file, lines) = match token.find(|c| c == '#' || c == ':') {
+                    Some(index) => (&token[..index], &token[index + 1..]),
+                    None if token.starts_with('L') && token[1..].starts_with(|c: char| c.is_ascii_digit()) => ("", token),
+                    None => (token, "")
+                };
+
+                if !file.is_empty() {
+                    if !file.contains('.') && !file.contains('/') {
+                        continue;
+                    }
+
+                    match locations.iter().position(|location| location.file == file) {
+                        // Move the file to the end so bare line references which follow are added to it
+                        Some(index) => {
+                            let location = locations.remove(index);
+                            locations.push(location);
+                        }
+
+                        None => locations.push(Location::new(file.to_string(), vec![]))
+                    }
+                }
+
+                let mut bounds = lines.split('-').map(|bound| bound.trim_start_matches('L').parse::<usize>());
+
+                if let (Some(Ok(start)), Some(last)) = (bounds.next(), locations.last_mut()) {
+                    let end = match bounds.next() {
+                        Some(Ok(end)) if end >= start => end,
+                        _ => start
+                    };
+
+                    last.lines.extend(start..=end);
+                }
+            }
+        }
+
+        for location in locations.iter_mut() {
+            location.lines.sort();
+            location.lines.dedup();
+        }
+
+        locations
+    }
+
+    /// Groups the lines of the location into inclusive ranges of consecutive lines.
+    pub fn ranges(&self) -> Vec<(usize, usize)> {
+        let mut lines = self.lines.clone();
+        lines.sort();
+        lines.dedup();
+
+        let mut ranges: Vec<(usize, usize)> = vec![];
+
+        for line in lines {
+            match ranges.last_mut() {
+                Some(range) if range.1 + 1 == line => range.1 = line,
+                _ => ranges.push((line, line))
+            }
+        }
+
+        ranges
+    }
+}
+
+impl std::fmt::Display for Location {
+    /// Formats the location as the file followed by its line ranges, e.g. `Vault.sol L10-12, L20`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.file)?;
+
+        for (index, (start, end)) in self.ranges().iter().enumerate() {
+            write!(f, "{}", if index == 0 { " " } else { ", " })?;
+
+            if start == end {
+                write!(f, "L{}", start)?;
+            } else {
+                write!(f, "L{}-{}", start, end)?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
@@ -18,16 +348,297 @@ pub struct Auditor {
     pub email: String
 }
 
+/// The prefix of markdown link targets which refer to an attachment of the finding, such as
+/// `![Call graph](attachment:graph.png)`.
+pub const ATTACHMENT_SCHEME: &str = "attachment:";
+
+/// Serializes binary data as base64, so attachments fit in plain JSON workbooks.
+mod base64_data {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(base64::encode(data).as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        base64::decode(text.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A file attached to a finding, such as a screenshot, diagram or proof of concept.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct Attachment {
+    pub name: String,
+    pub media_type: String,
+    /// The contents of the file. Workbook bundles store them as separate files instead.
+    #[serde(default, with = "base64_data", skip_serializing_if = "Vec::is_empty")]
+    pub data: Vec<u8>
+}
+
+impl Attachment {
+    /// Creates an attachment, deriving its media type from the file extension. Whitespace in the
+    /// name is replaced so it can be used in markdown link targets.
+    pub fn new(name: &str, data: Vec<u8>) -> Self {
+        let name = name.trim().split_whitespace().collect::<Vec<_>>().join("-");
+
+        let extension = name.rsplit('.').next().unwrap_or("").to_lowercase();
+
+        let media_type = match extension.as_str() {
+            "png" => "image/png",
+            "jpg" | "jpeg" => "image/jpeg",
+            "gif" => "image/gif",
+            "svg" => "image/svg+xml",
+            "webp" => "image/webp",
+            "txt" | "log" | "md" | "sol" | "rs" | "js" | "ts" | "py" => "text/plain",
+            "json" => "application/json",
+            "pdf" => "application/pdf",
+            _ => "application/octet-stream"
+        };
+
+        Self {
+            name,
+            media_type: media_type.to_string(),
+            data
+        }
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.media_type.starts_with("image/")
+    }
+
+    /// Returns the markdown which refers to the attachment from a finding's text.
+    pub fn reference(&self) -> String {
+        if self.is_image() {
+            format!("![{}]({}{})", self.name, ATTACHMENT_SCHEME, self.name)
+        } else {
+            format!("[{}]({}{})", self.name, ATTACHMENT_SCHEME, self.name)
+        }
+    }
+
+    pub fn data_uri(&self) -> String {
+        format!("data:{};base64,{}", self.media_type, base64::encode(&self.data))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
 pub struct Finding {
     pub id: usize,
     pub title: String,
     pub class: String,
     pub severity: Option<Severity>,
+    #[serde(default)]
+    pub severity_overridden: bool,
+    #[serde(default)]
+    pub likelihood: Option<Likelihood>,
+    #[serde(default)]
+    pub impact: Option<Impact>,
+    #[serde(default)]
+    pub justification: String,
+    #[serde(default)]
+    pub status: Status,
     pub location: String,
+    #[serde(default)]
+    pub locations: Vec<Location>,
     pub description: String,
     pub recommendation: String,
-    pub alleviation: String
+    pub alleviation: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub tool: Option<String>,
+    #[serde(default)]
+    pub rule: Option<String>,
+    #[serde(default)]
+    pub fingerprints: Vec<String>,
+    #[serde(default)]
+    pub reaudit: Option<Reaudit>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The values of the workbook's custom fields, keyed by field name.
+    #[serde(default)]
+    pub custom_fields: BTreeMap<String, String>
+}
+
+impl Finding {
+    pub fn new(id: usize) -> Self {
+        Self {
+            id,
+            title: String::new(),
+            class: String::new(),
+            severity: None,
+            severity_overridden: false,
+            likelihood: None,
+            impact: None,
+            justification: String::new(),
+            status: Status::default(),
+            location: String::new(),
+            locations: vec![],
+            description: String::new(),
+            recommendation: String::new(),
+            alleviation: String::new(),
+            draft: false,
+            tool: None,
+            rule: None,
+            fingerprints: vec![],
+            reaudit: None,
+            attachments: vec![],
+            tags: vec![],
+            custom_fields: BTreeMap::new()
+        }
+    }
+
+    /// Returns the severity given by the matrix, if both likelihood and impact have been rated.
+    pub fn matrix_severity(&self, matrix: &SeverityMatrix) -> Option<Severity> {
+        match (self.likelihood, self.impact) {
+            (Some(likelihood), Some(impact)) => Some(matrix.get(likelihood, impact)),
+            _ => None
+        }
+    }
+
+    /// Sets the severity chosen by hand, which overrides the matrix only when it disagrees with it.
+    /// Clearing the severity goes back to the one given by the matrix.
+    pub fn set_severity(&mut self, severity: Option<Severity>, matrix: &SeverityMatrix) {
+        match severity {
+            Some(severity) => {
+                self.severity_overridden = self.matrix_severity(matrix) != Some(severity);
+                self.severity = Some(severity);
+            }
+
+            None => {
+                self.severity_overridden = false;
+                self.severity = None;
+                self.update_severity(matrix);
+            }
+        }
+    }
+
+    /// Recomputes the severity from the matrix unless it has been manually overridden.
+    pub fn update_severity(&mut self, matrix: &SeverityMatrix) {
+        if !self.severity_overridden {
+            if let Some(severity) = self.matrix_severity(matrix) {
+                self.severity = Some(severity);
+            }
+        }
+    }
+}
+
+/// Parses a comma separated tag list, dropping empty and repeated tags.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = vec![];
+
+    for tag in text.split(',').map(|tag| tag.trim()).filter(|tag| !tag.is_empty()) {
+        if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+
+    tags
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Deserialize, Serialize)]
+pub enum FieldKind {
+    Text,
+    Number,
+    Enum
+}
+
+impl FieldKind {
+    pub const ALL: [FieldKind; 3] = [FieldKind::Text, FieldKind::Number, FieldKind::Enum];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldKind::Text => "Text",
+            FieldKind::Number => "Number",
+            FieldKind::Enum => "Enum"
+        }
+    }
+
+    /// Returns the identifier used for the kind in commands and element ids.
+    pub fn key(&self) -> &'static str {
+        match self {
+            FieldKind::Text => "text",
+            FieldKind::Number => "number",
+            FieldKind::Enum => "enum"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|kind| kind.key() == name || kind.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// A field the workbook defines on its findings, such as the affected component.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct CustomField {
+    pub name: String,
+    pub kind: FieldKind,
+    /// The values an enum field can take.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Whether the field is shown as a column in exports.
+    #[serde(default)]
+    pub exported: bool
+}
+
+impl CustomField {
+    /// Checks that a value can be stored in the field. Empty values clear the field.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self.kind {
+            _ if value.is_empty() => Ok(()),
+            FieldKind::Text => Ok(()),
+
+            FieldKind::Number => value.trim().parse::<f64>()
+                .map(|_| ())
+                .map_err(|_| format!("The {} field must be a number, not '{}'", self.name, value)),
+
+            FieldKind::Enum if self.options.iter().any(|option| option == value) => Ok(()),
+            FieldKind::Enum => Err(format!("The {} field must be one of {}, not '{}'", self.name, self.options.join(", "), value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub enum FindingOrder {
+    Severity,
+    Creation
+}
+
+impl Default for FindingOrder {
+    fn default() -> Self {
+        FindingOrder::Severity
+    }
+}
+
+impl FindingOrder {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FindingOrder::Severity => "severity",
+            FindingOrder::Creation => "creation"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "severity" => Some(FindingOrder::Severity),
+            "creation" => Some(FindingOrder::Creation),
+            _ => None
+        }
+    }
+
+    /// Orders findings by severity (most severe first) then id, or by id alone for creation order.
+    pub fn compare(&self, lhs: &Finding, rhs: &Finding) -> Ordering {
+        match self {
+            FindingOrder::Severity => rhs.severity.cmp(&lhs.severity).then(lhs.id.cmp(&rhs.id)),
+            FindingOrder::Creation => lhs.id.cmp(&rhs.id)
+        }
+    }
+
+    pub fn sort(&self, findings: &mut [Finding]) {
+        findings.sort_by(|lhs, rhs| self.compare(lhs, rhs));
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize, Serialize)]
@@ -38,7 +649,104 @@ pub struct Report {
     pub delivery_time: String,
     pub repository: String,
     pub commit_hashes: Vec<String>,
+    /// Only read from older workbooks, since the checkout is now kept in the user's local configuration.
+    #[serde(default, skip_serializing)]
+    pub repository_path: Option<String>,
+    #[serde(default)]
+    pub fix_commit: Option<String>,
     pub checklist: Vec<(bool, String)>,
     pub overview: String,
     pub findings: Vec<Finding>,
+    #[serde(default)]
+    pub severity_matrix: SeverityMatrix,
+    #[serde(default)]
+    pub finding_order: FindingOrder,
+    #[serde(default)]
+    pub export_template: Option<String>,
+    #[serde(default)]
+    pub baseline: Vec<Suppression>,
+    #[serde(default)]
+    pub lint_rules: lint::Rules,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_read_case_insensitively() {
+        for severity in Severity::ALL.iter() {
+            assert_eq!(Severity::from_name(severity.name()), Some(*severity));
+            assert_eq!(Severity::from_name(severity.name().to_uppercase().as_str()), Some(*severity));
+        }
+
+        assert_eq!(Likelihood::from_name("medium"), Some(Likelihood::Medium));
+        assert_eq!(Impact::from_name("HIGH"), Some(Impact::High));
+        assert_eq!(Severity::from_name("unrated"), None);
+        assert_eq!(Severity::from_name(""), None);
+    }
+
+    #[test]
+    fn matrix_rows_are_likelihoods_and_columns_are_impacts() {
+        let mut matrix = SeverityMatrix::default();
+
+        assert_eq!(matrix.get(Likelihood::Low, Impact::Low), Severity::Informational);
+        assert_eq!(matrix.get(Likelihood::Low, Impact::High), Severity::Major);
+        assert_eq!(matrix.get(Likelihood::High, Impact::Low), Severity::Minor);
+        assert_eq!(matrix.get(Likelihood::High, Impact::High), Severity::Critical);
+
+        matrix.set(Likelihood::Low, Impact::High, Severity::Minor);
+        assert_eq!(matrix.get(Likelihood::Low, Impact::High), Severity::Minor);
+        assert_eq!(matrix.get(Likelihood::High, Impact::Low), Severity::Minor);
+    }
+
+    #[test]
+    fn severity_follows_likelihood_and_impact() {
+        let matrix = SeverityMatrix::default();
+        let mut finding = Finding::new(1);
+
+        // Both are needed before the matrix gives a severity
+        finding.likelihood = Some(Likelihood::High);
+        finding.update_severity(&matrix);
+        assert_eq!(finding.severity, None);
+
+        finding.impact = Some(Impact::High);
+        finding.update_severity(&matrix);
+        assert_eq!(finding.severity, Some(Severity::Critical));
+
+        finding.likelihood = Some(Likelihood::Low);
+        finding.update_severity(&matrix);
+        assert_eq!(finding.severity, Some(Severity::Major));
+
+        finding.impact = Some(Impact::Low);
+        finding.update_severity(&matrix);
+        assert_eq!(finding.severity, Some(Severity::Informational));
+    }
+
+    #[test]
+    fn overridden_severity_is_kept() {
+        let matrix = SeverityMatrix::default();
+        let mut finding = Finding::new(1);
+        finding.likelihood = Some(Likelihood::Medium);
+        finding.impact = Some(Impact::Medium);
+        finding.update_severity(&matrix);
+
+        // Choosing the severity the matrix gives is not an override
+        finding.set_severity(Some(Severity::Major), &matrix);
+        assert!(!finding.severity_overridden);
+
+        finding.set_severity(Some(Severity::Critical), &matrix);
+        assert!(finding.severity_overridden);
+
+        finding.impact = Some(Impact::Low);
+        finding.update_severity(&matrix);
+        assert_eq!(finding.severity, Some(Severity::Critical));
+
+        // Clearing the override goes back to the matrix
+        finding.set_severity(None, &matrix);
+        assert!(!finding.severity_overridden);
+        assert_eq!(finding.severity, Some(Severity::Minor));
+    }
 }
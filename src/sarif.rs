@@ -0,0 +1,452 @@
+use crate::{
+    report::{Location, Severity},
+    summary,
+    template,
+    tool::{self, ToolResult},
+    StateData
+};
+
+use serde_json::{Map, Value};
+
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+pub const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    pub version: String,
+    #[serde(default)]
+    pub runs: Vec<SarifRun>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<SarifArtifact>,
+    #[serde(default)]
+    pub results: Vec<SarifResult>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SarifArtifact {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<SarifArtifactLocation>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub information_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<SarifRule>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_description: Option<SarifMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_description: Option<SarifMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<SarifMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_configuration: Option<SarifConfiguration>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub properties: Map<String, Value>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SarifConfiguration {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SarifMessage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<String>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_index: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    pub message: SarifMessage,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<SarifLocation>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub properties: Map<String, Value>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub physical_location: Option<SarifPhysicalLocation>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<SarifRegion>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SarifArtifactLocation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// The artifact of the run holding the uri, which tools such as CodeQL give instead of the uri itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<i64>
+}
+
+impl SarifArtifactLocation {
+    /// Returns the uri of the location, looking it up in the run's artifacts if only an index is given.
+    fn resolve<'a>(&'a self, run: &'a SarifRun) -> Option<&'a str> {
+        self.uri.as_deref().or_else(|| {
+            array_index(self.index)
+                .and_then(|index| run.artifacts.get(index))
+                .and_then(|artifact| artifact.location.as_ref())
+                .and_then(|location| location.uri.as_deref())
+        })
+    }
+}
+
+/// Converts an index of a SARIF array, where a negative index such as the default of `-1` means there is none.
+fn array_index(index: Option<i64>) -> Option<usize> {
+    index.filter(|index| *index >= 0).map(|index| index as usize)
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<SarifArtifactContent>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SarifArtifactContent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>
+}
+
+/// Parses a SARIF 2.1 log into tool results, one for each result of each run.
+pub fn parse(json: &str) -> Result<Vec<ToolResult>, String> {
+    let log: SarifLog = serde_json::from_str(json)
+        .map_err(|error| format!("Failed to parse SARIF log: {}", error))?;
+
+    if !log.version.starts_with("2.1") {
+        return Err(format!("Unsupported SARIF version: {}", log.version));
+    }
+
+    let mut results = vec![];
+
+    for run in &log.runs {
+        for result in &run.results {
+            results.push(to_tool_result(run, result));
+        }
+    }
+
+    Ok(results)
+}
+
+fn to_tool_result(run: &SarifRun, result: &SarifResult) -> ToolResult {
+    let rules = &run.tool.driver.rules;
+
+    let rule = array_index(result.rule_index).and_then(|index| rules.get(index))
+        .or_else(|| result.rule_id.as_ref().and_then(|id| rules.iter().find(|rule| &rule.id == id)));
+
+    let rule_id = result.rule_id.clone()
+        .or_else(|| rule.map(|rule| rule.id.clone()))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let title = rule.and_then(|rule| rule.short_description.as_ref().and_then(|description| description.text.clone()))
+        .or_else(|| rule.and_then(|rule| rule.name.clone()))
+        .unwrap_or_else(|| tool::rule_title(rule_id.rsplit('/').next().unwrap_or(rule_id.as_str())));
+
+    // Results take precedence over their rule's default configuration, which defaults to a warning
+    let level = result.level.clone()
+        .or_else(|| rule.and_then(|rule| rule.default_configuration.as_ref().and_then(|configuration| configuration.level.clone())))
+        .unwrap_or_else(|| "warning".to_string());
+
+    let security_severity = result.properties.get("security-severity")
+        .or_else(|| rule.and_then(|rule| rule.properties.get("security-severity")))
+        .and_then(|value| match value {
+            Value::String(string) => string.parse::<f64>().ok(),
+            value => value.as_f64()
+        });
+
+    // Logs exported by the workbook carry the original severity and type, which round-trip exactly
+    let workbook_severity = result.properties.get("severity")
+        .and_then(|value| value.as_str())
+        .and_then(Severity::from_name);
+
+    let workbook_class = result.properties.get("class")
+        .and_then(|value| value.as_str())
+        .map(|class| class.to_string());
+
+    let severity = workbook_severity.or(match security_severity {
+        Some(score) if score >= 9.0 => Some(Severity::Critical),
+        Some(score) if score >= 7.0 => Some(Severity::Major),
+        Some(score) if score >= 4.0 => Some(Severity::Minor),
+        Some(_) => Some(Severity::Informational),
+        None => match level.as_str() {
+            "error" => Some(Severity::Major),
+            "warning" => Some(Severity::Minor),
+            "note" => Some(Severity::Informational),
+            _ => None
+        }
+    });
+
+    let mut tags: Vec<String> = rule.and_then(|rule| rule.properties.get("tags"))
+        .and_then(|tags| tags.as_array())
+        .map(|tags| tags.iter().filter_map(|tag| tag.as_str()).map(|tag| tag.to_lowercase()).collect())
+        .unwrap_or_default();
+    tags.push(rule_id.to_lowercase());
+
+    let locations = result.locations.iter()
+        .filter_map(|location| location.physical_location.as_ref())
+        .filter_map(|physical_location| {
+            let uri = physical_location.artifact_location.resolve(run)?;
+
+            let lines = match physical_location.region.as_ref().and_then(|region| region.start_line) {
+                None => vec![],
+                Some(start_line) => {
+                    let end_line = physical_location.region.as_ref().and_then(|region| region.end_line).unwrap_or(start_line);
+                    (start_line..=end_line.max(start_line)).collect()
+                }
+            };
+
+            Some(Location::new(uri.strip_prefix("file://").unwrap_or(uri).to_string(), lines))
+        })
+        .collect();
+
+    ToolResult {
+        tool: run.tool.driver.name.clone(),
+        rule: rule_id,
+        title,
+        class: workbook_class.unwrap_or_else(|| tag_class(&tags).to_string()),
+        severity,
+        impact: None,
+        description: result.message.markdown.clone()
+            .or_else(|| result.message.text.clone())
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        locations: tool::merge_locations(locations),
+        snippet: result.locations.iter()
+            .filter_map(|location| location.physical_location.as_ref())
+            .filter_map(|physical_location| physical_location.region.as_ref())
+            .filter_map(|region| region.snippet.as_ref().and_then(|snippet| snippet.text.clone()))
+            .next()
+    }
+}
+
+/// Guesses the finding type from the rule id and tags, since SARIF has no notion of one.
+fn tag_class(tags: &[String]) -> &'static str {
+    let has_tag = |keywords: &[&str]| tags.iter().any(|tag| keywords.iter().any(|keyword| tag.contains(keyword)));
+
+    if has_tag(&["gas", "optimization", "performance"]) {
+        "Gas Optimization"
+    } else if has_tag(&["style", "naming", "maintainability", "best-practice"]) {
+        "Coding Style"
+    } else if has_tag(&["reentrancy", "unchecked", "uninitialized"]) {
+        "Volatile Code"
+    } else if has_tag(&["overflow", "underflow", "arithmetic", "division"]) {
+        "Mathematical Operations"
+    } else if has_tag(&["access-control", "authorization", "delegatecall", "selfdestruct"]) {
+        "Control Flow"
+    } else {
+        "Logical Issue"
+    }
+}
+
+fn severity_level(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::Critical) | Some(Severity::Major) => "error",
+        Some(Severity::Minor) => "warning",
+        Some(Severity::Informational) => "note",
+        None => "none"
+    }
+}
+
+fn text_message(text: &str) -> Option<SarifMessage> {
+    if text.trim().is_empty() {
+        None
+    } else {
+        Some(SarifMessage { text: Some(text.to_string()), markdown: None })
+    }
+}
+
+fn physical_location(file: &str, region: Option<(usize, usize)>) -> SarifLocation {
+    SarifLocation {
+        physical_location: Some(SarifPhysicalLocation {
+            artifact_location: SarifArtifactLocation { uri: Some(file.to_string()), index: None },
+            region: region.map(|(start_line, end_line)| SarifRegion {
+                start_line: Some(start_line),
+                end_line: Some(end_line),
+                snippet: None
+            })
+        })
+    }
+}
+
+/// Builds a SARIF 2.1 log of the reviewed findings in the workbook, with one rule per finding.
+pub fn export(state: &StateData) -> Result<String, String> {
+    let mut report = state.to_report();
+    report.findings.retain(|finding| !finding.draft);
+    state.finding_order.sort(&mut report.findings);
+
+    let mut rules = vec![];
+    let mut results = vec![];
+
+    for (index, finding) in report.findings.iter().enumerate() {
+        let rule_id = template::display_id(index);
+
+        let mut rule_properties = Map::new();
+        rule_properties.insert("tags".to_string(), Value::from(std::iter::once(&finding.class).chain(&finding.tags).cloned().collect::<Vec<_>>()));
+
+        rules.push(SarifRule {
+            id: rule_id.clone(),
+            name: Some(finding.title.clone()),
+            short_description: text_message(finding.title.as_str()),
+            full_description: text_message(finding.description.as_str()),
+            help: text_message(finding.recommendation.as_str()),
+            default_configuration: Some(SarifConfiguration { level: Some(severity_level(finding.severity).to_string()) }),
+            properties: rule_properties
+        });
+
+        // Structured locations carry line regions, otherwise only the files named in the location text are known
+        let locations = if finding.locations.is_empty() {
+            summary::location_files(finding.location.as_str()).iter()
+                .map(|file| physical_location(file.as_str(), None))
+                .collect()
+        } else {
+            finding.locations.iter()
+                .flat_map(|location| {
+                    let ranges = location.ranges();
+
+                    if ranges.is_empty() {
+                        vec![physical_location(location.file.as_str(), None)]
+                    } else {
+                        ranges.into_iter().map(|range| physical_location(location.file.as_str(), Some(range))).collect()
+                    }
+                })
+                .collect()
+        };
+
+        let mut properties = Map::new();
+        properties.insert("severity".to_string(), Value::from(finding.severity.map(|severity| severity.name()).unwrap_or("Unrated")));
+        properties.insert("status".to_string(), Value::from(finding.status.name()));
+        properties.insert("class".to_string(), Value::from(finding.class.clone()));
+
+        if let Some(likelihood) = finding.likelihood {
+            properties.insert("likelihood".to_string(), Value::from(likelihood.name()));
+        }
+
+        if let Some(impact) = finding.impact {
+            properties.insert("impact".to_string(), Value::from(impact.name()));
+        }
+
+        results.push(SarifResult {
+            rule_id: Some(rule_id),
+            rule_index: Some(index as i64),
+            level: Some(severity_level(finding.severity).to_string()),
+            message: SarifMessage {
+                text: Some(finding.title.clone()),
+                markdown: if finding.description.trim().is_empty() {
+                    None
+                } else {
+                    Some(format!("**{}**\n\n{}", finding.title, finding.description))
+                }
+            },
+            locations,
+            properties
+        });
+    }
+
+    let log = SarifLog {
+        schema: Some(SARIF_SCHEMA.to_string()),
+        version: SARIF_VERSION.to_string(),
+        runs: vec![
+            SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "CertiK Workbook".to_string(),
+                        version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                        information_uri: None,
+                        rules
+                    }
+                },
+                artifacts: vec![],
+                results
+            }
+        ]
+    };
+
+    serde_json::to_string_pretty(&log)
+        .map_err(|error| format!("Failed to build SARIF log: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_resolves_artifact_indexes() {
+        let results = parse(include_str!("../tests/fixtures/codeql.sarif")).unwrap();
+
+        assert_eq!(results.len(), 2);
+
+        assert_eq!(results[0].locations.len(), 1);
+        assert_eq!(results[0].locations[0].file, "contracts/Token.sol");
+        assert_eq!(results[0].locations[0].lines, vec![12]);
+
+        // An index outside the run's artifacts leaves the location out
+        assert_eq!(results[1].locations.len(), 1);
+        assert_eq!(results[1].locations[0].file, "contracts/Vault.sol");
+        assert_eq!(results[1].locations[0].lines, vec![40, 41, 42]);
+    }
+
+    #[test]
+    fn parse_treats_negative_rule_index_as_absent() {
+        let results = parse(include_str!("../tests/fixtures/codeql.sarif")).unwrap();
+
+        assert_eq!(results[0].title, "Unused variable");
+        assert_eq!(results[0].severity, Some(Severity::Informational));
+
+        // The rule is found by its id instead
+        assert_eq!(results[1].rule, "js/reentrancy");
+        assert_eq!(results[1].title, "Reentrant call");
+        assert_eq!(results[1].severity, Some(Severity::Major));
+    }
+}
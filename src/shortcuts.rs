@@ -0,0 +1,205 @@
+use crate::config;
+
+use std::collections::BTreeMap;
+
+/// The file holding the keyboard shortcuts, in the user's configuration directory.
+pub const SHORTCUTS_FILE: &str = "shortcuts.json";
+
+/// The modifier keys in the order they are written in a shortcut.
+const MODIFIERS: [&str; 4] = ["Ctrl", "Alt", "Shift", "Meta"];
+
+/// The shortcuts used for commands the user has not configured.
+const DEFAULT_SHORTCUTS: [(&str, &str); 5] = [
+    ("show_command_palette", "Ctrl+Shift+P"),
+    ("create_finding", "Ctrl+Alt+N"),
+    ("paste_finding", "Ctrl+Alt+V"),
+    ("save_workbook", "Ctrl+Alt+S"),
+    ("run_linter", "Ctrl+Alt+L")
+];
+
+/// Normalizes a shortcut such as `shift+cmd+k` to `Shift+Meta+K`.
+///
+/// A shortcut is any number of the modifiers `Ctrl`, `Alt` (or `Option`), `Shift` and `Meta` (or `Cmd`)
+/// followed by a key named as in the browser's `KeyboardEvent.key`, such as `K`, `F2` or `Enter`.
+pub fn normalize(shortcut: &str) -> Result<String, String> {
+    let mut modifiers = vec![];
+    let mut key = None;
+
+    for part in shortcut.split('+').map(str::trim) {
+        let modifier = match part.to_lowercase().as_str() {
+            "ctrl" | "control" => "Ctrl",
+            "alt" | "option" => "Alt",
+            "shift" => "Shift",
+            "meta" | "cmd" | "command" | "super" => "Meta",
+
+            _ if part.is_empty() => return Err(format!("Invalid shortcut: '{}'", shortcut)),
+            _ if key.is_some() => return Err(format!("Shortcut '{}' has more than one key", shortcut)),
+
+            _ => {
+                // Single characters are matched without case, since Shift changes the character typed
+                key = Some(if part.chars().count() == 1 { part.to_uppercase() } else { part.to_string() });
+                continue;
+            }
+        };
+
+        if key.is_some() {
+            return Err(format!("Modifiers must come before the key in shortcut '{}'", shortcut));
+        }
+
+        modifiers.push(modifier);
+    }
+
+    let key = key.ok_or_else(|| format!("Shortcut '{}' has no key", shortcut))?;
+
+    // Other keys need a modifier besides Shift, or they would run commands while editing text
+    let function_key = key.len() > 1 && key.starts_with('F') && key[1..].parse::<u8>().is_ok();
+
+    if !function_key && modifiers.iter().all(|modifier| *modifier == "Shift") {
+        return Err(format!("Shortcut '{}' needs Ctrl, Alt or Meta unless it is a function key", shortcut));
+    }
+
+    let mut parts: Vec<String> = MODIFIERS.iter()
+        .filter(|modifier| modifiers.contains(modifier))
+        .map(|modifier| modifier.to_string())
+        .collect();
+
+    parts.push(key);
+
+    Ok(parts.join("+"))
+}
+
+/// The keyboard shortcuts of the user's commands, keyed by command name.
+///
+/// Only the shortcuts changed from their defaults are stored, with an empty shortcut removing a default.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Shortcuts(BTreeMap<String, String>);
+
+impl Shortcuts {
+    /// Reads the shortcuts from the user's configuration directory, if they have been configured.
+    pub fn load() -> Result<Shortcuts, String> {
+        let path = match config::directory() {
+            None => return Ok(Shortcuts::default()),
+            Some(directory) => directory.join(SHORTCUTS_FILE)
+        };
+
+        if !path.exists() {
+            return Ok(Shortcuts::default());
+        }
+
+        let json = std::fs::read_to_string(&path).map_err(|error| error.to_string())?;
+        let mut shortcuts: Shortcuts = serde_json::from_str(json.as_str()).map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+
+        // The file may have been edited by hand
+        for shortcut in shortcuts.0.values_mut().filter(|shortcut| !shortcut.is_empty()) {
+            *shortcut = normalize(shortcut)?;
+        }
+
+        Ok(shortcuts)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let directory = config::directory().ok_or_else(|| "No configuration directory was found".to_string())?;
+        std::fs::create_dir_all(&directory).map_err(|error| error.to_string())?;
+
+        let json = serde_json::to_string_pretty(self).map_err(|error| error.to_string())?;
+        std::fs::write(directory.join(SHORTCUTS_FILE), json).map_err(|error| error.to_string())
+    }
+
+    /// Returns the shortcut of a command, or an empty string if it has none.
+    pub fn get(&self, command: &str) -> String {
+        match self.0.get(command) {
+            Some(shortcut) => shortcut.clone(),
+
+            None => DEFAULT_SHORTCUTS.iter()
+                .find(|(name, _)| *name == command)
+                .map(|(_, shortcut)| shortcut.to_string())
+                .unwrap_or_default()
+        }
+    }
+
+    /// Sets the shortcut of a command, removing it from any other command using it.
+    pub fn set(&mut self, command: &str, shortcut: &str) -> Result<(), String> {
+        let shortcut = if shortcut.trim().is_empty() { String::new() } else { normalize(shortcut)? };
+
+        if !shortcut.is_empty() {
+            let others: Vec<String> = self.0.keys()
+                .map(String::as_str)
+                .chain(DEFAULT_SHORTCUTS.iter().map(|(name, _)| *name))
+                .filter(|name| *name != command && self.get(name) == shortcut)
+                .map(str::to_string)
+                .collect();
+
+            for other in others {
+                self.set_entry(other.as_str(), String::new());
+            }
+        }
+
+        self.set_entry(command, shortcut);
+
+        Ok(())
+    }
+
+    fn set_entry(&mut self, command: &str, shortcut: String) {
+        let default = DEFAULT_SHORTCUTS.iter()
+            .find(|(name, _)| *name == command)
+            .map(|(_, shortcut)| shortcut.to_string())
+            .unwrap_or_default();
+
+        if shortcut == default {
+            self.0.remove(command);
+        } else {
+            self.0.insert(command.to_string(), shortcut);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_shortcuts() {
+        assert_eq!(normalize("shift+cmd+k"), Ok("Shift+Meta+K".to_string()));
+        assert_eq!(normalize(" Control + Option + n "), Ok("Ctrl+Alt+N".to_string()));
+        assert_eq!(normalize("meta+alt+ctrl+shift+Enter"), Ok("Ctrl+Alt+Shift+Meta+Enter".to_string()));
+        assert_eq!(normalize("Super+ArrowUp"), Ok("Meta+ArrowUp".to_string()));
+
+        // Function keys work without a modifier
+        assert_eq!(normalize("F2"), Ok("F2".to_string()));
+        assert_eq!(normalize("shift+F12"), Ok("Shift+F12".to_string()));
+    }
+
+    #[test]
+    fn rejects_invalid_shortcuts() {
+        assert!(normalize("").is_err());
+        assert!(normalize("Ctrl++").is_err());
+        assert!(normalize("Ctrl+Shift").is_err());
+        assert!(normalize("Ctrl+K+L").is_err());
+        assert!(normalize("K+Ctrl").is_err());
+
+        // Keys typed while editing text need a modifier besides Shift
+        assert!(normalize("K").is_err());
+        assert!(normalize("Shift+K").is_err());
+        assert!(normalize("Enter").is_err());
+        assert!(normalize("Fn").is_err());
+    }
+
+    #[test]
+    fn set_moves_shortcuts_between_commands() {
+        let mut shortcuts = Shortcuts::default();
+        assert_eq!(shortcuts.get("create_finding"), "Ctrl+Alt+N");
+
+        shortcuts.set("run_reaudit", "alt+ctrl+n").unwrap();
+        assert_eq!(shortcuts.get("run_reaudit"), "Ctrl+Alt+N");
+        assert_eq!(shortcuts.get("create_finding"), "");
+
+        // Setting a default back removes the stored entry
+        shortcuts.set("create_finding", "Ctrl+Alt+N").unwrap();
+        assert_eq!(shortcuts.get("run_reaudit"), "");
+        assert_eq!(shortcuts.0.get("create_finding"), None);
+
+        assert!(shortcuts.set("run_linter", "L").is_err());
+        assert_eq!(shortcuts.get("run_linter"), "Ctrl+Alt+L");
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    report::{Impact, Location, Severity},
+    tool::{self, ToolResult}
+};
+
+#[derive(Clone, Debug, Deserialize)]
+struct SlitherOutput {
+    success: bool,
+    error: Option<String>,
+    results: Option<SlitherResults>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct SlitherResults {
+    #[serde(default)]
+    detectors: Vec<SlitherDetector>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct SlitherDetector {
+    check: String,
+    impact: String,
+    confidence: String,
+    description: String,
+    #[serde(default)]
+    markdown: Option<String>,
+    #[serde(default)]
+    elements: Vec<SlitherElement>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct SlitherElement {
+    #[serde(default)]
+    source_mapping: Option<SlitherSourceMapping>
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct SlitherSourceMapping {
+    #[serde(default)]
+    filename_relative: Option<String>,
+    #[serde(default)]
+    filename_short: Option<String>,
+    #[serde(default)]
+    lines: Vec<usize>
+}
+
+/// Parses the output of `slither --json` into tool results.
+pub fn parse(json: &str) -> Result<Vec<ToolResult>, String> {
+    let output: SlitherOutput = serde_json::from_str(json)
+        .map_err(|error| format!("Failed to parse Slither JSON: {}", error))?;
+
+    if !output.success {
+        return Err(format!("Slither reported an error: {}", output.error.unwrap_or_default()));
+    }
+
+    let detectors = output.results.map(|results| results.detectors).unwrap_or_default();
+
+    Ok(detectors.iter().map(|detector| to_tool_result(detector)).collect())
+}
+
+fn to_tool_result(detector: &SlitherDetector) -> ToolResult {
+    let locations = detector.elements.iter()
+        .filter_map(|element| element.source_mapping.as_ref())
+        .filter(|mapping| !mapping.lines.is_empty())
+        .filter_map(|mapping| {
+            mapping.filename_relative.clone()
+                .or_else(|| mapping.filename_short.clone())
+                .map(|file| Location::new(file, mapping.lines.clone()))
+        })
+        .collect();
+
+    ToolResult {
+        tool: "Slither".to_string(),
+        rule: detector.check.clone(),
+        title: tool::rule_title(detector.check.as_str()),
+        class: detector_class(detector.check.as_str(), detector.impact.as_str()).to_string(),
+        severity: severity(detector.impact.as_str(), detector.confidence.as_str()),
+        impact: match detector.impact.as_str() {
+            "High" => Some(Impact::High),
+            "Medium" => Some(Impact::Medium),
+            "Low" => Some(Impact::Low),
+            _ => None
+        },
+        description: detector.markdown.clone().unwrap_or_else(|| detector.description.clone()).trim().to_string(),
+        locations: tool::merge_locations(locations),
+        snippet: None
+    }
+}
+
+/// Maps a Slither impact and confidence pair to a severity.
+fn severity(impact: &str, confidence: &str) -> Option<Severity> {
+    match (impact, confidence) {
+        ("High", "High") => Some(Severity::Critical),
+        ("High", _) => Some(Severity::Major),
+        ("Medium", "High") => Some(Severity::Major),
+        ("Medium", _) => Some(Severity::Minor),
+        ("Low", _) => Some(Severity::Minor),
+        ("Informational", _) | ("Optimization", _) => Some(Severity::Informational),
+        _ => None
+    }
+}
+
+/// Maps a Slither detector to the finding type used in reports.
+fn detector_class(check: &str, impact: &str) -> &'static str {
+    if impact == "Optimization" {
+        return "Gas Optimization";
+    }
+
+    match check {
+        "constable-states" | "external-function" | "immutable-states" | "costly-loop" | "cache-array-length" => "Gas Optimization",
+
+        "naming-convention" | "solc-version" | "pragma" | "similar-names" | "too-many-digits" | "unused-state" |
+        "dead-code" | "redundant-statements" | "missing-inheritance" => "Coding Style",
+
+        "unchecked-transfer" | "unchecked-lowlevel" | "unchecked-send" | "unused-return" | "locked-ether" |
+        "uninitialized-state" | "uninitialized-storage" | "uninitialized-local" | "missing-zero-check" => "Volatile Code",
+
+        "arbitrary-send" | "arbitrary-send-eth" | "arbitrary-send-erc20" | "suicidal" | "unprotected-upgrade" |
+        "controlled-delegatecall" | "delegatecall-loop" | "msg-value-loop" | "calls-loop" => "Control Flow",
+
+        "divide-before-multiply" | "tautology" | "incorrect-equality" | "incorrect-shift" | "weak-prng" => "Mathematical Operations",
+
+        "assembly" | "low-level-calls" | "deprecated-standards" | "timestamp" | "tx-origin" | "erc20-interface" |
+        "erc721-interface" | "incorrect-modifier" => "Language Specific",
+
+        check if check.starts_with("reentrancy") => "Volatile Code",
+        check if check.starts_with("shadowing") => "Language Specific",
+
+        _ => "Logical Issue"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_maps_impact_and_confidence_to_severity() {
+        let results = parse(include_str!("../tests/fixtures/slither.json")).unwrap();
+
+        assert_eq!(results.len(), 3);
+
+        assert_eq!(results[0].rule, "reentrancy-eth");
+        assert_eq!(results[0].title, "Reentrancy Eth");
+        assert_eq!(results[0].severity, Some(Severity::Major));
+        assert_eq!(results[0].impact, Some(Impact::High));
+
+        assert_eq!(results[1].severity, Some(Severity::Informational));
+        assert_eq!(results[1].impact, None);
+
+        assert_eq!(results[2].severity, Some(Severity::Minor));
+        assert_eq!(results[2].impact, Some(Impact::Low));
+    }
+
+    #[test]
+    fn parse_classifies_detectors() {
+        let results = parse(include_str!("../tests/fixtures/slither.json")).unwrap();
+        let classes: Vec<&str> = results.iter().map(|result| result.class.as_str()).collect();
+
+        assert_eq!(classes, vec!["Volatile Code", "Gas Optimization", "Language Specific"]);
+
+        // The optimization impact wins over the detector
+        assert_eq!(detector_class("naming-convention", "Optimization"), "Gas Optimization");
+        assert_eq!(detector_class("unknown-detector", "Medium"), "Logical Issue");
+    }
+
+    #[test]
+    fn parse_merges_source_mappings() {
+        let results = parse(include_str!("../tests/fixtures/slither.json")).unwrap();
+        let locations = &results[0].locations;
+
+        // Mappings of the same file are merged, and mappings without lines are left out
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].file, "contracts/Vault.sol");
+        assert_eq!(locations[0].lines, vec![40, 41, 42, 44]);
+
+        // The short file name is used when there is no relative one
+        assert_eq!(locations[1].file, "contracts/Token.sol");
+        assert_eq!(locations[1].lines, vec![12]);
+    }
+
+    #[test]
+    fn parse_prefers_markdown_descriptions() {
+        let results = parse(include_str!("../tests/fixtures/slither.json")).unwrap();
+
+        assert!(results[0].description.starts_with("Reentrancy in [Vault.withdraw()](contracts/Vault.sol#L40-L42)"));
+        assert_eq!(results[1].description, "Vault.fee (contracts/Vault.sol#8) should be constant");
+    }
+
+    #[test]
+    fn parse_reports_slither_errors() {
+        let error = parse("{\"success\": false, \"error\": \"Invalid compilation: solc not found\", \"results\": {}}").unwrap_err();
+
+        assert_eq!(error, "Slither reported an error: Invalid compilation: solc not found");
+        assert!(parse("not json").unwrap_err().starts_with("Failed to parse Slither JSON"));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    markdown,
+    report::{CustomField, Finding, Status},
+    template,
+    StateData
+};
+
+use rust_xlsxwriter::{Format, Workbook};
+
+/// The columns of the findings sheet, in order. The finding id is the one kept by the workbook, which
+/// unlike the display id does not change when findings are reordered.
+pub const FINDING_COLUMNS: [&str; 9] = ["ID", "Finding ID", "Title", "Type", "Severity", "Location", "Status", "Description", "Recommendation"];
+
+/// The columns of the checklist sheet, in order.
+pub const CHECKLIST_COLUMNS: [&str; 2] = ["Checked", "Entry"];
+
+fn exported_fields(state: &StateData) -> impl Iterator<Item = &CustomField> {
+    state.custom_fields.iter().filter(|field| field.exported)
+}
+
+/// Returns the columns of the findings sheet, followed by the tags when any finding has them and
+/// the exported custom fields.
+fn finding_columns(state: &StateData, with_tags: bool) -> Vec<String> {
+    let mut columns: Vec<String> = FINDING_COLUMNS.iter().map(|column| column.to_string()).collect();
+
+    if with_tags {
+        columns.push("Tags".to_string());
+    }
+
+    columns.extend(exported_fields(state).map(|field| field.name.clone()));
+    columns
+}
+
+fn finding_row(state: &StateData, index: usize, finding: &Finding, with_tags: bool) -> Vec<String> {
+    let mut row = base_finding_row(index, finding);
+
+    if with_tags {
+        row.push(finding.tags.join(", "));
+    }
+
+    row.extend(exported_fields(state).map(|field| finding.custom_fields.get(&field.name).cloned().unwrap_or_default()));
+    row
+}
+
+fn base_finding_row(index: usize, finding: &Finding) -> Vec<String> {
+    vec![
+        template::display_id(index),
+        finding.id.to_string(),
+        finding.title.clone(),
+        finding.class.clone(),
+        finding.severity.map(|severity| severity.name()).unwrap_or("Unrated").to_string(),
+        finding.location.clone(),
+        finding.status.name().to_string(),
+        markdown::to_plain_text(finding.description.as_str()),
+        markdown::to_plain_text(finding.recommendation.as_str())
+    ]
+}
+
+/// Returns the reviewed findings in the order they are exported.
+fn findings(state: &StateData) -> Vec<Finding> {
+    let mut report = state.to_report();
+    report.findings.retain(|finding| !finding.draft);
+    state.finding_order.sort(&mut report.findings);
+    report.findings
+}
+
+fn write_csv(header: &[&str], rows: Vec<Vec<String>>) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    writer.write_record(header).map_err(|error| error.to_string())?;
+
+    for row in rows {
+        writer.write_record(&row).map_err(|error| error.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|error| error.to_string())?;
+    String::from_utf8(bytes).map_err(|error| error.to_string())
+}
+
+pub fn findings_csv(state: &StateData) -> Result<String, String> {
+    let findings = findings(state);
+    let with_tags = findings.iter().any(|finding| !finding.tags.is_empty());
+
+    write_csv(
+        &finding_columns(state, with_tags).iter().map(String::as_str).collect::<Vec<_>>(),
+        findings.iter().enumerate().map(|(index, finding)| finding_row(state, index, finding, with_tags)).collect()
+    )
+}
+
+pub fn checklist_csv(state: &StateData) -> Result<String, String> {
+    write_csv(
+        &CHECKLIST_COLUMNS,
+        state.checklist.iter()
+            .map(|(checked, text)| vec![if *checked { "Yes" } else { "No" }.to_string(), text.clone()])
+            .collect()
+    )
+}
+
+/// Builds an XLSX workbook with a findings sheet and a checklist sheet.
+pub fn xlsx(state: &StateData) -> Result<Vec<u8>, String> {
+    let header_format = Format::new().set_bold();
+    let text_format = Format::new().set_text_wrap();
+
+    let mut workbook = Workbook::new();
+
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Findings").map_err(|error| error.to_string())?;
+    sheet.set_freeze_panes(1, 0).map_err(|error| error.to_string())?;
+
+    let findings = findings(state);
+    let with_tags = findings.iter().any(|finding| !finding.tags.is_empty());
+    let columns = finding_columns(state, with_tags);
+
+    // The tags and custom field columns after the fixed ones are all given the same width
+    for (column, (name, width)) in columns.iter().zip([10, 10, 40, 20, 14, 30, 18, 80, 80].iter().chain(std::iter::repeat(&20))).enumerate() {
+        sheet.write_string_with_format(0, column as u16, name.as_str(), &header_format).map_err(|error| error.to_string())?;
+        sheet.set_column_width(column as u16, *width).map_err(|error| error.to_string())?;
+    }
+
+    for (row, finding) in findings.iter().enumerate() {
+        for (column, value) in finding_row(state, row, finding, with_tags).into_iter().enumerate() {
+            sheet.write_string_with_format(row as u32 + 1, column as u16, value, &text_format).map_err(|error| error.to_string())?;
+        }
+    }
+
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Checklist").map_err(|error| error.to_string())?;
+    sheet.set_column_width(1, 80).map_err(|error| error.to_string())?;
+
+    for (column, name) in CHECKLIST_COLUMNS.iter().enumerate() {
+        sheet.write_string_with_format(0, column as u16, *name, &header_format).map_err(|error| error.to_string())?;
+    }
+
+    for (row, (checked, text)) in state.checklist.iter().enumerate() {
+        sheet.write_boolean(row as u32 + 1, 0, *checked).map_err(|error| error.to_string())?;
+        sheet.write_string_with_format(row as u32 + 1, 1, text.as_str(), &text_format).map_err(|error| error.to_string())?;
+    }
+
+    workbook.save_to_buffer().map_err(|error| error.to_string())
+}
+
+/// Parses the finding ids and statuses from a findings CSV, such as one previously exported and
+/// edited in a spreadsheet. Other columns, including the display ids, are ignored.
+pub fn parse_statuses(csv: &str) -> Result<Vec<(usize, Status)>, String> {
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+
+    let headers = reader.headers().map_err(|error| error.to_string())?.clone();
+    let column = |name: &str| headers.iter().position(|header| header.trim().eq_ignore_ascii_case(name));
+
+    let (id_column, status_column) = match (column("Finding ID"), column("Status")) {
+        (Some(id_column), Some(status_column)) => (id_column, status_column),
+        _ => return Err("The CSV file must have 'Finding ID' and 'Status' columns, as exported by the workbook".to_string())
+    };
+
+    let mut statuses = vec![];
+
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(|error| error.to_string())?;
+
+        let id = record.get(id_column).unwrap_or("").trim();
+        let id = id.parse::<usize>().map_err(|_| format!("Invalid finding id '{}' on row {}", id, index + 2))?;
+
+        let status = record.get(status_column).unwrap_or("").trim();
+        let status = Status::from_name(status)
+            .ok_or_else(|| format!("Invalid status '{}' on row {}", status, index + 2))?;
+
+        statuses.push((id, status));
+    }
+
+    Ok(statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_statuses_reads_the_finding_id_column() {
+        // The display ids no longer match after the findings were reordered, and are ignored
+        let csv = "ID,Finding ID,Title,Status\nXXX-01,7,Reentrancy,Resolved\nXXX-02,3,Overflow,acknowledged\n";
+
+        assert_eq!(parse_statuses(csv), Ok(vec![(7, Status::Resolved), (3, Status::Acknowledged)]));
+    }
+
+    #[test]
+    fn parse_statuses_refuses_files_without_finding_ids() {
+        assert!(parse_statuses("ID,Title,Status\nXXX-01,Reentrancy,Resolved\n").is_err());
+        assert_eq!(parse_statuses("Finding ID,Status\nXXX-01,Resolved\n"), Err("Invalid finding id 'XXX-01' on row 2".to_string()));
+    }
+}
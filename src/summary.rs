@@ -0,0 +1,118 @@
+use crate::{
+    report::{Finding, Severity, Status},
+    StateData
+};
+
+use std::collections::HashMap;
+
+/// The maximum number of files listed in the most affected files table.
+pub const MAX_SUMMARY_FILES: usize = 5;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Count {
+    pub name: String,
+    pub count: usize
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Summary {
+    pub total: usize,
+    pub drafts: usize,
+    pub severities: Vec<Count>,
+    pub unrated: usize,
+    pub statuses: Vec<Count>,
+    pub classes: Vec<Count>,
+    pub files: Vec<Count>,
+    pub checklist_checked: usize,
+    pub checklist_total: usize
+}
+
+impl Summary {
+    pub fn new(state: &StateData) -> Self {
+        // Draft findings have not been reviewed yet, so they are counted separately from the report
+        let findings: Vec<&Finding> = state.findings.values().filter(|finding| !finding.draft).collect();
+
+        let severities = Severity::ALL.iter()
+            .map(|severity| Count {
+                name: severity.name().to_string(),
+                count: findings.iter().filter(|finding| finding.severity == Some(*severity)).count()
+            })
+            .collect();
+
+        let statuses = Status::ALL.iter()
+            .map(|status| Count {
+                name: status.name().to_string(),
+                count: findings.iter().filter(|finding| finding.status == *status).count()
+            })
+            .collect();
+
+        let mut classes = HashMap::new();
+        let mut files = HashMap::new();
+
+        for finding in &findings {
+            let class = finding.class.trim();
+
+            *classes.entry(if class.is_empty() { "Unclassified" } else { class }.to_string()).or_insert(0) += 1;
+
+            for file in location_files(finding.location.as_str()) {
+                *files.entry(file).or_insert(0) += 1;
+            }
+        }
+
+        let mut files = sorted_counts(files);
+        files.truncate(MAX_SUMMARY_FILES);
+
+        Self {
+            total: findings.len(),
+            drafts: state.findings.values().filter(|finding| finding.draft).count(),
+            severities,
+            unrated: findings.iter().filter(|finding| finding.severity.is_none()).count(),
+            statuses,
+            classes: sorted_counts(classes),
+            files,
+            checklist_checked: state.checklist.iter().filter(|entry| entry.0).count(),
+            checklist_total: state.checklist.len()
+        }
+    }
+
+    /// Returns the percentage of checklist entries which have been checked.
+    pub fn checklist_percent(&self) -> usize {
+        if self.checklist_total == 0 {
+            100
+        } else {
+            self.checklist_checked * 100 / self.checklist_total
+        }
+    }
+}
+
+fn sorted_counts(counts: HashMap<String, usize>) -> Vec<Count> {
+    let mut counts: Vec<Count> = counts.into_iter()
+        .map(|(name, count)| Count { name, count })
+        .collect();
+
+    counts.sort_by(|lhs, rhs| rhs.count.cmp(&lhs.count).then(lhs.name.cmp(&rhs.name)));
+    counts
+}
+
+/// Extracts the distinct file names referenced by a free-text location, e.g. `Token.sol L12-15, Vault.sol`.
+pub fn location_files(location: &str) -> Vec<String> {
+    let mut files: Vec<String> = vec![];
+
+    for part in location.split(|c| c == ',' || c == ';' || c == '\n') {
+        let file = part.trim()
+            .split(|c: char| c.is_whitespace() || c == ':' || c == '#')
+            .next()
+            .unwrap_or("");
+
+        // Skip bare line references such as `L20` which follow a comma
+        if !file.contains('.') && !file.contains('/') {
+            continue;
+        }
+
+        if !files.iter().any(|existing| existing == file) {
+            files.push(file.to_string());
+        }
+    }
+
+    files
+}
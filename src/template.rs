@@ -0,0 +1,281 @@
+use crate::{
+    chart,
+    highlight,
+    reference,
+    report::{Attachment, Finding, Impact, Likelihood, Location, Report, Severity, ATTACHMENT_SCHEME},
+    summary::Summary,
+    StateData
+};
+
+use handlebars::{handlebars_helper, no_escape, Handlebars};
+
+use std::collections::HashMap;
+
+/// The layout used for exports when the workbook does not specify its own template.
+pub const DEFAULT_TEMPLATE: &str = include_str!("../templates/default.md.hbs");
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AttachmentContext {
+    pub name: String,
+    pub media_type: String,
+    pub uri: String
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FindingContext {
+    #[serde(flatten)]
+    pub finding: Finding,
+    pub display_id: String,
+    pub severity_name: String,
+    pub severity_class: String,
+    pub matrix_severity_name: Option<String>,
+    pub likelihood_name: String,
+    pub impact_name: String,
+    pub status_name: String,
+    pub icon: String,
+    /// The attachments which are not referenced from the finding's text.
+    pub other_attachments: Vec<AttachmentContext>,
+    pub tags_text: String,
+    /// The values of the exported custom fields, in the order of `exported_fields`.
+    pub exported_field_values: Vec<String>
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MatrixCellContext {
+    pub severity_name: String,
+    pub severity_class: String
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MatrixRowContext {
+    pub likelihood: String,
+    pub cells: Vec<MatrixCellContext>
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ChartsContext {
+    pub severity_donut: String,
+    pub status_bar: String
+}
+
+/// The data available to export templates.
+#[derive(Clone, Debug, Serialize)]
+pub struct ReportContext {
+    pub report: Report,
+    pub findings: Vec<FindingContext>,
+    pub summary: Summary,
+    pub checklist_percent: usize,
+    pub charts: ChartsContext,
+    pub impacts: Vec<String>,
+    pub severity_matrix: Vec<MatrixRowContext>,
+    /// Whether any exported finding has tags, so templates can leave out an empty tags column.
+    pub has_tags: bool,
+    /// The names of the custom fields shown as columns in exports.
+    pub exported_fields: Vec<String>
+}
+
+impl ReportContext {
+    pub fn new(state: &StateData) -> Self {
+        let mut report = state.to_report();
+        report.findings.retain(|finding| !finding.draft);
+        state.finding_order.sort(&mut report.findings);
+
+        let summary = Summary::new(state);
+
+        let exported_fields: Vec<String> = state.custom_fields.iter()
+            .filter(|field| field.exported)
+            .map(|field| field.name.clone())
+            .collect();
+
+        let has_tags = report.findings.iter().any(|finding| !finding.tags.is_empty());
+
+        let targets = reference_targets(state);
+
+        let findings = report.findings.iter()
+            .enumerate()
+            .map(|(index, finding)| FindingContext {
+                finding: resolve_references(tag_code_blocks(embed_attachments(finding)), &targets),
+                other_attachments: finding.attachments.iter()
+                    .filter(|attachment| !is_referenced(finding, attachment))
+                    .map(|attachment| AttachmentContext {
+                        name: attachment.name.clone(),
+                        media_type: attachment.media_type.clone(),
+                        uri: attachment.data_uri()
+                    })
+                    .collect(),
+                display_id: display_id(index),
+                severity_name: finding.severity.map(|severity| severity.name()).unwrap_or("Unrated").to_string(),
+                severity_class: severity_class(finding.severity),
+                matrix_severity_name: finding.matrix_severity(&state.severity_matrix).map(|severity| severity.name().to_string()),
+                likelihood_name: finding.likelihood.map(|likelihood| likelihood.name()).unwrap_or("-").to_string(),
+                impact_name: finding.impact.map(|impact| impact.name()).unwrap_or("-").to_string(),
+                status_name: finding.status.name().to_string(),
+                icon: chart::severity_icon(finding.severity),
+                tags_text: finding.tags.join(", "),
+                exported_field_values: exported_fields.iter()
+                    .map(|name| finding.custom_fields.get(name).cloned().unwrap_or_default())
+                    .collect()
+            })
+            .collect();
+
+        let severity_matrix = Likelihood::ALL.iter()
+            .map(|likelihood| MatrixRowContext {
+                likelihood: likelihood.name().to_string(),
+                cells: Impact::ALL.iter()
+                    .map(|impact| {
+                        let severity = state.severity_matrix.get(*likelihood, *impact);
+
+                        MatrixCellContext {
+                            severity_name: severity.name().to_string(),
+                            severity_class: severity.name().to_lowercase()
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            report,
+            findings,
+            checklist_percent: summary.checklist_percent(),
+            charts: ChartsContext {
+                severity_donut: chart::severity_donut(&summary),
+                status_bar: chart::status_bar(&summary)
+            },
+            summary,
+            impacts: Impact::ALL.iter().map(|impact| impact.name().to_string()).collect(),
+            severity_matrix,
+            has_tags,
+            exported_fields
+        }
+    }
+}
+
+fn is_referenced(finding: &Finding, attachment: &Attachment) -> bool {
+    let target = format!("{}{}", ATTACHMENT_SCHEME, attachment.name);
+
+    [&finding.justification, &finding.description, &finding.recommendation, &finding.alleviation].iter()
+        .any(|text| text.contains(target.as_str()))
+}
+
+/// Replaces references to the finding's attachments with data URIs, so exports are self-contained.
+fn embed_attachments(finding: &Finding) -> Finding {
+    let mut finding = finding.clone();
+
+    for text in [&mut finding.justification, &mut finding.description, &mut finding.recommendation, &mut finding.alleviation].iter_mut() {
+        **text = embed_attachment_uris(text.as_str(), &finding.attachments);
+    }
+
+    finding
+}
+
+/// Returns the language of the code a finding refers to, from the files of its locations.
+pub fn code_language(finding: &Finding) -> Option<&'static str> {
+    // Findings from older workbooks only have the location text
+    if finding.locations.is_empty() {
+        return Location::parse_all(finding.location.as_str()).iter().find_map(|location| highlight::language_for_path(location.file.as_str()));
+    }
+
+    finding.locations.iter().find_map(|location| highlight::language_for_path(location.file.as_str()))
+}
+
+/// Gives the code blocks without a language hint the language of the finding's locations.
+fn tag_code_blocks(mut finding: Finding) -> Finding {
+    if let Some(language) = code_language(&finding) {
+        for text in [&mut finding.justification, &mut finding.description, &mut finding.recommendation, &mut finding.alleviation].iter_mut() {
+            **text = highlight::tag_code_blocks(text.as_str(), language);
+        }
+    }
+
+    finding
+}
+
+/// Replaces references to other findings with links to them in the report.
+fn resolve_references(mut finding: Finding, targets: &HashMap<usize, reference::Target>) -> Finding {
+    for text in [&mut finding.justification, &mut finding.description, &mut finding.recommendation, &mut finding.alleviation].iter_mut() {
+        **text = reference::resolve(text.as_str(), |id| targets.get(&id).cloned(), |_, target| format!("#{}", target.display_id));
+    }
+
+    finding
+}
+
+/// Replaces references to the attachments in a markdown text with their data URIs.
+pub fn embed_attachment_uris(text: &str, attachments: &[Attachment]) -> String {
+    let mut text = text.to_string();
+
+    // Longer names are replaced first so `a.png` does not match inside `a.png.old`
+    let mut attachments = attachments.to_vec();
+    attachments.sort_by(|lhs, rhs| rhs.name.len().cmp(&lhs.name.len()));
+
+    for attachment in &attachments {
+        let target = format!("{}{}", ATTACHMENT_SCHEME, attachment.name);
+
+        if text.contains(target.as_str()) {
+            text = text.replace(target.as_str(), attachment.data_uri().as_str());
+        }
+    }
+
+    text
+}
+
+/// Returns the class styling a severity in exported reports, as defined in `html/report.css`.
+pub fn severity_class(severity: Option<Severity>) -> String {
+    severity.map(|severity| severity.name()).unwrap_or("Unrated").to_lowercase()
+}
+
+/// Returns the identifier the finding at an index of the exported findings is displayed with.
+pub fn display_id(index: usize) -> String {
+    format!("XXX-{:02}", index + 1)
+}
+
+/// Returns the display ids of the exported findings by finding id. Findings are numbered in the
+/// order they are exported, so adding, removing or reordering findings renumbers the ones after.
+pub fn display_ids(state: &StateData) -> HashMap<usize, String> {
+    let mut findings: Vec<&Finding> = state.findings.values().filter(|finding| !finding.draft).collect();
+    findings.sort_by(|lhs, rhs| state.finding_order.compare(lhs, rhs));
+
+    findings.iter()
+        .enumerate()
+        .map(|(index, finding)| (finding.id, display_id(index)))
+        .collect()
+}
+
+/// Returns the display id of a finding, or its id for drafts, which are not numbered until they are accepted.
+pub fn finding_label(display_ids: &HashMap<usize, String>, id: usize) -> String {
+    display_ids.get(&id).cloned().unwrap_or_else(|| format!("Draft {}", id))
+}
+
+/// Returns what references to findings resolve to. Only exported findings are targets, so
+/// references to drafts are left missing like ones to removed findings.
+pub fn reference_targets(state: &StateData) -> HashMap<usize, reference::Target> {
+    display_ids(state).into_iter()
+        .map(|(id, display_id)| (id, reference::Target { display_id, title: state.findings[&id].title.clone() }))
+        .collect()
+}
+
+handlebars_helper!(table_cell: |text: str| text.replace("|", "\\|").replace("\r\n", " ").replace("\n", " "));
+
+/// Renders the report context with the supplied handlebars template source.
+pub fn render(template: &str, context: &ReportContext) -> Result<String, String> {
+    let mut handlebars = Handlebars::new();
+
+    // Exports are markdown, so values are inserted verbatim rather than HTML-escaped
+    handlebars.register_escape_fn(no_escape);
+    handlebars.register_helper("table_cell", Box::new(table_cell));
+
+    handlebars.render_template(template, context)
+        .map_err(|error| format!("Failed to render the export template: {}", error))
+}
+
+/// Loads the template configured for the workbook, falling back to the workspace template and
+/// then the default template.
+pub fn load(state: &StateData) -> Result<String, String> {
+    let path = state.export_template.clone()
+        .or_else(|| state.workspace.as_ref().and_then(|workspace| workspace.template_path()));
+
+    match path {
+        None => Ok(DEFAULT_TEMPLATE.to_string()),
+        Some(ref path) => std::fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read the export template '{}': {}", path, error))
+    }
+}
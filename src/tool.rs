@@ -0,0 +1,79 @@
+use crate::{
+    baseline,
+    report::{Finding, Impact, Location, Severity}
+};
+
+/// A single result reported by an external analysis tool, prior to becoming a draft finding.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolResult {
+    pub tool: String,
+    pub rule: String,
+    pub title: String,
+    pub class: String,
+    pub severity: Option<Severity>,
+    pub impact: Option<Impact>,
+    pub description: String,
+    pub locations: Vec<Location>,
+    pub snippet: Option<String>
+}
+
+impl ToolResult {
+    /// Creates a draft finding from the result, marked with the tool that generated it.
+    pub fn to_finding(&self, id: usize) -> Finding {
+        let mut finding = Finding::new(id);
+        finding.title = self.title.clone();
+        finding.class = self.class.clone();
+        finding.severity = self.severity;
+        finding.impact = self.impact;
+        finding.location = format_locations(&self.locations);
+        finding.locations = self.locations.clone();
+        finding.description = self.description.clone();
+        finding.draft = true;
+        finding.tool = Some(self.tool.clone());
+        finding.rule = Some(self.rule.clone());
+        finding.fingerprints = vec![baseline::fingerprint(self)];
+        finding
+    }
+}
+
+pub fn format_locations(locations: &[Location]) -> String {
+    locations.iter()
+        .map(|location| location.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Merges the lines of locations which refer to the same file, preserving the order files were first seen.
+pub fn merge_locations(locations: Vec<Location>) -> Vec<Location> {
+    let mut merged: Vec<Location> = vec![];
+
+    for location in locations {
+        match merged.iter_mut().find(|existing| existing.file == location.file) {
+            Some(existing) => existing.lines.extend(location.lines),
+            None => merged.push(location)
+        }
+    }
+
+    for location in merged.iter_mut() {
+        location.lines.sort();
+        location.lines.dedup();
+    }
+
+    merged
+}
+
+/// Converts a tool rule identifier such as `reentrancy-eth` into a readable title.
+pub fn rule_title(rule: &str) -> String {
+    rule.split(|c| c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
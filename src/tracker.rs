@@ -0,0 +1,271 @@
+use crate::{
+    markdown,
+    report::{Finding, Severity},
+    summary,
+    template,
+    StateData
+};
+
+use std::{collections::HashMap, path::Path};
+
+/// The payload of a GitHub issue, matching the body of the REST API's create issue request.
+#[derive(Clone, Debug, Serialize)]
+pub struct GitHubIssue {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>
+}
+
+/// A link to a file, or a range of lines within it, at the audited commit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Permalink {
+    pub text: String,
+    pub url: Option<String>
+}
+
+/// Normalizes a repository URL, converting SSH remotes such as `git@github.com:org/repo.git` to HTTPS.
+fn repository_url(repository: &str) -> Option<String> {
+    let repository = repository.trim().trim_end_matches('/');
+    let repository = repository.strip_suffix(".git").unwrap_or(repository);
+
+    if let Some(path) = repository.strip_prefix("git@") {
+        return Some(format!("https://{}", path.replacen(':', "/", 1)));
+    }
+
+    if repository.starts_with("https://") || repository.starts_with("http://") {
+        Some(repository.to_string())
+    } else {
+        None
+    }
+}
+
+/// Builds a permalink to a file at a commit, optionally anchored to a range of lines, using the
+/// URL layout of the repository's host.
+pub fn permalink(repository: &str, commit: &str, file: &str, lines: Option<(usize, usize)>) -> Option<String> {
+    let repository = repository_url(repository)?;
+    let file = file.trim_start_matches("./").trim_start_matches('/');
+
+    if commit.trim().is_empty() {
+        return None;
+    }
+
+    Some(
+        if repository.contains("bitbucket.org") {
+            let anchor = lines.map(|(start, end)| format!("#lines-{}:{}", start, end)).unwrap_or_default();
+            format!("{}/src/{}/{}{}", repository, commit, file, anchor)
+        } else if repository.contains("gitlab") {
+            let anchor = lines.map(|(start, end)| format!("#L{}-{}", start, end)).unwrap_or_default();
+            format!("{}/-/blob/{}/{}{}", repository, commit, file, anchor)
+        } else {
+            let anchor = lines.map(|(start, end)| if start == end { format!("#L{}", start) } else { format!("#L{}-L{}", start, end) }).unwrap_or_default();
+            format!("{}/blob/{}/{}{}", repository, commit, file, anchor)
+        }
+    )
+}
+
+/// Returns a permalink for each location of the finding at the commit its lines refer to, which is
+/// the first audited commit unless the location was pinned to another one.
+pub fn finding_permalinks(state: &StateData, finding: &Finding) -> Vec<Permalink> {
+    let commit = state.commit_hashes.first().map(|commit| commit.as_str()).unwrap_or("");
+
+    if finding.locations.is_empty() {
+        return summary::location_files(finding.location.as_str()).into_iter()
+            .map(|file| Permalink {
+                url: permalink(state.repository.as_str(), commit, file.as_str(), None),
+                text: file
+            })
+            .collect();
+    }
+
+    let mut permalinks = vec![];
+
+    for location in &finding.locations {
+        let commit = location.commit.as_deref().unwrap_or(commit);
+        let ranges = location.ranges();
+
+        if ranges.is_empty() {
+            permalinks.push(Permalink {
+                text: location.file.clone(),
+                url: permalink(state.repository.as_str(), commit, location.file.as_str(), None)
+            });
+        }
+
+        for (start, end) in ranges {
+            permalinks.push(Permalink {
+                text: if start == end { format!("{} L{}", location.file, start) } else { format!("{} L{}-{}", location.file, start, end) },
+                url: permalink(state.repository.as_str(), commit, location.file.as_str(), Some((start, end)))
+            });
+        }
+    }
+
+    permalinks
+}
+
+fn severity_name(finding: &Finding) -> &'static str {
+    finding.severity.map(|severity| severity.name()).unwrap_or("Unrated")
+}
+
+/// Converts text to a label which is valid in both GitHub and Jira, which disallows spaces.
+fn label(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+pub fn labels(finding: &Finding) -> Vec<String> {
+    let mut labels = vec!["audit".to_string(), format!("severity-{}", label(severity_name(finding)))];
+
+    if !finding.class.trim().is_empty() {
+        labels.push(label(finding.class.as_str()));
+    }
+
+    for tag in &finding.tags {
+        let tag = label(tag);
+
+        if !labels.contains(&tag) {
+            labels.push(tag);
+        }
+    }
+
+    labels
+}
+
+/// Maps a finding severity to a Jira priority.
+pub fn jira_priority(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::Critical) => "Highest",
+        Some(Severity::Major) => "High",
+        Some(Severity::Minor) => "Medium",
+        Some(Severity::Informational) => "Low",
+        None => "Medium"
+    }
+}
+
+pub fn issue_title(display_ids: &HashMap<usize, String>, finding: &Finding) -> String {
+    format!("[{}] {}", template::finding_label(display_ids, finding.id), finding.title)
+}
+
+/// Builds the markdown body of a GitHub issue for the finding.
+pub fn github_issue(state: &StateData, display_ids: &HashMap<usize, String>, finding: &Finding) -> GitHubIssue {
+    let mut body = String::new();
+
+    body.push_str("| Type | Severity | Status |\n");
+    body.push_str("|:-:|:-:|:-:|\n");
+    body.push_str(format!("| {} | {} | {} |\n\n", finding.class, severity_name(finding), finding.status.name()).as_str());
+
+    let permalinks = finding_permalinks(state, finding);
+
+    if !permalinks.is_empty() {
+        body.push_str("### Locations\n\n");
+
+        for permalink in permalinks {
+            match permalink.url {
+                Some(url) => body.push_str(format!("- [{}]({})\n", permalink.text, url).as_str()),
+                None => body.push_str(format!("- {}\n", permalink.text).as_str())
+            }
+        }
+
+        body.push('\n');
+    }
+
+    body.push_str(format!("### Description\n\n{}\n\n", finding.description.trim()).as_str());
+    body.push_str(format!("### Recommendation\n\n{}\n\n", finding.recommendation.trim()).as_str());
+    body.push_str(format!("_Exported from the audit report \"{}\"._\n", state.title).as_str());
+
+    GitHubIssue {
+        title: issue_title(display_ids, finding),
+        body,
+        labels: labels(finding)
+    }
+}
+
+/// Builds the description of a Jira issue for the finding, using Jira's wiki markup for links.
+fn jira_description(state: &StateData, finding: &Finding) -> String {
+    let mut description = String::new();
+
+    description.push_str(format!("Type: {}\nSeverity: {}\nStatus: {}\n\n", finding.class, severity_name(finding), finding.status.name()).as_str());
+
+    let permalinks = finding_permalinks(state, finding);
+
+    if !permalinks.is_empty() {
+        description.push_str("h3. Locations\n");
+
+        for permalink in permalinks {
+            match permalink.url {
+                Some(url) => description.push_str(format!("* [{}|{}]\n", permalink.text, url).as_str()),
+                None => description.push_str(format!("* {}\n", permalink.text).as_str())
+            }
+        }
+
+        description.push('\n');
+    }
+
+    description.push_str(format!("h3. Description\n{}\n\n", markdown::to_plain_text(finding.description.as_str())).as_str());
+    description.push_str(format!("h3. Recommendation\n{}\n", markdown::to_plain_text(finding.recommendation.as_str())).as_str());
+
+    description
+}
+
+/// Builds a CSV file for Jira's bulk issue importer. Jira reads repeated `Labels` columns as
+/// multiple labels, so every row has as many label columns as the finding with the most labels.
+pub fn jira_csv(state: &StateData, display_ids: &HashMap<usize, String>, findings: &[Finding]) -> Result<String, String> {
+    let label_columns = findings.iter().map(|finding| labels(finding).len()).max().unwrap_or(1);
+
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    let mut header = vec!["Summary", "Issue Type", "Priority", "Description"];
+    header.extend(std::iter::repeat("Labels").take(label_columns));
+    writer.write_record(&header).map_err(|error| error.to_string())?;
+
+    for finding in findings {
+        let mut record = vec![
+            issue_title(display_ids, finding),
+            "Bug".to_string(),
+            jira_priority(finding.severity).to_string(),
+            jira_description(state, finding)
+        ];
+
+        let mut labels = labels(finding);
+        labels.resize(label_columns, String::new());
+        record.extend(labels);
+
+        writer.write_record(&record).map_err(|error| error.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|error| error.to_string())?;
+    String::from_utf8(bytes).map_err(|error| error.to_string())
+}
+
+/// Writes the issue bundle to a directory: a markdown and JSON file for each GitHub issue, an
+/// `issues.json` file with every issue and a `jira.csv` file for Jira's bulk importer.
+pub fn write_bundle(state: &StateData, directory: &Path) -> Result<usize, String> {
+    let mut report = state.to_report();
+    report.findings.retain(|finding| !finding.draft);
+    state.finding_order.sort(&mut report.findings);
+
+    let github_directory = directory.join("github");
+    std::fs::create_dir_all(&github_directory).map_err(|error| error.to_string())?;
+
+    let display_ids = template::display_ids(state);
+    let mut issues = vec![];
+
+    for finding in &report.findings {
+        let issue = github_issue(state, &display_ids, finding);
+        let name = template::finding_label(&display_ids, finding.id);
+
+        let json = serde_json::to_string_pretty(&issue).map_err(|error| error.to_string())?;
+        std::fs::write(github_directory.join(format!("{}.json", name)), json).map_err(|error| error.to_string())?;
+        std::fs::write(github_directory.join(format!("{}.md", name)), format!("# {}\n\n{}", issue.title, issue.body)).map_err(|error| error.to_string())?;
+
+        issues.push(issue);
+    }
+
+    let json = serde_json::to_string_pretty(&issues).map_err(|error| error.to_string())?;
+    std::fs::write(github_directory.join("issues.json"), json).map_err(|error| error.to_string())?;
+
+    std::fs::write(directory.join("jira.csv"), jira_csv(state, &display_ids, &report.findings)?).map_err(|error| error.to_string())?;
+
+    Ok(report.findings.len())
+}
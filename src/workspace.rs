@@ -0,0 +1,287 @@
+use crate::report::{Auditor, CustomField, FindingOrder, Report, SeverityMatrix};
+
+use serde::de::IgnoredAny;
+
+use std::path::{Path, PathBuf};
+
+/// The file holding the workspace settings, in the workspace directory.
+pub const SETTINGS_FILE: &str = "workspace.json";
+
+/// The directory holding the export templates shared by the workspace.
+pub const TEMPLATES_DIRECTORY: &str = "templates";
+
+/// Settings shared by every workbook in a workspace.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Settings {
+    /// The auditors of the engagements, used by every workbook in the workspace.
+    #[serde(default)]
+    pub auditors: Vec<Auditor>,
+
+    /// The name of the export template in the templates directory used by workbooks without their own.
+    #[serde(default)]
+    pub export_template: Option<String>,
+
+    /// The severity matrix, finding order and checklist given to new workbooks.
+    #[serde(default)]
+    pub severity_matrix: Option<SeverityMatrix>,
+
+    #[serde(default)]
+    pub finding_order: Option<FindingOrder>,
+
+    #[serde(default)]
+    pub checklist: Vec<String>,
+
+    /// The custom fields defined on the findings of new workbooks.
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>
+}
+
+/// A workbook found in the workspace directory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub path: PathBuf,
+    /// The path relative to the workspace directory, without the extension.
+    pub name: String,
+    pub title: String,
+    pub findings: usize
+}
+
+#[derive(Debug)]
+pub struct Workspace {
+    pub directory: PathBuf,
+    pub settings: Settings,
+    pub workbooks: Vec<Entry>,
+    /// The workbook loaded from the workspace, which is saved before switching to another one.
+    pub current: Option<PathBuf>
+}
+
+/// The fields of a workbook listed in the workspace, read without building its findings.
+#[derive(Deserialize)]
+struct Header {
+    title: String,
+    #[serde(default)]
+    findings: Vec<IgnoredAny>
+}
+
+/// Returns true if a JSON file looks like a workbook, which always starts with its title, so other
+/// JSON files such as suppression baselines are skipped without parsing them.
+fn is_workbook_json(json: &str) -> bool {
+    json.trim_start()
+        .strip_prefix('{')
+        .map_or(false, |rest| rest.trim_start().starts_with("\"title\""))
+}
+
+/// Finds the workbooks in a directory and its subdirectories, skipping hidden directories and the templates.
+fn find_workbooks(directory: &Path, root: &Path, workbooks: &mut Vec<Entry>) -> Result<(), String> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(directory)
+        .map_err(|error| format!("Failed to read {}: {}", directory.display(), error))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+
+    paths.sort();
+
+    for path in paths {
+        let file_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+
+        if file_name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            if directory != root || file_name != TEMPLATES_DIRECTORY {
+                find_workbooks(&path, root, workbooks)?;
+            }
+
+            continue;
+        }
+
+        if path.extension().map(|extension| extension != "json").unwrap_or(true) || (directory == root && file_name == SETTINGS_FILE) {
+            continue;
+        }
+
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) if is_workbook_json(json.as_str()) => json,
+            _ => continue
+        };
+
+        let header: Header = match serde_json::from_str(json.as_str()) {
+            Err(_) => continue,
+            Ok(header) => header
+        };
+
+        let name = path.strip_prefix(root).unwrap_or(&path).with_extension("").to_string_lossy().replace('\\', "/");
+
+        workbooks.push(Entry {
+            path,
+            name,
+            title: header.title,
+            findings: header.findings.len()
+        });
+    }
+
+    Ok(())
+}
+
+impl Workspace {
+    /// Opens a directory as a workspace, reading its settings if it has any.
+    pub fn open(directory: &Path) -> Result<Workspace, String> {
+        let settings_path = directory.join(SETTINGS_FILE);
+
+        let settings = if settings_path.exists() {
+            let json = std::fs::read_to_string(&settings_path).map_err(|error| error.to_string())?;
+            serde_json::from_str(json.as_str()).map_err(|error| format!("Failed to read {}: {}", settings_path.display(), error))?
+        } else {
+            Settings::default()
+        };
+
+        let mut workspace = Workspace {
+            directory: directory.to_path_buf(),
+            settings,
+            workbooks: vec![],
+            current: None
+        };
+
+        workspace.scan()?;
+
+        Ok(workspace)
+    }
+
+    /// Refreshes the list of workbooks from the directory.
+    pub fn scan(&mut self) -> Result<(), String> {
+        let mut workbooks = vec![];
+        find_workbooks(&self.directory, &self.directory, &mut workbooks)?;
+
+        self.workbooks = workbooks;
+
+        Ok(())
+    }
+
+    pub fn save_settings(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.settings).map_err(|error| error.to_string())?;
+        std::fs::write(self.directory.join(SETTINGS_FILE), json).map_err(|error| error.to_string())
+    }
+
+    /// Returns the names of the export templates in the templates directory.
+    pub fn templates(&self) -> Vec<String> {
+        let mut templates: Vec<String> = std::fs::read_dir(self.directory.join(TEMPLATES_DIRECTORY))
+            .map(|entries| {
+                entries.filter_map(|entry| entry.ok())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|name| name.ends_with(".hbs"))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        templates.sort();
+        templates
+    }
+
+    /// Returns the path of the workspace export template, if one is set.
+    pub fn template_path(&self) -> Option<String> {
+        self.settings.export_template.as_ref()
+            .map(|name| self.directory.join(TEMPLATES_DIRECTORY).join(name).to_string_lossy().to_string())
+    }
+
+    /// Returns the path for a new workbook, which may be in a subdirectory such as `client/module`.
+    pub fn workbook_path(&self, name: &str) -> Result<PathBuf, String> {
+        let name = name.trim().trim_end_matches(".json");
+
+        if name.is_empty() || name.split('/').any(|part| part.is_empty() || part.starts_with('.')) {
+            return Err(format!("Invalid workbook name: '{}'", name));
+        }
+
+        let path = self.directory.join(format!("{}.json", name));
+
+        if path.exists() {
+            return Err(format!("A workbook named '{}' already exists", name));
+        }
+
+        Ok(path)
+    }
+
+    pub fn entry(&self, path: &Path) -> Option<&Entry> {
+        self.workbooks.iter().find(|entry| entry.path == path)
+    }
+
+    /// Applies the settings scoped to the workspace to a workbook opened from it.
+    pub fn apply_settings(&self, report: &mut Report) {
+        if !self.settings.auditors.is_empty() {
+            report.auditors = self.settings.auditors.clone();
+        }
+    }
+
+    /// Creates a workbook from the workspace defaults.
+    pub fn new_report(&self, title: &str) -> Report {
+        let mut report = Report {
+            title: title.to_string(),
+            auditors: vec![],
+            start_time: String::new(),
+            delivery_time: String::new(),
+            repository: String::new(),
+            commit_hashes: vec![],
+            repository_path: None,
+            fix_commit: None,
+            checklist: self.settings.checklist.iter().map(|text| (false, text.clone())).collect(),
+            overview: String::new(),
+            findings: vec![],
+            severity_matrix: self.settings.severity_matrix.clone().unwrap_or_default(),
+            finding_order: self.settings.finding_order.unwrap_or_default(),
+            export_template: None,
+            baseline: vec![],
+            lint_rules: Default::default(),
+            custom_fields: self.settings.custom_fields.clone()
+        };
+
+        self.apply_settings(&mut report);
+        report
+    }
+}
+
+/// Parses an auditor list with one `Name <email>` per line.
+pub fn parse_auditors(text: &str) -> Vec<Auditor> {
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| match (line.find('<'), line.rfind('>')) {
+            (Some(start), Some(end)) if start < end => Auditor {
+                name: line[..start].trim().to_string(),
+                email: line[start + 1..end].trim().to_string()
+            },
+
+            _ => Auditor {
+                name: line.to_string(),
+                email: String::new()
+            }
+        })
+        .collect()
+}
+
+pub fn format_auditors(auditors: &[Auditor]) -> String {
+    auditors.iter()
+        .map(|auditor| if auditor.email.is_empty() { auditor.name.clone() } else { format!("{} <{}>", auditor.name, auditor.email) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workbooks_are_recognized_by_their_first_field() {
+        assert!(is_workbook_json("{\"title\":\"Audit\",\"findings\":[]}"));
+        assert!(is_workbook_json("\n{\n  \"title\": \"Audit\"\n}"));
+
+        assert!(!is_workbook_json("[{\"fingerprint\":\"abc\"}]"));
+        assert!(!is_workbook_json("{\"auditors\":[]}"));
+        assert!(!is_workbook_json(""));
+    }
+
+    #[test]
+    fn header_counts_findings() {
+        let header: Header = serde_json::from_str("{\"title\":\"Audit\",\"findings\":[{\"id\":1},{\"id\":2}],\"overview\":\"\"}").unwrap();
+
+        assert_eq!(header.title, "Audit");
+        assert_eq!(header.findings.len(), 2);
+    }
+}